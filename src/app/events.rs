@@ -1,24 +1,30 @@
-use crate::ipc::Command;
+use crate::app::reload::ReloadedState;
+use crate::dmenu::DmenuSelection;
+use crate::ipc::IpcRequest;
 
 /// Events that the UI can send to the daemon.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub enum WindowEvent {
     RequestHide,
+    /// Entries picked in a dmenu selection
+    DmenuSelected(Vec<DmenuSelection>),
 }
 
 /// Unified event type for the daemon event loop.
 /// Combines IPC commands and window events into a single channel.
 #[derive(Debug, Clone)]
 pub enum DaemonEvent {
-    /// IPC command received from external client
-    Ipc(Command),
+    /// IPC command received from external client, awaiting a response
+    Ipc(IpcRequest),
     /// Window event from the UI
     Window(WindowEvent),
+    /// Config and applications were reloaded in the background
+    Reloaded(Box<ReloadedState>),
 }
 
-impl From<Command> for DaemonEvent {
-    fn from(cmd: Command) -> Self {
-        Self::Ipc(cmd)
+impl From<IpcRequest> for DaemonEvent {
+    fn from(request: IpcRequest) -> Self {
+        Self::Ipc(request)
     }
 }
 
@@ -0,0 +1,220 @@
+//! Live reloading of the config file and installed applications.
+//!
+//! A background watcher observes the config file and the application
+//! directories. Changes are debounced, then the config and application list
+//! are rebuilt off the main thread and handed to the daemon event loop as a
+//! [`DaemonEvent::Reloaded`]. The daemon applies them the next time the
+//! launcher window opens.
+
+use crate::app::{DaemonEvent, DaemonEventSender};
+use crate::config::{Config, config_path, load_config};
+use crate::desktop::cache::load_applications;
+use crate::desktop::get_application_dirs;
+use crate::items::ApplicationItem;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
+
+/// How long the filesystem must be quiet before a reload starts.
+/// Package managers and editors touch many files in quick succession.
+const DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Extension of the files that define applications.
+#[cfg(unix)]
+const APPLICATION_EXTENSION: &str = "desktop";
+#[cfg(windows)]
+const APPLICATION_EXTENSION: &str = "lnk";
+
+/// Freshly loaded daemon state.
+#[derive(Clone, Debug)]
+pub struct ReloadedState {
+    /// The new config, or None if it failed to load and the previous
+    /// config should be kept.
+    pub config: Option<Config>,
+    /// The rescanned application list.
+    pub applications: Vec<ApplicationItem>,
+}
+
+/// Load the config file and scan applications.
+///
+/// This blocks on disk I/O and should not be called on the UI thread.
+pub fn load_state() -> ReloadedState {
+    let config = match load_config() {
+        Ok(config) => Some(config),
+        Err(e) => {
+            error!(%e, "Failed to load config");
+            None
+        }
+    };
+
+    let applications: Vec<ApplicationItem> =
+        load_applications().into_iter().map(Into::into).collect();
+    info!(count = applications.len(), "Loaded applications");
+
+    ReloadedState {
+        config,
+        applications,
+    }
+}
+
+/// Load state on a background thread and send it to the daemon event loop.
+pub fn reload_in_background(event_tx: DaemonEventSender) {
+    std::thread::spawn(move || {
+        let state = load_state();
+        let _ = event_tx.send(DaemonEvent::Reloaded(Box::new(state)));
+    });
+}
+
+/// Keeps the filesystem watcher alive. Dropping it stops watching.
+pub struct ReloadWatcher {
+    _watcher: RecommendedWatcher,
+}
+
+/// Start watching the config file and application directories.
+///
+/// Every debounced batch of changes triggers a reload that is delivered
+/// to the daemon event loop via `event_tx`.
+pub fn spawn_watcher(event_tx: DaemonEventSender) -> notify::Result<ReloadWatcher> {
+    let config_file = config_path();
+    let app_dirs = get_application_dirs();
+
+    let (change_tx, change_rx) = mpsc::channel::<()>();
+    let filter_config = config_file.clone();
+    let filter_app_dirs = app_dirs.clone();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| match res {
+        Ok(event) if is_relevant(&event, filter_config.as_deref(), &filter_app_dirs) => {
+            debug!(paths = ?event.paths, "Detected change");
+            let _ = change_tx.send(());
+        }
+        Ok(_) => {}
+        Err(e) => warn!(%e, "File watcher error"),
+    })?;
+
+    // Watch the config directory rather than the file, since editors often
+    // replace the file instead of writing it in place. Fall back to the
+    // parent if the zlaunch directory does not exist yet.
+    if let Some(dir) = config_file.as_deref().and_then(nearest_existing_dir) {
+        watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+    }
+
+    for dir in app_dirs.iter().filter(|d| d.is_dir()) {
+        if let Err(e) = watcher.watch(dir, RecursiveMode::Recursive) {
+            warn!(%e, dir = %dir.display(), "Failed to watch application directory");
+        }
+    }
+
+    std::thread::spawn(move || {
+        while change_rx.recv().is_ok() {
+            // Wait until changes stop arriving before reloading
+            while change_rx.recv_timeout(DEBOUNCE).is_ok() {}
+
+            info!("Reloading config and applications");
+            let state = load_state();
+            if event_tx
+                .send(DaemonEvent::Reloaded(Box::new(state)))
+                .is_err()
+            {
+                // Daemon exited
+                break;
+            }
+        }
+    });
+
+    Ok(ReloadWatcher { _watcher: watcher })
+}
+
+/// Check whether a filesystem event should trigger a reload.
+fn is_relevant(event: &Event, config_file: Option<&Path>, app_dirs: &[PathBuf]) -> bool {
+    use notify::event::{MetadataKind, ModifyKind};
+
+    // Ignore reads (including our own rescans) and access time updates
+    match event.kind {
+        EventKind::Create(_) | EventKind::Remove(_) => {}
+        EventKind::Modify(ModifyKind::Metadata(MetadataKind::AccessTime)) => return false,
+        EventKind::Modify(_) => {}
+        _ => return false,
+    }
+
+    event.paths.iter().any(|path| {
+        if config_file == Some(path.as_path()) {
+            return true;
+        }
+        path.extension()
+            .is_some_and(|ext| ext == APPLICATION_EXTENSION)
+            && app_dirs.iter().any(|dir| path.starts_with(dir))
+    })
+}
+
+/// Find the closest ancestor directory of `path` that exists.
+fn nearest_existing_dir(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .skip(1)
+        .find(|dir| dir.is_dir())
+        .map(Path::to_path_buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{AccessKind, CreateKind, DataChange, MetadataKind, ModifyKind};
+
+    fn event(kind: EventKind, path: &str) -> Event {
+        Event::new(kind).add_path(PathBuf::from(path))
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_relevant_changes() {
+        let config = Some(Path::new("/home/u/.config/zlaunch/config.toml"));
+        let apps = [PathBuf::from("/usr/share/applications")];
+        let modify = EventKind::Modify(ModifyKind::Data(DataChange::Content));
+
+        assert!(is_relevant(
+            &event(modify, "/home/u/.config/zlaunch/config.toml"),
+            config,
+            &apps
+        ));
+        assert!(is_relevant(
+            &event(
+                EventKind::Create(CreateKind::File),
+                "/usr/share/applications/kde/org.kde.dolphin.desktop"
+            ),
+            config,
+            &apps
+        ));
+
+        // Other files next to the config, and non-desktop files
+        assert!(!is_relevant(
+            &event(modify, "/home/u/.config/zlaunch/config.toml.swp"),
+            config,
+            &apps
+        ));
+        assert!(!is_relevant(
+            &event(modify, "/usr/share/applications/mimeinfo.cache"),
+            config,
+            &apps
+        ));
+    }
+
+    #[test]
+    fn test_reads_ignored() {
+        let config = Some(Path::new("/home/u/.config/zlaunch/config.toml"));
+        let path = "/home/u/.config/zlaunch/config.toml";
+
+        assert!(!is_relevant(
+            &event(EventKind::Access(AccessKind::Read), path),
+            config,
+            &[]
+        ));
+        assert!(!is_relevant(
+            &event(
+                EventKind::Modify(ModifyKind::Metadata(MetadataKind::AccessTime)),
+                path
+            ),
+            config,
+            &[]
+        ));
+    }
+}
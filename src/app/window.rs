@@ -1,14 +1,18 @@
 use crate::app::{DaemonEvent, DaemonEventSender, WindowEvent};
 use crate::compositor::Compositor;
-use crate::items::{ApplicationItem, ListItem, WindowItem};
+use crate::config::{OutputChoice, config};
+use crate::dmenu::DmenuRequest;
+use crate::ipc::ShowOptions;
+use crate::items::{ApplicationItem, DmenuItem, ListItem, SubmenuItem, WindowItem};
 use crate::ui::LauncherView;
 use gpui::{
-    App, AppContext, Bounds, WindowBackgroundAppearance, WindowBounds, WindowDecorations,
-    WindowHandle, WindowKind, WindowOptions,
+    App, AppContext, Bounds, Context, PlatformDisplay, Window, WindowBackgroundAppearance,
+    WindowBounds, WindowDecorations, WindowHandle, WindowKind, WindowOptions,
     layer_shell::{Anchor, KeyboardInteractivity, Layer, LayerShellOptions},
     point, px, size,
 };
 use gpui_component::Root;
+use std::rc::Rc;
 use std::sync::Arc;
 use tracing::warn;
 
@@ -16,6 +20,7 @@ pub fn create_and_show_window(
     applications: Vec<ApplicationItem>,
     compositor: Arc<dyn Compositor>,
     event_tx: DaemonEventSender,
+    show_options: &ShowOptions,
     cx: &mut App,
 ) -> anyhow::Result<WindowHandle<Root>> {
     // Fetch open windows from compositor
@@ -26,15 +31,67 @@ pub fn create_and_show_window(
     let mut items: Vec<ListItem> = Vec::with_capacity(windows.len() + applications.len());
     items.extend(windows.into_iter().map(ListItem::Window));
     items.extend(applications.into_iter().map(ListItem::Application));
-    // Get display size - try displays() first, then primary_display(), then use huge fallback
-    // The layer shell will clamp to actual screen size, so overshooting is fine
-    //let display_size = cx
-    //    .displays()
-    //    .first()
-    //    .map(|d| d.bounds().size)
-    //    .or_else(|| cx.primary_display().map(|d| d.bounds().size))
-    //    .unwrap_or_else(|| size(px(7680.0), px(4320.0))); // 8K fallback - will be clamped
-    let display_size = size(px(7680.0), px(4320.0));
+
+    // Offer the workspace list when the compositor has workspaces
+    if compositor.has_workspaces() {
+        items.push(ListItem::Submenu(
+            SubmenuItem::list("submenu-workspaces", "Workspaces")
+                .with_description("Switch to a workspace")
+                .with_icon("workspaces"),
+        ));
+    }
+
+    open_launcher(items, compositor, event_tx, cx, |launcher, window, cx| {
+        launcher.apply_options(show_options, window, cx);
+    })
+}
+
+/// Open the launcher to pick from the entries of a `zlaunch dmenu` request.
+pub fn create_dmenu_window(
+    request: &DmenuRequest,
+    compositor: Arc<dyn Compositor>,
+    event_tx: DaemonEventSender,
+    cx: &mut App,
+) -> anyhow::Result<WindowHandle<Root>> {
+    use crate::ui::icon::resolve_icon_path;
+
+    let items = request
+        .entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let icon_path = entry.icon.as_deref().and_then(resolve_icon_path);
+            ListItem::Dmenu(DmenuItem::new(index, entry.text.clone(), icon_path))
+        })
+        .collect();
+
+    let select_tx = event_tx.clone();
+    let on_select = move |selection| {
+        let _ = select_tx.send(DaemonEvent::Window(WindowEvent::DmenuSelected(selection)));
+        let _ = select_tx.send(DaemonEvent::Window(WindowEvent::RequestHide));
+    };
+
+    open_launcher(items, compositor, event_tx, cx, |launcher, window, cx| {
+        launcher.start_dmenu(request, on_select, window, cx);
+    })
+}
+
+/// Open the launcher window with the given items.
+/// `setup` runs on the new view before it gets focus.
+fn open_launcher(
+    items: Vec<ListItem>,
+    compositor: Arc<dyn Compositor>,
+    event_tx: DaemonEventSender,
+    cx: &mut App,
+    setup: impl FnOnce(&mut LauncherView, &mut Window, &mut Context<LauncherView>),
+) -> anyhow::Result<WindowHandle<Root>> {
+    // Cover the chosen display. Without one the compositor picks the output
+    // and clamps the huge fallback to its size, so overshooting is fine.
+    let display = select_display(compositor.as_ref(), cx);
+    let display_size = display
+        .as_ref()
+        .map(|d| d.bounds().size)
+        .unwrap_or_else(|| size(px(7680.0), px(4320.0))); // 8K fallback - will be clamped
 
     let fullscreen_bounds = Bounds {
         origin: point(px(0.0), px(0.0)),
@@ -43,6 +100,7 @@ pub fn create_and_show_window(
 
     let options = WindowOptions {
         window_bounds: Some(WindowBounds::Windowed(fullscreen_bounds)),
+        display_id: display.map(|d| d.id()),
         titlebar: None,
         focus: true,
         show: true,
@@ -67,8 +125,9 @@ pub fn create_and_show_window(
         };
         let view = cx.new(|cx| LauncherView::new(items, compositor.clone(), on_hide, window, cx));
 
-        // Auto-focus the list/search input
+        // Open the requested view and auto-focus the list/search input
         view.update(cx, |launcher: &mut LauncherView, cx| {
+            setup(launcher, window, cx);
             launcher.focus(window, cx);
         });
 
@@ -82,6 +141,42 @@ pub fn create_and_show_window(
     Ok(window_handle)
 }
 
+/// Pick the display to open on from the `[window] output` setting.
+///
+/// Returns None to leave the choice to the compositor, which places the
+/// launcher on its focused output. Only a named output needs the outputs
+/// listed, so the default opens without querying the compositor.
+fn select_display(compositor: &dyn Compositor, cx: &App) -> Option<Rc<dyn PlatformDisplay>> {
+    let name = match &config().window.output {
+        OutputChoice::Focused => return None,
+        OutputChoice::Primary => return cx.primary_display(),
+        OutputChoice::Named(name) => name,
+    };
+
+    let outputs = match compositor.list_outputs() {
+        Ok(outputs) => outputs,
+        Err(e) => {
+            warn!(%e, "Failed to list outputs");
+            return None;
+        }
+    };
+    let Some(output) = outputs.iter().find(|o| &o.name == name) else {
+        warn!(%name, "Output is not connected, using the focused output");
+        return None;
+    };
+
+    // Displays have no names, so match them by their position in the layout
+    let origin = point(px(output.x as f32), px(output.y as f32));
+    let display = cx
+        .displays()
+        .into_iter()
+        .find(|d| d.bounds().origin == origin);
+    if display.is_none() {
+        warn!(output = %output.name, "No display at the output's position");
+    }
+    display
+}
+
 pub fn close_window(handle: &WindowHandle<Root>, cx: &mut App) {
     let _ = handle.update(cx, |_root, window, _cx| {
         window.remove_window();
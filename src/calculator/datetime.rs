@@ -0,0 +1,694 @@
+//! Date and time arithmetic.
+//!
+//! Handles input like `now + 36h`, `2026-12-24 - today`, `unix 1760000000`,
+//! `today to unix`, `week of 2026-12-24` and `3pm PST in Berlin`. Time zones
+//! are IANA names (`Europe/Berlin`), city names from the system tzdata
+//! (`Berlin`, `New York`) or common abbreviations (`PST`, `CET`).
+//!
+//! Points in time are remembered as `ans` by their unix timestamp, and
+//! durations by their length in seconds.
+
+use super::evaluation::{CalcResult, EvalError};
+use jiff::civil::{Date, Time};
+use jiff::tz::{Offset, TimeZone};
+use jiff::{Span, Timestamp, Unit, Zoned, ZonedDifference};
+use lazy_static::lazy_static;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use regex::Regex;
+
+lazy_static! {
+    /// Matches one token: a date, a time of day, a number, a word or an operator.
+    static ref TOKEN: Regex = Regex::new(
+        r"(?x)^(?:
+            (?P<date>\d{4}-\d{1,2}-\d{1,2})t?
+          | (?P<clock>\d{1,2}:\d{2}(?::\d{2})?)(?:\s*(?P<clock_meridiem>[ap]m)\b)?
+          | (?P<hour>\d{1,2})\s*(?P<meridiem>[ap]m)\b
+          | (?P<number>\d+)
+          | (?P<word>[a-z_][a-z_/]*)
+          | (?P<operator>[+-])
+        )"
+    )
+    .unwrap();
+}
+
+/// Common time zone abbreviations and their offsets from UTC in minutes.
+///
+/// Abbreviations are not tzdata names, so they always mean a fixed offset:
+/// `3pm PST` is 15:00 at UTC-8 even in summer.
+const ABBREVIATIONS: &[(&str, i32)] = &[
+    ("utc", 0),
+    ("gmt", 0),
+    ("wet", 0),
+    ("west", 60),
+    ("bst", 60),
+    ("cet", 60),
+    ("cest", 120),
+    ("eet", 120),
+    ("eest", 180),
+    ("msk", 180),
+    ("ist", 330),
+    ("sgt", 480),
+    ("hkt", 480),
+    ("awst", 480),
+    ("jst", 540),
+    ("kst", 540),
+    ("acst", 570),
+    ("aest", 600),
+    ("aedt", 660),
+    ("nzst", 720),
+    ("nzdt", 780),
+    ("hst", -600),
+    ("akst", -540),
+    ("akdt", -480),
+    ("pst", -480),
+    ("pdt", -420),
+    ("mst", -420),
+    ("mdt", -360),
+    ("cst", -360),
+    ("cdt", -300),
+    ("est", -300),
+    ("edt", -240),
+    ("ast", -240),
+    ("adt", -180),
+    ("nst", -210),
+    ("ndt", -150),
+];
+
+/// Words with a meaning of their own, which never start a time zone name.
+const KEYWORDS: &[&str] = &[
+    "in",
+    "into",
+    "to",
+    "as",
+    "of",
+    "unix",
+    "week",
+    "now",
+    "today",
+    "tomorrow",
+    "yesterday",
+];
+
+/// Words introducing the time zone or form to convert to.
+const TARGET_KEYWORDS: &[&str] = &["in", "into", "to", "as"];
+
+/// Duration units, in the order of `Parser::duration`'s amounts.
+const DURATION_UNITS: &[&[&str]] = &[
+    &["y", "yr", "yrs", "year", "years"],
+    &["mo", "month", "months"],
+    &["w", "wk", "wks", "week", "weeks"],
+    &["d", "day", "days"],
+    &["h", "hr", "hrs", "hour", "hours"],
+    &["m", "min", "mins", "minute", "minutes"],
+    &["s", "sec", "secs", "second", "seconds"],
+];
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Date(Date),
+    Time(Time),
+    Number(i64),
+    Word(String),
+    Plus,
+    Minus,
+}
+
+/// Split lowercased input into tokens.
+///
+/// Returns None if the input contains anything else, or an invalid date or
+/// time like `2026-02-30` or `25:00`.
+fn tokenize(input: &str) -> Option<Vec<Token>> {
+    let input = input.to_lowercase();
+    let mut tokens = Vec::new();
+    let mut rest = input.trim();
+
+    while !rest.is_empty() {
+        let captures = TOKEN.captures(rest)?;
+        let token = if let Some(date) = captures.name("date") {
+            let mut parts = date.as_str().split('-').map(|part| part.parse::<i16>());
+            let (year, month, day) = (
+                parts.next()?.ok()?,
+                parts.next()?.ok()?,
+                parts.next()?.ok()?,
+            );
+            Token::Date(Date::new(year, month as i8, day as i8).ok()?)
+        } else if let Some(clock) = captures.name("clock") {
+            let mut parts = clock.as_str().split(':').map(|part| part.parse::<i8>());
+            let hour = parts.next()?.ok()?;
+            let minute = parts.next()?.ok()?;
+            let second = parts.next().transpose().ok()?.unwrap_or(0);
+            let meridiem = captures.name("clock_meridiem").map(|m| m.as_str());
+            Token::Time(Time::new(to_24_hour(hour, meridiem)?, minute, second, 0).ok()?)
+        } else if let Some(hour) = captures.name("hour") {
+            let meridiem = captures.name("meridiem").map(|m| m.as_str());
+            let hour = to_24_hour(hour.as_str().parse().ok()?, meridiem)?;
+            Token::Time(Time::new(hour, 0, 0, 0).ok()?)
+        } else if let Some(number) = captures.name("number") {
+            Token::Number(number.as_str().parse().ok()?)
+        } else if let Some(word) = captures.name("word") {
+            Token::Word(word.as_str().to_string())
+        } else if &captures["operator"] == "+" {
+            Token::Plus
+        } else {
+            Token::Minus
+        };
+
+        tokens.push(token);
+        rest = rest[captures.get(0)?.end()..].trim_start();
+    }
+
+    Some(tokens)
+}
+
+/// Convert an hour written with `am` or `pm` to the 24-hour clock.
+fn to_24_hour(hour: i8, meridiem: Option<&str>) -> Option<i8> {
+    match meridiem {
+        None => Some(hour),
+        Some(_) if !(1..=12).contains(&hour) => None,
+        Some("am") => Some(hour % 12),
+        Some(_) => Some(hour % 12 + 12),
+    }
+}
+
+/// Find a time zone by abbreviation, IANA name or city name.
+fn time_zone(name: &str, now: &Zoned) -> Option<TimeZone> {
+    if name.is_empty() {
+        return None;
+    }
+    if name == "local" {
+        return Some(now.time_zone().clone());
+    }
+    if let Some(&(_, minutes)) = ABBREVIATIONS.iter().find(|(abbr, _)| *abbr == name) {
+        return Offset::from_seconds(minutes * 60).ok().map(TimeZone::fixed);
+    }
+    if let Ok(tz) = TimeZone::get(name) {
+        return Some(tz);
+    }
+
+    // City names like `berlin` or `new_york` are the last part of a tzdata name
+    let zone = jiff::tz::db().available().find(|zone| {
+        zone.as_str()
+            .rsplit('/')
+            .next()
+            .is_some_and(|city| city.eq_ignore_ascii_case(name))
+    })?;
+    TimeZone::get(zone.as_str()).ok()
+}
+
+fn out_of_range(_: jiff::Error) -> EvalError {
+    EvalError::Math("Date out of range")
+}
+
+/// A point in time, or a whole day like `today`.
+#[derive(Clone, Debug)]
+enum Moment {
+    Day(Date),
+    At(Zoned),
+}
+
+impl Moment {
+    /// Get the point in time, taking a day to start at local midnight.
+    fn zoned(self, now: &Zoned) -> Result<Zoned, EvalError> {
+        match self {
+            Self::Day(date) => date.to_zoned(now.time_zone().clone()).map_err(out_of_range),
+            Self::At(zoned) => Ok(zoned),
+        }
+    }
+
+    fn date(&self) -> Date {
+        match self {
+            Self::Day(date) => *date,
+            Self::At(zoned) => zoned.date(),
+        }
+    }
+
+    /// Add a duration. A day stays a day unless the duration has hours,
+    /// minutes or seconds.
+    fn add(self, span: Span, now: &Zoned) -> Result<Self, EvalError> {
+        let whole_days =
+            span.get_hours() == 0 && span.get_minutes() == 0 && span.get_seconds() == 0;
+        match self {
+            Self::Day(date) if whole_days => date.checked_add(span).map(Self::Day),
+            moment => moment.zoned(now)?.checked_add(span).map(Self::At),
+        }
+        .map_err(out_of_range)
+    }
+
+    /// Get the duration from `other` to this moment.
+    fn since(self, other: Self, now: &Zoned) -> Result<Value, EvalError> {
+        if let (Self::Day(date), Self::Day(other)) = (&self, &other) {
+            let span = *date - *other;
+            let seconds = i64::from(span.get_days()) * 86400;
+            return Ok(Value::Duration { span, seconds });
+        }
+
+        let (zoned, other) = (self.zoned(now)?, other.zoned(now)?);
+        let difference = ZonedDifference::new(&other)
+            .largest(Unit::Day)
+            .smallest(Unit::Second);
+        let span = zoned.since(difference).map_err(out_of_range)?;
+        let seconds = zoned.timestamp().as_second() - other.timestamp().as_second();
+        Ok(Value::Duration { span, seconds })
+    }
+}
+
+/// The result of a date and time query.
+#[derive(Clone, Debug)]
+enum Value {
+    Moment(Moment),
+    Duration {
+        span: Span,
+        seconds: i64,
+    },
+    /// Seconds since the unix epoch
+    Timestamp(i64),
+    /// The ISO week containing a date
+    Week(Date),
+}
+
+impl Value {
+    fn into_result(self, expression: String, now: &Zoned) -> Result<CalcResult, EvalError> {
+        let (display_result, clipboard_result, value) = match self {
+            Self::Moment(Moment::Day(date)) => {
+                let display = format!(
+                    "{} (week {})",
+                    date.strftime("%a %-d %b %Y"),
+                    date.iso_week_date().week()
+                );
+                let timestamp = Moment::Day(date).zoned(now)?.timestamp().as_second();
+                (display, date.to_string(), timestamp)
+            }
+            Self::Moment(Moment::At(zoned)) => {
+                let format = if zoned.second() == 0 {
+                    "%a %-d %b %Y %H:%M %Z"
+                } else {
+                    "%a %-d %b %Y %H:%M:%S %Z"
+                };
+                (
+                    zoned.strftime(format).to_string(),
+                    zoned.strftime("%Y-%m-%dT%H:%M:%S%:z").to_string(),
+                    zoned.timestamp().as_second(),
+                )
+            }
+            Self::Duration { span, seconds } => {
+                let formatted = format_span(&span);
+                (formatted.clone(), formatted, seconds)
+            }
+            Self::Timestamp(seconds) => (seconds.to_string(), seconds.to_string(), seconds),
+            Self::Week(date) => {
+                let week = date.iso_week_date();
+                (
+                    format!("Week {} of {}", week.week(), week.year()),
+                    format!("{}-W{:02}", week.year(), week.week()),
+                    i64::from(week.week()),
+                )
+            }
+        };
+
+        Ok(CalcResult::Success {
+            expression,
+            value: value as f64,
+            display_result,
+            clipboard_result,
+            exact: Some(BigRational::from_integer(BigInt::from(value))),
+            integer: None,
+        })
+    }
+}
+
+/// Format a duration like `1 day 4 hours`.
+fn format_span(span: &Span) -> String {
+    let sign = if span.is_negative() { "-" } else { "" };
+    let span = span.abs();
+    let parts: Vec<String> = [
+        (i64::from(span.get_years()), "year"),
+        (i64::from(span.get_months()), "month"),
+        (i64::from(span.get_weeks()), "week"),
+        (i64::from(span.get_days()), "day"),
+        (i64::from(span.get_hours()), "hour"),
+        (span.get_minutes(), "minute"),
+        (span.get_seconds(), "second"),
+    ]
+    .into_iter()
+    .filter(|&(amount, _)| amount != 0)
+    .map(|(amount, unit)| format!("{} {}{}", amount, unit, if amount == 1 { "" } else { "s" }))
+    .collect();
+
+    if parts.is_empty() {
+        "0 seconds".to_string()
+    } else {
+        format!("{}{}", sign, parts.join(" "))
+    }
+}
+
+/// Parser over the tokens of a date and time query:
+///
+/// ```text
+/// query    := "week" ["of"] sum | sum [target]
+/// target   := ("in" | "to" | "as") ("unix" | "week" | zone)
+/// sum      := moment (("+" | "-") duration)* ["-" moment]
+/// moment   := "now" | "today" | "tomorrow" | "yesterday" | "unix" number
+///           | date [time] [zone] | time [zone]
+/// duration := (number unit)+
+/// ```
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    now: &'a Zoned,
+    /// Whether the query computes something. `now` or `3pm` alone are
+    /// left to the app search.
+    calculation: bool,
+}
+
+impl<'a> Parser<'a> {
+    fn new(tokens: Vec<Token>, now: &'a Zoned) -> Self {
+        Self {
+            tokens,
+            pos: 0,
+            now,
+            calculation: false,
+        }
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    /// Consume the next token if it is one of `words`.
+    fn eat_word(&mut self, words: &[&str]) -> bool {
+        let found = matches!(self.tokens.get(self.pos), Some(Token::Word(w)) if words.contains(&w.as_str()));
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn parse(mut self) -> Result<Value, EvalError> {
+        let value = if self.eat_word(&["week"]) {
+            self.eat_word(&["of"]);
+            self.calculation = true;
+            match self.sum()? {
+                Value::Moment(moment) => Value::Week(moment.date()),
+                _ => return Err(EvalError::Syntax),
+            }
+        } else {
+            let value = self.sum()?;
+            self.target(value)?
+        };
+
+        if self.pos < self.tokens.len() || !self.calculation {
+            return Err(EvalError::Syntax);
+        }
+        Ok(value)
+    }
+
+    fn target(&mut self, value: Value) -> Result<Value, EvalError> {
+        if !self.eat_word(TARGET_KEYWORDS) {
+            return Ok(value);
+        }
+        self.calculation = true;
+
+        let Value::Moment(moment) = value else {
+            return Err(EvalError::Syntax);
+        };
+        if self.eat_word(&["unix"]) {
+            let zoned = moment.zoned(self.now)?;
+            return Ok(Value::Timestamp(zoned.timestamp().as_second()));
+        }
+        if self.eat_word(&["week"]) {
+            return Ok(Value::Week(moment.date()));
+        }
+
+        let tz = self.zone(true).ok_or(EvalError::Syntax)?;
+        let zoned = moment.zoned(self.now)?.with_time_zone(tz);
+        Ok(Value::Moment(Moment::At(zoned)))
+    }
+
+    fn sum(&mut self) -> Result<Value, EvalError> {
+        let mut moment = self.moment()?;
+        loop {
+            let subtract = match self.tokens.get(self.pos) {
+                Some(Token::Plus) => false,
+                Some(Token::Minus) => true,
+                _ => return Ok(Value::Moment(moment)),
+            };
+            self.pos += 1;
+            self.calculation = true;
+
+            if let Some(span) = self.duration()? {
+                let span = if subtract { span.negate() } else { span };
+                moment = moment.add(span, self.now)?;
+            } else if subtract {
+                let other = self.moment()?;
+                return moment.since(other, self.now);
+            } else {
+                return Err(EvalError::Syntax);
+            }
+        }
+    }
+
+    fn moment(&mut self) -> Result<Moment, EvalError> {
+        let now = self.now;
+        match self.next() {
+            Some(Token::Word(word)) => match word.as_str() {
+                "now" => Ok(Moment::At(now.clone())),
+                "today" => Ok(Moment::Day(now.date())),
+                "tomorrow" => now.date().tomorrow().map(Moment::Day).map_err(out_of_range),
+                "yesterday" => now
+                    .date()
+                    .yesterday()
+                    .map(Moment::Day)
+                    .map_err(out_of_range),
+                "unix" => {
+                    self.calculation = true;
+                    let Some(Token::Number(seconds)) = self.next() else {
+                        return Err(EvalError::Syntax);
+                    };
+                    let timestamp = Timestamp::from_second(seconds).map_err(out_of_range)?;
+                    Ok(Moment::At(timestamp.to_zoned(now.time_zone().clone())))
+                }
+                _ => Err(EvalError::Syntax),
+            },
+            Some(Token::Date(date)) => {
+                // A date literal alone is worth showing with its weekday
+                self.calculation = true;
+                let time = match self.tokens.get(self.pos) {
+                    Some(&Token::Time(time)) => {
+                        self.pos += 1;
+                        Some(time)
+                    }
+                    _ => None,
+                };
+                let tz = self.zone(false);
+                if time.is_none() && tz.is_none() {
+                    return Ok(Moment::Day(date));
+                }
+                date.to_datetime(time.unwrap_or(Time::midnight()))
+                    .to_zoned(tz.unwrap_or_else(|| now.time_zone().clone()))
+                    .map(Moment::At)
+                    .map_err(out_of_range)
+            }
+            Some(Token::Time(time)) => {
+                let tz = self.zone(false);
+                self.calculation |= tz.is_some();
+                let tz = tz.unwrap_or_else(|| now.time_zone().clone());
+                now.with_time_zone(tz.clone())
+                    .date()
+                    .to_datetime(time)
+                    .to_zoned(tz)
+                    .map(Moment::At)
+                    .map_err(out_of_range)
+            }
+            _ => Err(EvalError::Syntax),
+        }
+    }
+
+    /// Parse a duration like `1h 30m` or `2 weeks`, if one follows.
+    fn duration(&mut self) -> Result<Option<Span>, EvalError> {
+        // Years, months, weeks, days, hours, minutes and seconds
+        let mut amounts = [0i64; 7];
+        let start = self.pos;
+
+        while let (Some(&Token::Number(amount)), Some(Token::Word(unit))) =
+            (self.tokens.get(self.pos), self.tokens.get(self.pos + 1))
+        {
+            let Some(index) = DURATION_UNITS
+                .iter()
+                .position(|names| names.contains(&unit.as_str()))
+            else {
+                break;
+            };
+            amounts[index] = amounts[index]
+                .checked_add(amount)
+                .ok_or(EvalError::Math("Duration out of range"))?;
+            self.pos += 2;
+        }
+
+        if self.pos == start {
+            return Ok(None);
+        }
+
+        let [years, months, weeks, days, hours, minutes, seconds] = amounts;
+        Span::new()
+            .try_years(years)
+            .and_then(|span| span.try_months(months))
+            .and_then(|span| span.try_weeks(weeks))
+            .and_then(|span| span.try_days(days))
+            .and_then(|span| span.try_hours(hours))
+            .and_then(|span| span.try_minutes(minutes))
+            .and_then(|span| span.try_seconds(seconds))
+            .map(Some)
+            .map_err(|_| EvalError::Math("Duration out of range"))
+    }
+
+    /// Parse a time zone name, which may span several words like
+    /// `new york`. With `whole`, all remaining words must form the name.
+    fn zone(&mut self, whole: bool) -> Option<TimeZone> {
+        let words: Vec<&str> = self.tokens[self.pos.min(self.tokens.len())..]
+            .iter()
+            .map_while(|token| match token {
+                Token::Word(word) if !KEYWORDS.contains(&word.as_str()) => Some(word.as_str()),
+                _ => None,
+            })
+            .collect();
+
+        let shortest = if whole { words.len() } else { 1 };
+        let (len, tz) = (shortest..=words.len())
+            .rev()
+            .find_map(|len| Some((len, time_zone(&words[..len].join("_"), self.now)?)))?;
+        self.pos += len;
+        Some(tz)
+    }
+}
+
+/// Evaluate a date and time query relative to `now`.
+fn evaluate_at(input: &str, now: &Zoned) -> Option<CalcResult> {
+    let input = input.trim();
+    let tokens = tokenize(input)?;
+    let result = Parser::new(tokens, now)
+        .parse()
+        .and_then(|value| value.into_result(input.to_string(), now));
+
+    match result {
+        Ok(result) => Some(result),
+        Err(EvalError::Syntax) => None,
+        Err(EvalError::Math(message)) => Some(CalcResult::Error {
+            expression: input.to_string(),
+            message: message.to_string(),
+        }),
+    }
+}
+
+/// Check if the input is a date and time query.
+pub fn is_datetime(input: &str) -> bool {
+    tokenize(input).is_some_and(|tokens| {
+        !tokens.is_empty()
+            && Parser::new(tokens, &Zoned::now()).parse().err() != Some(EvalError::Syntax)
+    })
+}
+
+/// Evaluate a date and time query like `now + 36h` or `3pm PST in Berlin`.
+///
+/// Returns None if the input is not a date and time query.
+pub fn evaluate_datetime(input: &str) -> Option<CalcResult> {
+    evaluate_at(input, &Zoned::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> Zoned {
+        "2026-10-18T14:30:00+02:00[Europe/Berlin]".parse().unwrap()
+    }
+
+    fn eval(input: &str) -> Option<(String, String)> {
+        match evaluate_at(input, &now())? {
+            CalcResult::Success {
+                display_result,
+                clipboard_result,
+                ..
+            } => Some((display_result, clipboard_result)),
+            CalcResult::Error { message, .. } => Some((message, String::new())),
+        }
+    }
+
+    fn display(input: &str) -> String {
+        eval(input).map(|(display, _)| display).unwrap_or_default()
+    }
+
+    fn clipboard(input: &str) -> String {
+        eval(input)
+            .map(|(_, clipboard)| clipboard)
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(display("now + 36h"), "Tue 20 Oct 2026 02:30 CEST");
+        assert_eq!(clipboard("now + 36h"), "2026-10-20T02:30:00+02:00");
+        assert_eq!(display("today + 1w 2d"), "Tue 27 Oct 2026 (week 44)");
+        assert_eq!(clipboard("tomorrow - 1 month"), "2026-09-19");
+        // Crossing the end of summer time keeps the wall clock
+        assert_eq!(display("now + 1 week"), "Sun 25 Oct 2026 14:30 CET");
+        assert_eq!(display("2026-12-24"), "Thu 24 Dec 2026 (week 52)");
+        assert_eq!(
+            display("2026-12-24 18:00 + 90 min"),
+            "Thu 24 Dec 2026 19:30 CET"
+        );
+    }
+
+    #[test]
+    fn test_differences() {
+        assert_eq!(display("2026-12-24 - today"), "67 days");
+        assert_eq!(display("2026-01-01 - 2026-12-31"), "-364 days");
+        assert_eq!(display("2026-10-20 9am - now"), "1 day 18 hours 30 minutes");
+        let result = evaluate_at("tomorrow - today", &now()).unwrap();
+        assert_eq!(result.value(), Some(86400.0));
+    }
+
+    #[test]
+    fn test_unix_timestamps() {
+        assert_eq!(display("unix 1760000000"), "Thu 9 Oct 2025 10:53:20 CEST");
+        assert_eq!(display("unix 0 in utc"), "Thu 1 Jan 1970 00:00 UTC");
+        assert_eq!(display("2026-10-18 to unix"), "1792274400");
+        assert_eq!(display("now as unix"), "1792326600");
+    }
+
+    #[test]
+    fn test_weeks() {
+        assert_eq!(display("week of 2026-12-24"), "Week 52 of 2026");
+        assert_eq!(clipboard("week 2027-01-01"), "2026-W53");
+        assert_eq!(clipboard("today in week"), "2026-W42");
+    }
+
+    #[test]
+    fn test_time_zones() {
+        assert_eq!(display("3pm PST in Berlin"), "Mon 19 Oct 2026 01:00 CEST");
+        assert_eq!(display("now in new york"), "Sun 18 Oct 2026 08:30 EDT");
+        assert_eq!(
+            display("12:15 am in Asia/Tokyo"),
+            "Sun 18 Oct 2026 07:15 JST"
+        );
+        assert_eq!(
+            clipboard("2026-12-24 18:00 utc"),
+            "2026-12-24T18:00:00+00:00"
+        );
+        assert_eq!(display("now in atlantis"), "");
+    }
+
+    #[test]
+    fn test_not_datetime() {
+        assert_eq!(eval("now"), None);
+        assert_eq!(eval("3pm"), None);
+        assert_eq!(eval("today + 2"), None);
+        assert_eq!(eval("2h 30m"), None);
+        assert_eq!(eval("2026-02-30"), None);
+        assert_eq!(eval("13pm in utc"), None);
+        assert!(!is_datetime("firefox"));
+        assert!(is_datetime("now + 36h"));
+    }
+}
@@ -3,6 +3,9 @@
 //! Determines whether user input looks like a mathematical expression
 //! that should be evaluated by the calculator.
 
+use super::datetime::is_datetime;
+use super::programmer::is_integer_expression;
+use super::units::is_conversion;
 use lazy_static::lazy_static;
 use regex::Regex;
 
@@ -15,8 +18,9 @@ const MATH_FUNCTIONS: &[&str] = &[
 lazy_static! {
     /// Matches strings containing only math-safe characters.
     /// Allows: digits, whitespace, operators, parentheses, letters (for functions), dots, commas.
+    /// `!` is the factorial.
     static ref MATH_SAFE_CHARS: Regex = Regex::new(
-        r"^[\d\s\.\,\+\-\*/%\^()a-zA-Z_]+$"
+        r"^[\d\s\.\,\+\-\*/%\^!()a-zA-Z_]+$"
     ).unwrap();
 
     /// Matches parentheses that contain something (not empty).
@@ -27,7 +31,8 @@ lazy_static! {
 
 /// Check if input looks like a potential calculator expression.
 ///
-/// Returns `true` if the input:
+/// Returns `true` if the input is a unit conversion, an integer expression
+/// for the programmer calculator or a date and time query, or:
 /// 1. Contains only math-safe characters
 /// 2. Has at least one operator, function call, or non-trivial parentheses
 /// 3. Is not just a plain number
@@ -41,6 +46,21 @@ pub fn looks_like_expression(input: &str) -> bool {
         return false;
     }
 
+    // Unit conversions use characters like ° and ²
+    if is_conversion(trimmed) {
+        return true;
+    }
+
+    // Bitwise operators and radix literals like `0xff` are not math-safe
+    if is_integer_expression(trimmed) {
+        return true;
+    }
+
+    // Dates like `2026-12-24` would otherwise read as subtractions
+    if is_datetime(trimmed) {
+        return true;
+    }
+
     // Must contain only math-safe characters
     if !MATH_SAFE_CHARS.is_match(trimmed) {
         return false;
@@ -71,12 +91,13 @@ fn is_plain_number(input: &str) -> bool {
 
 /// Check if input contains a binary operator.
 fn has_operator(input: &str) -> bool {
-    // Check for +, *, /, ^, %
+    // Check for +, *, /, ^, % and the postfix factorial
     if input.contains('+')
         || input.contains('*')
         || input.contains('/')
         || input.contains('^')
         || input.contains('%')
+        || input.contains('!')
     {
         return true;
     }
@@ -135,6 +156,8 @@ mod tests {
         assert!(looks_like_expression("10 % 3"));
         assert!(looks_like_expression("(2 + 3) * 4"));
         assert!(looks_like_expression("10 - 5"));
+        assert!(looks_like_expression("30!"));
+        assert!(looks_like_expression("1/3 as fraction"));
     }
 
     #[test]
@@ -146,6 +169,29 @@ mod tests {
         assert!(looks_like_expression("log(10, 100)"));
     }
 
+    #[test]
+    fn test_conversions_accepted() {
+        assert!(looks_like_expression("5 mi in km"));
+        assert!(looks_like_expression("72 °F to °C"));
+        assert!(looks_like_expression("2h 30m"));
+        assert!(!looks_like_expression("30 days"));
+    }
+
+    #[test]
+    fn test_integer_expressions_accepted() {
+        assert!(looks_like_expression("0xff & 0b1010"));
+        assert!(looks_like_expression("1 << 12 in hex"));
+        assert!(looks_like_expression("~0"));
+        assert!(!looks_like_expression("salt & pepper"));
+    }
+
+    #[test]
+    fn test_datetime_queries_accepted() {
+        assert!(looks_like_expression("unix 1760000000"));
+        assert!(looks_like_expression("3pm PST in Berlin"));
+        assert!(!looks_like_expression("now"));
+    }
+
     #[test]
     fn test_invalid_input_rejected() {
         assert!(!looks_like_expression(""));
@@ -1,10 +1,22 @@
-//! Expression evaluation using fasteval.
+//! Expression evaluation, exact where possible with fasteval as fallback.
 //!
-//! Wraps fasteval to provide a simple interface for evaluating
-//! mathematical expressions and formatting results.
+//! Provides a simple interface for evaluating mathematical expressions
+//! and formatting results.
 
+use super::exact::{self, Number, Scope, format_decimal, format_fraction};
+use super::programmer::IntegerForms;
+use lazy_static::lazy_static;
+use num_rational::BigRational;
+use num_traits::ToPrimitive;
+use regex::Regex;
 use std::collections::BTreeMap;
 
+lazy_static! {
+    /// Matches a trailing request for a fraction, e.g. `as fraction`.
+    static ref FRACTION_SUFFIX: Regex =
+        Regex::new(r"(?i)\s+(?:in|to|as)\s+(?:a\s+)?fractions?\s*$").unwrap();
+}
+
 /// Result of evaluating a calculator expression.
 #[derive(Clone, Debug)]
 pub enum CalcResult {
@@ -12,12 +24,16 @@ pub enum CalcResult {
     Success {
         /// The original expression.
         expression: String,
-        /// The numeric value.
+        /// The numeric value, approximated if the result is exact.
         value: f64,
         /// Formatted for display (with thousand separators).
         display_result: String,
         /// Formatted for clipboard (raw number).
         clipboard_result: String,
+        /// The exact value, when no approximation was needed.
+        exact: Option<BigRational>,
+        /// The result in each number base, for integer expressions.
+        integer: Option<IntegerForms>,
     },
     /// Expression evaluated but result is not a valid number.
     Error {
@@ -59,78 +75,182 @@ impl CalcResult {
             Self::Error { .. } => None,
         }
     }
+
+    /// Get the value as an f64, approximating exact values (only for
+    /// successful results).
+    pub fn value(&self) -> Option<f64> {
+        match self {
+            Self::Success { value, .. } => Some(*value),
+            Self::Error { .. } => None,
+        }
+    }
+
+    /// Get the exact value (only for results computed without approximation).
+    pub fn exact(&self) -> Option<&BigRational> {
+        match self {
+            Self::Success { exact, .. } => exact.as_ref(),
+            Self::Error { .. } => None,
+        }
+    }
+
+    /// Get the value, exact if possible (only for successful results).
+    pub(super) fn number(&self) -> Option<Number> {
+        match self {
+            Self::Success {
+                exact: Some(exact), ..
+            } => Some(Number::Exact(exact.clone())),
+            Self::Success { value, .. } => Some(Number::Approx(*value)),
+            Self::Error { .. } => None,
+        }
+    }
+
+    /// Replace the expression shown with the result.
+    pub(super) fn with_expression(mut self, input: &str) -> Self {
+        match &mut self {
+            Self::Success { expression, .. } | Self::Error { expression, .. } => {
+                *expression = input.to_string();
+            }
+        }
+        self
+    }
+
+    /// Get the result in each number base (only for integer results).
+    pub fn integer(&self) -> Option<&IntegerForms> {
+        match self {
+            Self::Success { integer, .. } => integer.as_ref(),
+            Self::Error { .. } => None,
+        }
+    }
+}
+
+/// Why an expression has no value.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum EvalError {
+    /// The input is not an expression the evaluator understands
+    Syntax,
+    /// The input parsed but cannot be evaluated
+    Math(&'static str),
 }
 
 /// Evaluate a mathematical expression.
 ///
+/// Arithmetic is exact where possible, so `0.1 + 0.2` is `0.3` and `30!`
+/// keeps every digit. A trailing `as fraction` shows the exact result as
+/// a fraction. Expressions the exact evaluator does not understand are
+/// left to fasteval.
+///
 /// Returns `Some(CalcResult)` if the expression can be parsed,
 /// or `None` if parsing fails entirely.
 pub fn evaluate_expression(input: &str) -> Option<CalcResult> {
+    evaluate_expression_in(input, &Scope::default())
+}
+
+/// Evaluate a mathematical expression that may use the names in `scope`.
+pub(super) fn evaluate_expression_in(input: &str, scope: &Scope) -> Option<CalcResult> {
     let expression = input.trim().to_string();
+    let (body, as_fraction) = match FRACTION_SUFFIX.find(&expression) {
+        Some(suffix) => (&expression[..suffix.start()], true),
+        None => (expression.as_str(), false),
+    };
+
+    match exact::evaluate(body, scope) {
+        Ok(Number::Exact(value)) => Some(exact_result(expression, value, as_fraction)),
+        Ok(Number::Approx(value)) => Some(approximate_result(expression, value)),
+        Err(EvalError::Math(message)) => Some(CalcResult::Error {
+            expression,
+            message: message.to_string(),
+        }),
+        Err(EvalError::Syntax) if as_fraction => None,
+        Err(EvalError::Syntax) => {
+            let mut namespace: BTreeMap<String, f64> = scope
+                .variables
+                .iter()
+                .map(|(name, value)| (name.clone(), value.to_f64()))
+                .collect();
 
-    // Use an empty namespace (no custom variables)
-    let mut namespace = BTreeMap::<String, f64>::new();
-
-    match fasteval::ez_eval(&expression, &mut namespace) {
-        Ok(value) => {
-            if value.is_nan() {
-                Some(CalcResult::Error {
-                    expression,
-                    message: "Not a Number".to_string(),
-                })
-            } else if value.is_infinite() {
-                let msg = if value.is_sign_positive() {
-                    "Infinity"
-                } else {
-                    "-Infinity"
-                };
-                Some(CalcResult::Error {
-                    expression,
-                    message: msg.to_string(),
-                })
-            } else {
-                Some(CalcResult::Success {
-                    expression,
-                    display_result: format_display(value),
-                    clipboard_result: format_clipboard(value),
-                    value,
-                })
+            match fasteval::ez_eval(&expression, &mut namespace) {
+                Ok(value) => Some(approximate_result(expression, value)),
+                Err(_) => None, // Parse error - silently fail
             }
         }
-        Err(_) => None, // Parse error - silently fail
     }
 }
 
-/// Format a number for display with thousand separators.
-fn format_display(value: f64) -> String {
-    if value.fract() == 0.0 && value.abs() < 1e15 {
-        // Integer display with thousand separators
-        format_with_separators(value as i64)
+/// Build the result of an exact value, shown as a decimal or a fraction.
+fn exact_result(expression: String, value: BigRational, as_fraction: bool) -> CalcResult {
+    let (display_result, clipboard_result) = if as_fraction {
+        let fraction = format_fraction(&value);
+        let display = match fraction.split_once('/') {
+            Some((numer, denom)) => format!("{}/{}", format_digits(numer), format_digits(denom)),
+            None => format_digits(&fraction),
+        };
+        (display, fraction)
     } else {
-        // Decimal display
-        let formatted = format!("{:.10}", value);
-        let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+        let decimal = format_decimal(&value);
+        (format_grouped(&decimal), decimal)
+    };
+
+    CalcResult::Success {
+        expression,
+        value: value.to_f64().unwrap_or(f64::NAN),
+        display_result,
+        clipboard_result,
+        exact: Some(value),
+        integer: None,
+    }
+}
 
-        // Add thousand separators to the integer part
-        if let Some(dot_pos) = trimmed.find('.') {
-            let (int_part, dec_part) = trimmed.split_at(dot_pos);
-            let int_val: i64 = int_part.parse().unwrap_or(0);
-            format!("{}{}", format_with_separators(int_val), dec_part)
+/// Build the result of an f64 value, which may be NaN or infinite.
+fn approximate_result(expression: String, value: f64) -> CalcResult {
+    if value.is_nan() {
+        CalcResult::Error {
+            expression,
+            message: "Not a Number".to_string(),
+        }
+    } else if value.is_infinite() {
+        let msg = if value.is_sign_positive() {
+            "Infinity"
         } else {
-            let int_val: i64 = trimmed.parse().unwrap_or(0);
-            format_with_separators(int_val)
+            "-Infinity"
+        };
+        CalcResult::Error {
+            expression,
+            message: msg.to_string(),
+        }
+    } else {
+        CalcResult::Success {
+            expression,
+            display_result: format_display(value),
+            clipboard_result: format_clipboard(value),
+            value,
+            exact: None,
+            integer: None,
         }
     }
 }
 
-/// Format an integer with thousand separators.
-fn format_with_separators(value: i64) -> String {
-    let is_negative = value < 0;
-    let abs_value = value.abs();
-    let s = abs_value.to_string();
+/// Format a number for display with thousand separators.
+pub(super) fn format_display(value: f64) -> String {
+    format_grouped(&format_clipboard(value))
+}
+
+/// Add thousand separators to the integer part of a number like `-1234.5`.
+fn format_grouped(number: &str) -> String {
+    match number.split_once('.') {
+        Some((whole, fraction)) => format!("{}.{}", format_digits(whole), fraction),
+        None => format_digits(number),
+    }
+}
+
+/// Add thousand separators to a decimal integer like `-1234567`.
+pub(super) fn format_digits(digits: &str) -> String {
+    let (sign, digits) = match digits.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", digits),
+    };
 
     let mut result = String::new();
-    for (i, c) in s.chars().rev().enumerate() {
+    for (i, c) in digits.chars().rev().enumerate() {
         if i > 0 && i % 3 == 0 {
             result.push(',');
         }
@@ -138,15 +258,11 @@ fn format_with_separators(value: i64) -> String {
     }
 
     let formatted: String = result.chars().rev().collect();
-    if is_negative {
-        format!("-{}", formatted)
-    } else {
-        formatted
-    }
+    format!("{}{}", sign, formatted)
 }
 
 /// Format a number for clipboard (raw number, no separators).
-fn format_clipboard(value: f64) -> String {
+pub(super) fn format_clipboard(value: f64) -> String {
     if value.fract() == 0.0 && value.abs() < 1e15 {
         format!("{}", value as i64)
     } else {
@@ -186,6 +302,39 @@ mod tests {
         assert!(result.display().starts_with("0.333"));
     }
 
+    #[test]
+    fn test_exact_results() {
+        let result = evaluate_expression("0.1 + 0.2").unwrap();
+        assert_eq!(result.display(), "0.3");
+        assert_eq!(result.exact(), Some(&BigRational::new(3.into(), 10.into())));
+
+        let result = evaluate_expression("30!").unwrap();
+        assert_eq!(
+            result.display(),
+            "265,252,859,812,191,058,636,308,480,000,000"
+        );
+        assert_eq!(
+            result.clipboard(),
+            Some("265252859812191058636308480000000")
+        );
+
+        let result = evaluate_expression("2/6 + 1 as fraction").unwrap();
+        assert_eq!(result.display(), "4/3");
+        assert_eq!(result.clipboard(), Some("4/3"));
+        assert!((result.value().unwrap() - 4.0 / 3.0).abs() < 1e-12);
+
+        // Transcendental functions are approximate
+        let result = evaluate_expression("2 * sin(1)").unwrap();
+        assert!(result.is_success());
+        assert_eq!(result.exact(), None);
+    }
+
+    #[test]
+    fn test_large_approximate_values() {
+        assert_eq!(format_display(1e20), "100,000,000,000,000,000,000");
+        assert_eq!(format_display(-1234567.25), "-1,234,567.25");
+    }
+
     #[test]
     fn test_division_by_zero() {
         let result = evaluate_expression("1 / 0").unwrap();
@@ -0,0 +1,664 @@
+//! Exact evaluation over big rationals.
+//!
+//! Numbers are kept as exact fractions of arbitrary-size integers, so
+//! `0.1 + 0.2` is exactly `3/10`, `30!` and `2^200` keep every digit, and
+//! `1/3` can be shown as a fraction. Functions without exact results, such
+//! as `sin` or `ln`, fall back to f64 and make the rest of the expression
+//! approximate. Function names and arguments follow fasteval, which handles
+//! any expression this evaluator does not understand.
+
+use super::evaluation::EvalError;
+use crate::config::CalculatorFunction;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Largest number of bits an exact power or factorial may have before
+/// falling back to f64, to keep the launcher responsive.
+const MAX_BITS: u64 = 1 << 16;
+
+/// Largest number whose factorial is computed.
+const MAX_FACTORIAL: u32 = 5000;
+
+/// Number of decimal places shown for fractions.
+const DECIMAL_PLACES: usize = 10;
+
+/// Deepest nesting of user function calls.
+const MAX_DEPTH: usize = 32;
+
+/// A value that is either exact or an f64 approximation.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum Number {
+    Exact(BigRational),
+    Approx(f64),
+}
+
+impl Number {
+    fn integer(value: i64) -> Self {
+        Self::Exact(BigRational::from_integer(value.into()))
+    }
+
+    /// Get the value as an f64, approximating exact values.
+    pub(super) fn to_f64(&self) -> f64 {
+        match self {
+            Self::Exact(value) => value.to_f64().unwrap_or(f64::NAN),
+            Self::Approx(value) => *value,
+        }
+    }
+
+    /// Combine two numbers exactly if both are exact, otherwise in f64.
+    fn combine(
+        self,
+        rhs: Self,
+        exact: impl FnOnce(BigRational, BigRational) -> BigRational,
+        approx: impl FnOnce(f64, f64) -> f64,
+    ) -> Self {
+        match (self, rhs) {
+            (Self::Exact(lhs), Self::Exact(rhs)) => Self::Exact(exact(lhs, rhs)),
+            (lhs, rhs) => Self::Approx(approx(lhs.to_f64(), rhs.to_f64())),
+        }
+    }
+
+    /// Apply a function exactly if the number is exact, otherwise in f64.
+    fn map(
+        self,
+        exact: impl FnOnce(BigRational) -> BigRational,
+        approx: impl FnOnce(f64) -> f64,
+    ) -> Self {
+        match self {
+            Self::Exact(value) => Self::Exact(exact(value)),
+            Self::Approx(value) => Self::Approx(approx(value)),
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        match self {
+            Self::Exact(value) => value.is_zero(),
+            Self::Approx(value) => *value == 0.0,
+        }
+    }
+
+    fn compare(&self, rhs: &Self) -> Option<Ordering> {
+        match (self, rhs) {
+            (Self::Exact(lhs), Self::Exact(rhs)) => Some(lhs.cmp(rhs)),
+            (lhs, rhs) => lhs.to_f64().partial_cmp(&rhs.to_f64()),
+        }
+    }
+}
+
+/// Error for dividing `lhs` by zero, matching how f64 results are reported.
+fn division_by_zero(lhs: &Number) -> EvalError {
+    match lhs.compare(&Number::integer(0)) {
+        Some(Ordering::Greater) => EvalError::Math("Infinity"),
+        Some(Ordering::Less) => EvalError::Math("-Infinity"),
+        _ => EvalError::Math("Not a Number"),
+    }
+}
+
+/// Names an expression can use besides the built-in functions.
+#[derive(Clone, Debug, Default)]
+pub(super) struct Scope {
+    /// Variables and constants by lowercase name.
+    pub(super) variables: HashMap<String, Number>,
+    /// User functions by lowercase name.
+    pub(super) functions: HashMap<String, CalculatorFunction>,
+    /// Number of nested user function calls, to stop runaway recursion.
+    depth: usize,
+}
+
+impl Scope {
+    /// Call a user function, or return None if there is no such function.
+    fn call(&self, name: &str, args: &[Number]) -> Option<Result<Number, EvalError>> {
+        let function = self.functions.get(name)?;
+        if args.len() != function.params.len() {
+            return Some(Err(EvalError::Math("Wrong number of arguments")));
+        }
+        if self.depth >= MAX_DEPTH {
+            return Some(Err(EvalError::Math("Too much recursion")));
+        }
+
+        let mut scope = self.clone();
+        scope.depth += 1;
+        scope
+            .variables
+            .extend(function.params.iter().cloned().zip(args.iter().cloned()));
+
+        Some(match evaluate(&function.body, &scope) {
+            Err(EvalError::Syntax) => Err(EvalError::Math("Invalid function")),
+            result => result,
+        })
+    }
+}
+
+/// A token of an expression.
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(BigRational),
+    Ident(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Percent,
+    Caret,
+    Bang,
+    Comma,
+    Open,
+    Close,
+}
+
+/// Split an expression into tokens.
+fn tokenize(input: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut rest = input.trim_start();
+
+    while let Some(c) = rest.chars().next() {
+        let (token, len) = if c.is_ascii_digit() || c == '.' {
+            let len = number_end(rest);
+            (Token::Number(parse_number(&rest[..len])?), len)
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let len = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            (Token::Ident(rest[..len].to_ascii_lowercase()), len)
+        } else if rest.starts_with("**") {
+            (Token::Caret, 2)
+        } else {
+            let token = match c {
+                '+' => Token::Plus,
+                '-' => Token::Minus,
+                '*' => Token::Star,
+                '/' => Token::Slash,
+                '%' => Token::Percent,
+                '^' => Token::Caret,
+                '!' => Token::Bang,
+                ',' => Token::Comma,
+                '(' => Token::Open,
+                ')' => Token::Close,
+                _ => return None,
+            };
+            (token, 1)
+        };
+
+        tokens.push(token);
+        rest = rest[len..].trim_start();
+    }
+
+    Some(tokens)
+}
+
+/// Find the end of a number like `12.5` or `1.5e-3` at the start of `input`.
+fn number_end(input: &str) -> usize {
+    let bytes = input.as_bytes();
+    let digits = |mut i: usize| {
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        i
+    };
+
+    let mut end = digits(0);
+    if bytes.get(end) == Some(&b'.') {
+        end = digits(end + 1);
+    }
+
+    // Only an `e` followed by digits is an exponent, so `2e` stays invalid
+    if matches!(bytes.get(end), Some(b'e' | b'E')) {
+        let sign = matches!(bytes.get(end + 1), Some(b'+' | b'-')) as usize;
+        let exponent_end = digits(end + 1 + sign);
+        if exponent_end > end + 1 + sign {
+            end = exponent_end;
+        }
+    }
+    end
+}
+
+/// Parse a decimal number exactly, so `0.1` is `1/10`.
+fn parse_number(literal: &str) -> Option<BigRational> {
+    let (mantissa, exponent) = match literal.find(['e', 'E']) {
+        Some(pos) => (&literal[..pos], literal[pos + 1..].parse::<i32>().ok()?),
+        None => (literal, 0),
+    };
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return None;
+    }
+
+    let digits: BigInt = format!("{}{}", whole, fraction).parse().ok()?;
+    let scale = exponent.checked_sub(i32::try_from(fraction.len()).ok()?)?;
+    if scale.unsigned_abs() > 1000 {
+        return None;
+    }
+
+    let power = BigInt::from(10).pow(scale.unsigned_abs());
+    Some(if scale >= 0 {
+        BigRational::from_integer(digits * power)
+    } else {
+        BigRational::new(digits, power)
+    })
+}
+
+/// Recursive descent evaluator over the tokens.
+///
+/// Precedence from loosest to tightest: `+ -`, `* / %`, unary `-`, `^`
+/// which binds to the right, then the postfix factorial `!`.
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    scope: &'a Scope,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    /// Consume the next token if it is one of `expected`.
+    fn eat(&mut self, expected: &[Token]) -> Option<Token> {
+        let token = self.peek().filter(|t| expected.contains(t))?.clone();
+        self.pos += 1;
+        Some(token)
+    }
+
+    fn parse(mut self) -> Result<Number, EvalError> {
+        let value = self.additive()?;
+        if self.pos < self.tokens.len() {
+            return Err(EvalError::Syntax);
+        }
+        Ok(value)
+    }
+
+    fn additive(&mut self) -> Result<Number, EvalError> {
+        let mut value = self.multiplicative()?;
+        while let Some(op) = self.eat(&[Token::Plus, Token::Minus]) {
+            let rhs = self.multiplicative()?;
+            value = if op == Token::Plus {
+                value.combine(rhs, |a, b| a + b, |a, b| a + b)
+            } else {
+                value.combine(rhs, |a, b| a - b, |a, b| a - b)
+            };
+        }
+        Ok(value)
+    }
+
+    fn multiplicative(&mut self) -> Result<Number, EvalError> {
+        let mut value = self.unary()?;
+        while let Some(op) = self.eat(&[Token::Star, Token::Slash, Token::Percent]) {
+            let rhs = self.unary()?;
+            if op != Token::Star && rhs.is_zero() {
+                return Err(division_by_zero(&value));
+            }
+            value = match op {
+                Token::Star => value.combine(rhs, |a, b| a * b, |a, b| a * b),
+                Token::Slash => value.combine(rhs, |a, b| a / b, |a, b| a / b),
+                // Remainder has the sign of the dividend, as in fasteval
+                _ => value.combine(rhs, |a, b| &a - (&a / &b).trunc() * &b, |a, b| a % b),
+            };
+        }
+        Ok(value)
+    }
+
+    fn unary(&mut self) -> Result<Number, EvalError> {
+        match self.eat(&[Token::Minus, Token::Plus]) {
+            Some(Token::Minus) => Ok(self.unary()?.map(|a| -a, |a| -a)),
+            Some(_) => self.unary(),
+            None => self.power(),
+        }
+    }
+
+    fn power(&mut self) -> Result<Number, EvalError> {
+        let base = self.postfix()?;
+        if self.eat(&[Token::Caret]).is_none() {
+            return Ok(base);
+        }
+        power(base, self.unary()?)
+    }
+
+    fn postfix(&mut self) -> Result<Number, EvalError> {
+        let mut value = self.primary()?;
+        while self.eat(&[Token::Bang]).is_some() {
+            value = factorial(value)?;
+        }
+        Ok(value)
+    }
+
+    fn primary(&mut self) -> Result<Number, EvalError> {
+        match self.peek().cloned() {
+            Some(Token::Number(value)) => {
+                self.pos += 1;
+                Ok(Number::Exact(value))
+            }
+            Some(Token::Open) => {
+                self.pos += 1;
+                let value = self.additive()?;
+                self.eat(&[Token::Close]).ok_or(EvalError::Syntax)?;
+                Ok(value)
+            }
+            Some(Token::Ident(name)) => {
+                self.pos += 1;
+                if self.eat(&[Token::Open]).is_none() {
+                    if let Some(value) = self.scope.variables.get(&name) {
+                        return Ok(value.clone());
+                    }
+                    return call(&name, Vec::new());
+                }
+
+                let args = self.arguments()?;
+                match self.scope.call(&name, &args) {
+                    Some(result) => result,
+                    None => call(&name, args),
+                }
+            }
+            _ => Err(EvalError::Syntax),
+        }
+    }
+
+    /// Parse comma-separated arguments after an opening parenthesis.
+    fn arguments(&mut self) -> Result<Vec<Number>, EvalError> {
+        let mut args = Vec::new();
+        if self.eat(&[Token::Close]).is_some() {
+            return Ok(args);
+        }
+        loop {
+            args.push(self.additive()?);
+            match self.eat(&[Token::Comma, Token::Close]) {
+                Some(Token::Comma) => continue,
+                Some(_) => return Ok(args),
+                None => return Err(EvalError::Syntax),
+            }
+        }
+    }
+}
+
+/// Raise `base` to `exponent`, exactly for integer exponents.
+fn power(base: Number, exponent: Number) -> Result<Number, EvalError> {
+    let (Number::Exact(base), Number::Exact(exponent)) = (&base, &exponent) else {
+        return Ok(Number::Approx(base.to_f64().powf(exponent.to_f64())));
+    };
+
+    let exact_exponent = exponent
+        .is_integer()
+        .then(|| exponent.to_integer().abs().to_u32())
+        .flatten()
+        .filter(|e| {
+            let bits = base.numer().bits().max(base.denom().bits());
+            bits.saturating_mul(u64::from(*e)) <= MAX_BITS
+        });
+    let Some(e) = exact_exponent else {
+        return Ok(Number::Approx(
+            base.to_f64()
+                .unwrap_or(f64::NAN)
+                .powf(exponent.to_f64().unwrap_or(f64::NAN)),
+        ));
+    };
+
+    let base = if exponent.is_negative() {
+        if base.is_zero() {
+            return Err(EvalError::Math("Infinity"));
+        }
+        base.recip()
+    } else {
+        base.clone()
+    };
+    Ok(Number::Exact(num_traits::pow(base, e as usize)))
+}
+
+/// Compute `value!` for a non-negative integer.
+fn factorial(value: Number) -> Result<Number, EvalError> {
+    let n = match &value {
+        Number::Exact(n) if n.is_integer() && !n.is_negative() => n.to_integer().to_u32(),
+        _ => return Err(EvalError::Math("Not a Number")),
+    };
+    let n = n
+        .filter(|n| *n <= MAX_FACTORIAL)
+        .ok_or(EvalError::Math("Result too large"))?;
+
+    let product = (2..=n).fold(BigInt::one(), |acc, i| acc * i);
+    Ok(Number::Exact(BigRational::from_integer(product)))
+}
+
+/// Call a function or read a constant.
+///
+/// Names unknown here are syntax errors, leaving them to fasteval.
+fn call(name: &str, args: Vec<Number>) -> Result<Number, EvalError> {
+    let approx = |f: fn(f64) -> f64| match args.as_slice() {
+        [value] => Ok(Number::Approx(f(value.to_f64()))),
+        _ => Err(EvalError::Syntax),
+    };
+
+    match (name, args.as_slice()) {
+        ("pi", []) => Ok(Number::Approx(std::f64::consts::PI)),
+        ("e", []) => Ok(Number::Approx(std::f64::consts::E)),
+        ("abs", [value]) => Ok(value.clone().map(|a| a.abs(), f64::abs)),
+        ("sign", [value]) => Ok(value.clone().map(|a| a.signum(), f64::signum)),
+        ("int", [value]) => Ok(value.clone().map(|a| a.trunc(), f64::trunc)),
+        ("floor", [value]) => Ok(value.clone().map(|a| a.floor(), f64::floor)),
+        ("ceil", [value]) => Ok(value.clone().map(|a| a.ceil(), f64::ceil)),
+        ("round", [value]) => Ok(value.clone().map(|a| a.round(), f64::round)),
+        ("round", [modulus, value]) => {
+            if modulus.is_zero() {
+                return Err(EvalError::Math("Not a Number"));
+            }
+            Ok(value.clone().combine(
+                modulus.clone(),
+                |v, m| (&v / &m).round() * m,
+                |v, m| (v / m).round() * m,
+            ))
+        }
+        ("min" | "max", [first, rest @ ..]) => {
+            let wanted = if name == "min" {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            };
+            rest.iter()
+                .try_fold(first.clone(), |best, value| match value.compare(&best) {
+                    Some(ordering) if ordering == wanted => Ok(value.clone()),
+                    Some(_) => Ok(best),
+                    None => Err(EvalError::Math("Not a Number")),
+                })
+        }
+        ("sqrt", [value]) => Ok(exact_sqrt(value).unwrap_or(Number::Approx(value.to_f64().sqrt()))),
+        ("log", [base, value]) => Ok(Number::Approx(value.to_f64().log(base.to_f64()))),
+        ("log", [_]) => approx(f64::log10),
+        ("ln", _) => approx(f64::ln),
+        ("exp", _) => approx(f64::exp),
+        ("sin", _) => approx(f64::sin),
+        ("cos", _) => approx(f64::cos),
+        ("tan", _) => approx(f64::tan),
+        ("asin", _) => approx(f64::asin),
+        ("acos", _) => approx(f64::acos),
+        ("atan", _) => approx(f64::atan),
+        ("sinh", _) => approx(f64::sinh),
+        ("cosh", _) => approx(f64::cosh),
+        ("tanh", _) => approx(f64::tanh),
+        ("asinh", _) => approx(f64::asinh),
+        ("acosh", _) => approx(f64::acosh),
+        ("atanh", _) => approx(f64::atanh),
+        _ => Err(EvalError::Syntax),
+    }
+}
+
+/// Get the square root of a fraction of perfect squares, like `9/4`.
+fn exact_sqrt(value: &Number) -> Option<Number> {
+    let Number::Exact(value) = value else {
+        return None;
+    };
+    if value.is_negative() {
+        return None;
+    }
+
+    let numer = value.numer().sqrt();
+    let denom = value.denom().sqrt();
+    (&numer * &numer == *value.numer() && &denom * &denom == *value.denom())
+        .then(|| Number::Exact(BigRational::new(numer, denom)))
+}
+
+/// Evaluate an expression with the names in `scope`, exactly where possible.
+///
+/// Returns `EvalError::Syntax` for anything this evaluator does not
+/// understand, so the caller can fall back to fasteval.
+pub(super) fn evaluate(input: &str, scope: &Scope) -> Result<Number, EvalError> {
+    let tokens = tokenize(input).ok_or(EvalError::Syntax)?;
+    if tokens.is_empty() {
+        return Err(EvalError::Syntax);
+    }
+    Parser {
+        tokens,
+        pos: 0,
+        scope,
+    }
+    .parse()
+}
+
+/// Write a fraction as a decimal rounded to ten places, without trailing
+/// zeros or thousand separators.
+pub(super) fn format_decimal(value: &BigRational) -> String {
+    let scale = BigInt::from(10).pow(DECIMAL_PLACES as u32);
+    let scaled = (value * BigRational::from_integer(scale.clone()))
+        .round()
+        .to_integer();
+    if scaled.is_zero() {
+        return "0".to_string();
+    }
+
+    let sign = if scaled.is_negative() { "-" } else { "" };
+    let magnitude = scaled.abs();
+    let whole = &magnitude / &scale;
+    let fraction = format!(
+        "{:0>width$}",
+        (&magnitude % &scale).to_string(),
+        width = DECIMAL_PLACES
+    );
+    let fraction = fraction.trim_end_matches('0');
+
+    if fraction.is_empty() {
+        format!("{}{}", sign, whole)
+    } else {
+        format!("{}{}.{}", sign, whole, fraction)
+    }
+}
+
+/// Parse a number written by [`format_fraction`].
+pub(super) fn parse_fraction(fraction: &str) -> Option<BigRational> {
+    match fraction.split_once('/') {
+        Some((numer, denom)) => {
+            let numer: BigInt = numer.parse().ok()?;
+            let denom: BigInt = denom.parse().ok()?;
+            (!denom.is_zero()).then(|| BigRational::new(numer, denom))
+        }
+        None => Some(BigRational::from_integer(fraction.parse().ok()?)),
+    }
+}
+
+/// Write a fraction as `numerator/denominator`, or just the numerator for
+/// integers.
+pub(super) fn format_fraction(value: &BigRational) -> String {
+    if value.is_integer() {
+        value.numer().to_string()
+    } else {
+        format!("{}/{}", value.numer(), value.denom())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(input: &str) -> Result<Number, EvalError> {
+        evaluate(input, &Scope::default())
+    }
+
+    fn exact(input: &str) -> String {
+        match eval(input) {
+            Ok(Number::Exact(value)) => format_fraction(&value),
+            other => panic!("{} evaluated to {:?}", input, other),
+        }
+    }
+
+    #[test]
+    fn test_exact_arithmetic() {
+        assert_eq!(exact("0.1 + 0.2"), "3/10");
+        assert_eq!(exact("1/3 + 1/6"), "1/2");
+        assert_eq!(exact("-2^2"), "-4");
+        assert_eq!(exact("2^-2"), "1/4");
+        assert_eq!(exact("2^3^2"), "512");
+        assert_eq!(exact("-7 % 3"), "-1");
+        assert_eq!(exact("1.5e3"), "1500");
+        assert_eq!(exact("30!"), "265252859812191058636308480000000");
+        assert_eq!(
+            exact("2^200"),
+            "1606938044258990275541962092341162602522202993782792835301376"
+        );
+        assert_eq!(exact("sqrt(9/4)"), "3/2");
+        assert_eq!(exact("round(0.05, 1.23)"), "5/4");
+        assert_eq!(exact("max(1, 7/2, 3)"), "7/2");
+    }
+
+    #[test]
+    fn test_transcendental_functions_are_approximate() {
+        assert_eq!(eval("sin(0)"), Ok(Number::Approx(0.0)));
+        assert_eq!(eval("log(10, 100)"), Ok(Number::Approx(2.0)));
+        assert!(matches!(eval("sqrt(2)"), Ok(Number::Approx(_))));
+        assert!(matches!(eval("2 * pi()"), Ok(Number::Approx(_))));
+        assert!(matches!(eval("16^0.5"), Ok(Number::Approx(v)) if v == 4.0));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(eval("1 / 0"), Err(EvalError::Math("Infinity")));
+        assert_eq!(eval("0 / 0"), Err(EvalError::Math("Not a Number")));
+        assert_eq!(eval("(-1)!"), Err(EvalError::Math("Not a Number")));
+        assert_eq!(eval("99999!"), Err(EvalError::Math("Result too large")));
+
+        // Left to fasteval
+        assert_eq!(eval("2 +* 2"), Err(EvalError::Syntax));
+        assert_eq!(eval("2k"), Err(EvalError::Syntax));
+        assert_eq!(eval("foo(1)"), Err(EvalError::Syntax));
+    }
+
+    #[test]
+    fn test_scope() {
+        let mut scope = Scope::default();
+        scope.variables.insert("r".to_string(), Number::integer(2));
+        for definition in ["hyp(a, b) = sqrt(a^2 + b^2)", "loop(x) = loop(x)"] {
+            let function: CalculatorFunction =
+                serde_json::from_value(serde_json::json!(definition)).unwrap();
+            scope.functions.insert(function.name.clone(), function);
+        }
+
+        let value = |input| match evaluate(input, &scope) {
+            Ok(Number::Exact(value)) => format_fraction(&value),
+            other => panic!("{} evaluated to {:?}", input, other),
+        };
+        assert_eq!(value("R * 3/4"), "3/2");
+        assert_eq!(value("hyp(3, 4) + r"), "7");
+        assert_eq!(
+            evaluate("hyp(3)", &scope),
+            Err(EvalError::Math("Wrong number of arguments"))
+        );
+        assert_eq!(
+            evaluate("loop(1)", &scope),
+            Err(EvalError::Math("Too much recursion"))
+        );
+        assert_eq!(
+            parse_fraction("-4/3"),
+            Some(BigRational::new((-4).into(), 3.into()))
+        );
+    }
+
+    #[test]
+    fn test_format_decimal() {
+        let value = |input| match eval(input) {
+            Ok(Number::Exact(value)) => format_decimal(&value),
+            other => panic!("{} evaluated to {:?}", input, other),
+        };
+        assert_eq!(value("0.1 + 0.2"), "0.3");
+        assert_eq!(value("1/3"), "0.3333333333");
+        assert_eq!(value("-2/3"), "-0.6666666667");
+        assert_eq!(value("10^20 + 0.5"), "100000000000000000000.5");
+        assert_eq!(value("1/10^12"), "0");
+    }
+}
@@ -0,0 +1,313 @@
+//! Locale-dependent notation of numbers.
+//!
+//! The evaluators read and write numbers like `1,234.5`. Input is converted
+//! to that notation before evaluation, and results are converted back, so a
+//! German locale reads and shows `1.234,5`. In every locale `;` separates
+//! function arguments, which `max(1,5; 2)` needs when `,` is the decimal
+//! separator.
+
+use super::evaluation::CalcResult;
+use crate::config::ClipboardFormat;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    /// Matches a run of digits and the separators that may appear between them.
+    static ref NUMBER: Regex = Regex::new(r"\d(?:[\d.,'\u{a0}]*\d)?").unwrap();
+
+    /// Matches a number as written by the evaluators for the clipboard.
+    static ref PLAIN_NUMBER: Regex = Regex::new(r"^-?\d+(?:\.\d+)?$").unwrap();
+}
+
+/// Languages writing `1.234,5`.
+const DOT_GROUPING: &[&str] = &[
+    "az", "bs", "ca", "da", "de", "el", "es", "eu", "gl", "hr", "id", "is", "it", "mk", "nl", "pt",
+    "ro", "sl", "sr", "tr", "vi",
+];
+
+/// Languages writing `1 234,5` with a no-break space.
+const SPACE_GROUPING: &[&str] = &[
+    "af", "be", "bg", "cs", "et", "fi", "fr", "hu", "ka", "kk", "lt", "lv", "nb", "nn", "no", "pl",
+    "ru", "sk", "sq", "sv", "uk",
+];
+
+/// Regions writing numbers differently from the rest of their language.
+const REGIONS: &[(&str, NumberFormat)] = &[
+    ("de_CH", NumberFormat::SWISS),
+    ("de_LI", NumberFormat::SWISS),
+    ("fr_CH", NumberFormat::SWISS),
+    ("it_CH", NumberFormat::SWISS),
+    ("es_MX", NumberFormat::DEFAULT),
+    ("es_US", NumberFormat::DEFAULT),
+];
+
+/// The separators used to write numbers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// Separator between the integer and fractional part
+    pub decimal: char,
+    /// Separator between groups of three digits
+    pub grouping: char,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl NumberFormat {
+    /// `1,234.5`, the notation of the evaluators.
+    pub const DEFAULT: Self = Self {
+        decimal: '.',
+        grouping: ',',
+    };
+
+    const SWISS: Self = Self {
+        decimal: '.',
+        grouping: '\'',
+    };
+
+    /// Get the format of a locale, or of the environment if there is none.
+    pub fn new(locale: Option<&str>) -> Self {
+        match locale {
+            Some(locale) => Self::from_locale(locale),
+            None => Self::from_env(),
+        }
+    }
+
+    /// Get the format of a locale name like `de_DE.UTF-8`.
+    ///
+    /// Unknown locales, `C` and `POSIX` use [`NumberFormat::DEFAULT`].
+    pub fn from_locale(locale: &str) -> Self {
+        let locale = locale
+            .split(['.', '@'])
+            .next()
+            .unwrap_or_default()
+            .replace('-', "_");
+        let language = locale.split('_').next().unwrap_or_default().to_lowercase();
+
+        if let Some((_, format)) = REGIONS
+            .iter()
+            .find(|(region, _)| region.eq_ignore_ascii_case(&locale))
+        {
+            *format
+        } else if DOT_GROUPING.contains(&language.as_str()) {
+            Self {
+                decimal: ',',
+                grouping: '.',
+            }
+        } else if SPACE_GROUPING.contains(&language.as_str()) {
+            Self {
+                decimal: ',',
+                grouping: '\u{a0}',
+            }
+        } else {
+            Self::DEFAULT
+        }
+    }
+
+    /// Get the format of the locale numbers are written in, from
+    /// `LC_ALL`, `LC_NUMERIC` or `LANG`.
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_NUMERIC", "LANG"]
+            .into_iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .map(|locale| Self::from_locale(&locale))
+            .unwrap_or_default()
+    }
+
+    /// Convert input to the notation of the evaluators.
+    ///
+    /// Where `,` groups thousands, it still separates arguments inside a
+    /// function call, so `log(10,100)` has two arguments. Numbers with
+    /// misplaced separators are left alone.
+    pub fn delocalize(&self, input: &str) -> String {
+        let input = NUMBER.replace_all(input, |captures: &regex::Captures| {
+            let number = captures.get(0).unwrap();
+            let parse = |number: &str| self.parse(number).unwrap_or_else(|| number.to_string());
+            if self.grouping == ',' && in_arguments(&input[..number.start()]) {
+                number
+                    .as_str()
+                    .split(',')
+                    .map(parse)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            } else {
+                parse(number.as_str())
+            }
+        });
+        input.replace(';', ",")
+    }
+
+    /// Parse a number like `1.234,5` into `1234.5`.
+    fn parse(&self, number: &str) -> Option<String> {
+        let (whole, fraction) = match number.split_once(self.decimal) {
+            Some((whole, fraction)) => (whole, Some(fraction)),
+            None => (number, None),
+        };
+
+        let groups: Vec<&str> = whole.split(self.grouping).collect();
+        let grouped = groups.len() == 1
+            || (!groups[0].is_empty()
+                && groups[0].len() <= 3
+                && groups[1..].iter().all(|group| group.len() == 3));
+        let digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+
+        if !grouped || !groups.iter().all(|group| digits(group)) {
+            return None;
+        }
+        let whole = groups.concat();
+        match fraction {
+            Some(fraction) if digits(fraction) => Some(format!("{}.{}", whole, fraction)),
+            Some(_) => None,
+            None => Some(whole),
+        }
+    }
+
+    /// Convert numbers written by the evaluators, like `1,234.5`, to this
+    /// format.
+    pub fn localize(&self, text: &str) -> String {
+        if *self == Self::DEFAULT {
+            return text.to_string();
+        }
+        NUMBER
+            .replace_all(text, |captures: &regex::Captures| {
+                captures[0]
+                    .chars()
+                    .map(|c| match c {
+                        '.' => self.decimal,
+                        ',' => self.grouping,
+                        c => c,
+                    })
+                    .collect::<String>()
+            })
+            .into_owned()
+    }
+
+    /// Localize the display of a result and format its clipboard text.
+    pub(super) fn localize_result(
+        &self,
+        result: CalcResult,
+        clipboard: ClipboardFormat,
+    ) -> CalcResult {
+        let CalcResult::Success {
+            expression,
+            value,
+            display_result,
+            clipboard_result,
+            exact,
+            integer,
+        } = result
+        else {
+            return result;
+        };
+
+        let display_result = self.localize(&display_result);
+        let clipboard_result = if PLAIN_NUMBER.is_match(&clipboard_result) {
+            match clipboard {
+                ClipboardFormat::Plain => clipboard_result,
+                ClipboardFormat::Locale => clipboard_result.replace('.', &self.decimal.to_string()),
+                ClipboardFormat::Display => {
+                    // Copy the number as shown, unless the display rounds it
+                    // or shows something else, like a fraction
+                    let (sign, digits) = match clipboard_result.strip_prefix('-') {
+                        Some(digits) => ("-", digits),
+                        None => ("", clipboard_result.as_str()),
+                    };
+                    match NUMBER.find(&display_result) {
+                        Some(shown) if self.parse(shown.as_str()).as_deref() == Some(digits) => {
+                            format!("{}{}", sign, shown.as_str())
+                        }
+                        _ => clipboard_result,
+                    }
+                }
+            }
+        } else {
+            clipboard_result
+        };
+
+        CalcResult::Success {
+            expression,
+            value,
+            display_result,
+            clipboard_result,
+            exact,
+            integer,
+        }
+    }
+}
+
+/// Check whether the text before a position ends inside the argument list
+/// of a function call, like `log(10, `.
+fn in_arguments(before: &str) -> bool {
+    let mut depth = 0;
+    for (i, c) in before.char_indices().rev() {
+        match c {
+            ')' => depth += 1,
+            '(' if depth > 0 => depth -= 1,
+            '(' => {
+                return before[..i]
+                    .chars()
+                    .next_back()
+                    .is_some_and(|c| c.is_alphanumeric() || c == '_');
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GERMAN: NumberFormat = NumberFormat {
+        decimal: ',',
+        grouping: '.',
+    };
+
+    #[test]
+    fn test_from_locale() {
+        assert_eq!(NumberFormat::from_locale("de_DE.UTF-8"), GERMAN);
+        assert_eq!(NumberFormat::from_locale("fr_FR").grouping, '\u{a0}');
+        assert_eq!(NumberFormat::from_locale("de_CH.UTF-8").decimal, '.');
+        assert_eq!(
+            NumberFormat::from_locale("en_US.UTF-8"),
+            NumberFormat::DEFAULT
+        );
+        assert_eq!(NumberFormat::from_locale("C"), NumberFormat::DEFAULT);
+    }
+
+    #[test]
+    fn test_delocalize() {
+        let english = NumberFormat::DEFAULT;
+        assert_eq!(english.delocalize("1,234.5 * 2"), "1234.5 * 2");
+        assert_eq!(english.delocalize("max(1,2)"), "max(1,2)");
+        assert_eq!(english.delocalize("max(1; 2)"), "max(1, 2)");
+        assert_eq!(english.delocalize("log(10,100)"), "log(10,100)");
+        assert_eq!(english.delocalize("max(1,234)"), "max(1,234)");
+        assert_eq!(english.delocalize("max(1,234.5, 2)"), "max(1,234.5, 2)");
+        assert_eq!(english.delocalize("(1,234 + 1) * 2"), "(1234 + 1) * 2");
+
+        assert_eq!(GERMAN.delocalize("1.234,56 + 0,5"), "1234.56 + 0.5");
+        assert_eq!(GERMAN.delocalize("max(1,5; 2)"), "max(1.5, 2)");
+        assert_eq!(GERMAN.delocalize("log(10, 100)"), "log(10, 100)");
+        // Not a valid grouping, so the dot is a decimal point
+        assert_eq!(GERMAN.delocalize("1.5 + 1"), "1.5 + 1");
+        assert_eq!(
+            GERMAN.delocalize("2026-12-24 - today"),
+            "2026-12-24 - today"
+        );
+    }
+
+    #[test]
+    fn test_localize() {
+        assert_eq!(GERMAN.localize("1,234.5 km"), "1.234,5 km");
+        assert_eq!(
+            GERMAN.localize("Thu 24 Dec 2026 18:00"),
+            "Thu 24 Dec 2026 18:00"
+        );
+    }
+}
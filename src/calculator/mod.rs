@@ -2,13 +2,28 @@
 //!
 //! This module provides functionality to:
 //! - Detect if user input looks like a calculator expression
-//! - Evaluate expressions using fasteval
+//! - Evaluate expressions exactly over big rationals, using fasteval as fallback
+//! - Convert between units
+//! - Do date and time arithmetic with time zones
+//! - Evaluate integer expressions with bitwise operators in any base
+//! - Keep `ans`, variables and a history of copied results
+//! - Read and show numbers in the configured locale
 //! - Copy results to the clipboard
 
 mod clipboard;
+mod datetime;
 mod detection;
 mod evaluation;
+mod exact;
+mod locale;
+mod programmer;
+mod session;
+mod units;
 
 pub use clipboard::copy_to_clipboard;
+pub use datetime::evaluate_datetime;
 pub use detection::looks_like_expression;
 pub use evaluation::{CalcResult, evaluate_expression};
+pub use programmer::{IntegerForms, NumberBase, evaluate_integer};
+pub use session::{evaluate, recent_calculations, remember};
+pub use units::evaluate_conversion;
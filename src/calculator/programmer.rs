@@ -0,0 +1,472 @@
+//! Integer evaluation for programmer-style expressions.
+//!
+//! Handles hex, binary and octal literals (`0xff`, `0b1010`, `0o17`),
+//! bitwise operators (`&`, `|`, `xor`, `~`, `<<`, `>>`) and a trailing
+//! `in hex`, `in bin`, `in oct` or `in dec` choosing how the result is shown.
+//! Integers have arbitrary width, so negative numbers are shown with a sign
+//! rather than in two's complement. As in the rest of the calculator, `^`
+//! is exponentiation; use `xor` for exclusive or.
+
+use super::evaluation::{CalcResult, EvalError, format_digits};
+use lazy_static::lazy_static;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{Signed, ToPrimitive, Zero};
+use regex::Regex;
+
+lazy_static! {
+    /// Matches a hex, binary or octal literal.
+    static ref RADIX_LITERAL: Regex = Regex::new(r"(?i)\b0[xbo][0-9a-f_]").unwrap();
+
+    /// Matches a bitwise operator or keyword.
+    static ref BITWISE_OPERATOR: Regex =
+        Regex::new(r"<<|>>|[&|~]|\b(?:xor|and|or|not)\b").unwrap();
+
+    /// Matches the trailing base selection, e.g. `in hex`.
+    static ref BASE_SUFFIX: Regex =
+        Regex::new(r"(?i)\s+(?:in|to|as)\s+(hex|bin|oct|dec)\s*$").unwrap();
+}
+
+/// Largest number of bits a result may have, to keep `1 << 1e9` from
+/// freezing the launcher.
+const MAX_BITS: u64 = 1 << 20;
+
+/// A number base results can be shown in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NumberBase {
+    #[default]
+    Decimal,
+    Hex,
+    Octal,
+    Binary,
+}
+
+impl NumberBase {
+    fn from_suffix(suffix: &str) -> Self {
+        match suffix.to_ascii_lowercase().as_str() {
+            "hex" => Self::Hex,
+            "oct" => Self::Octal,
+            "bin" => Self::Binary,
+            _ => Self::Decimal,
+        }
+    }
+}
+
+/// An integer result written in each base.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntegerForms {
+    /// Base the result was asked for, shown first
+    pub base: NumberBase,
+    pub decimal: String,
+    pub hex: String,
+    pub octal: String,
+    pub binary: String,
+}
+
+impl IntegerForms {
+    fn new(value: &BigInt, base: NumberBase) -> Self {
+        Self {
+            base,
+            decimal: value.to_str_radix(10),
+            hex: prefixed(value, "0x", 16),
+            octal: prefixed(value, "0o", 8),
+            binary: prefixed(value, "0b", 2),
+        }
+    }
+
+    /// Get the result written in a base.
+    pub fn get(&self, base: NumberBase) -> &str {
+        match base {
+            NumberBase::Decimal => &self.decimal,
+            NumberBase::Hex => &self.hex,
+            NumberBase::Octal => &self.octal,
+            NumberBase::Binary => &self.binary,
+        }
+    }
+
+    /// Get the forms shown next to the main result: decimal, hex and binary
+    /// without the one asked for.
+    pub fn others(&self) -> impl Iterator<Item = &str> {
+        [NumberBase::Decimal, NumberBase::Hex, NumberBase::Binary]
+            .into_iter()
+            .filter(move |base| *base != self.base)
+            .map(move |base| self.get(base))
+    }
+}
+
+/// Write a value with a base prefix after its sign, e.g. `-0xff`.
+fn prefixed(value: &BigInt, prefix: &str, radix: u32) -> String {
+    let sign = if value.is_negative() { "-" } else { "" };
+    format!("{}{}{}", sign, prefix, value.abs().to_str_radix(radix))
+}
+
+/// A token of an integer expression.
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(BigInt),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Percent,
+    Power,
+    And,
+    Or,
+    Xor,
+    Not,
+    ShiftLeft,
+    ShiftRight,
+    Open,
+    Close,
+}
+
+/// Split an expression into tokens.
+fn tokenize(input: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut rest = input.trim_start();
+
+    while let Some(c) = rest.chars().next() {
+        let (token, len) = if c.is_ascii_digit() {
+            let len = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            (Token::Number(parse_literal(&rest[..len])?), len)
+        } else if c.is_ascii_alphabetic() {
+            let len = rest
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(rest.len());
+            let token = match rest[..len].to_ascii_lowercase().as_str() {
+                "and" => Token::And,
+                "or" => Token::Or,
+                "xor" => Token::Xor,
+                "not" => Token::Not,
+                "mod" => Token::Percent,
+                _ => return None,
+            };
+            (token, len)
+        } else {
+            let two = rest.get(..2).unwrap_or_default();
+            match two {
+                "<<" => (Token::ShiftLeft, 2),
+                ">>" => (Token::ShiftRight, 2),
+                "**" => (Token::Power, 2),
+                _ => {
+                    let token = match c {
+                        '+' => Token::Plus,
+                        '-' => Token::Minus,
+                        '*' => Token::Star,
+                        '/' => Token::Slash,
+                        '%' => Token::Percent,
+                        '^' => Token::Power,
+                        '&' => Token::And,
+                        '|' => Token::Or,
+                        '~' => Token::Not,
+                        '(' => Token::Open,
+                        ')' => Token::Close,
+                        _ => return None,
+                    };
+                    (token, 1)
+                }
+            }
+        };
+
+        tokens.push(token);
+        rest = rest[len..].trim_start();
+    }
+
+    Some(tokens)
+}
+
+/// Parse an integer literal in decimal, hex, binary or octal.
+/// Underscores may separate digits, as in `0xffff_0000`.
+fn parse_literal(literal: &str) -> Option<BigInt> {
+    let digits = literal.replace('_', "");
+    let lower = digits.to_ascii_lowercase();
+    let (digits, radix) = match lower.get(..2) {
+        Some("0x") => (&lower[2..], 16),
+        Some("0b") => (&lower[2..], 2),
+        Some("0o") => (&lower[2..], 8),
+        _ => (lower.as_str(), 10),
+    };
+    BigInt::parse_bytes(digits.as_bytes(), radix)
+}
+
+/// Recursive descent evaluator over the tokens.
+///
+/// Precedence from loosest to tightest: `|`, `xor`, `&`, shifts, `+ -`,
+/// `* / %`, unary `- ~`, then `^` which binds to the right.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    /// Consume the next token if it is one of `expected`.
+    fn eat(&mut self, expected: &[Token]) -> Option<Token> {
+        let token = self.peek().filter(|t| expected.contains(t))?.clone();
+        self.pos += 1;
+        Some(token)
+    }
+
+    fn parse(mut self) -> Result<BigInt, EvalError> {
+        let value = self.or()?;
+        if self.pos < self.tokens.len() {
+            return Err(EvalError::Syntax);
+        }
+        Ok(value)
+    }
+
+    fn or(&mut self) -> Result<BigInt, EvalError> {
+        let mut value = self.xor()?;
+        while self.eat(&[Token::Or]).is_some() {
+            value |= self.xor()?;
+        }
+        Ok(value)
+    }
+
+    fn xor(&mut self) -> Result<BigInt, EvalError> {
+        let mut value = self.and()?;
+        while self.eat(&[Token::Xor]).is_some() {
+            value ^= self.and()?;
+        }
+        Ok(value)
+    }
+
+    fn and(&mut self) -> Result<BigInt, EvalError> {
+        let mut value = self.shift()?;
+        while self.eat(&[Token::And]).is_some() {
+            value &= self.shift()?;
+        }
+        Ok(value)
+    }
+
+    fn shift(&mut self) -> Result<BigInt, EvalError> {
+        let mut value = self.additive()?;
+        while let Some(op) = self.eat(&[Token::ShiftLeft, Token::ShiftRight]) {
+            let amount = self.additive()?;
+            let amount = amount
+                .to_u64()
+                .ok_or(EvalError::Math("Invalid shift amount"))?;
+            value = if op == Token::ShiftLeft {
+                if value.bits() + amount > MAX_BITS {
+                    return Err(EvalError::Math("Result too large"));
+                }
+                value << amount as usize
+            } else {
+                value >> amount.min(MAX_BITS) as usize
+            };
+        }
+        Ok(value)
+    }
+
+    fn additive(&mut self) -> Result<BigInt, EvalError> {
+        let mut value = self.multiplicative()?;
+        while let Some(op) = self.eat(&[Token::Plus, Token::Minus]) {
+            let rhs = self.multiplicative()?;
+            value = if op == Token::Plus {
+                value + rhs
+            } else {
+                value - rhs
+            };
+        }
+        Ok(value)
+    }
+
+    fn multiplicative(&mut self) -> Result<BigInt, EvalError> {
+        let mut value = self.unary()?;
+        while let Some(op) = self.eat(&[Token::Star, Token::Slash, Token::Percent]) {
+            let rhs = self.unary()?;
+            if op != Token::Star && rhs.is_zero() {
+                return Err(EvalError::Math("Division by zero"));
+            }
+            value = match op {
+                Token::Star => value * rhs,
+                // Division truncates toward zero
+                Token::Slash => value / rhs,
+                _ => value % rhs,
+            };
+        }
+        Ok(value)
+    }
+
+    fn unary(&mut self) -> Result<BigInt, EvalError> {
+        match self.eat(&[Token::Minus, Token::Plus, Token::Not]) {
+            Some(Token::Minus) => Ok(-self.unary()?),
+            Some(Token::Not) => Ok(!self.unary()?),
+            Some(_) => self.unary(),
+            None => self.power(),
+        }
+    }
+
+    fn power(&mut self) -> Result<BigInt, EvalError> {
+        let base = self.primary()?;
+        if self.eat(&[Token::Power]).is_none() {
+            return Ok(base);
+        }
+
+        let exponent = self.unary()?;
+        if exponent.is_negative() {
+            return Err(EvalError::Math("Negative exponent"));
+        }
+        let exponent = exponent
+            .to_u32()
+            .filter(|e| base.bits().saturating_mul(u64::from(*e)) <= MAX_BITS)
+            .ok_or(EvalError::Math("Result too large"))?;
+        Ok(base.pow(exponent))
+    }
+
+    fn primary(&mut self) -> Result<BigInt, EvalError> {
+        match self.peek().cloned() {
+            Some(Token::Number(value)) => {
+                self.pos += 1;
+                Ok(value)
+            }
+            Some(Token::Open) => {
+                self.pos += 1;
+                let value = self.or()?;
+                self.eat(&[Token::Close]).ok_or(EvalError::Syntax)?;
+                Ok(value)
+            }
+            _ => Err(EvalError::Syntax),
+        }
+    }
+}
+
+/// Split off a trailing `in hex` style suffix.
+fn split_base(input: &str) -> (&str, Option<NumberBase>) {
+    match BASE_SUFFIX.captures(input) {
+        Some(captures) => {
+            let start = captures.get(0).map_or(input.len(), |m| m.start());
+            (&input[..start], Some(NumberBase::from_suffix(&captures[1])))
+        }
+        None => (input, None),
+    }
+}
+
+/// Check if the input uses programmer calculator features: radix literals,
+/// bitwise operators or a base suffix.
+fn uses_integer_features(input: &str) -> bool {
+    let (expression, base) = split_base(input);
+    base.is_some() || RADIX_LITERAL.is_match(expression) || BITWISE_OPERATOR.is_match(expression)
+}
+
+/// Check if the input is an integer expression for the programmer calculator.
+pub fn is_integer_expression(input: &str) -> bool {
+    let input = input.trim();
+    if !uses_integer_features(input) {
+        return false;
+    }
+    let (expression, _) = split_base(input);
+    tokenize(expression).is_some_and(|tokens| {
+        !tokens.is_empty() && Parser { tokens, pos: 0 }.parse() != Err(EvalError::Syntax)
+    })
+}
+
+/// Evaluate an integer expression like `0xff & 0b1010` or `1 << 12 in hex`.
+///
+/// Returns None unless the input uses programmer calculator features and
+/// parses as an integer expression.
+pub fn evaluate_integer(input: &str) -> Option<CalcResult> {
+    let input = input.trim();
+    if !uses_integer_features(input) {
+        return None;
+    }
+
+    let (expression, base) = split_base(input);
+    let tokens = tokenize(expression)?;
+    let value = match (Parser { tokens, pos: 0 }).parse() {
+        Ok(value) => value,
+        Err(EvalError::Syntax) => return None,
+        Err(EvalError::Math(message)) => {
+            return Some(CalcResult::Error {
+                expression: input.to_string(),
+                message: message.to_string(),
+            });
+        }
+    };
+
+    let forms = IntegerForms::new(&value, base.unwrap_or_default());
+    let display_result = match forms.base {
+        NumberBase::Decimal => format_digits(&forms.decimal),
+        base => forms.get(base).to_string(),
+    };
+
+    Some(CalcResult::Success {
+        expression: input.to_string(),
+        value: value.to_f64().unwrap_or(f64::NAN),
+        display_result,
+        clipboard_result: forms.get(forms.base).to_string(),
+        exact: Some(BigRational::from_integer(value)),
+        integer: Some(forms),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn forms(input: &str) -> IntegerForms {
+        match evaluate_integer(input) {
+            Some(CalcResult::Success {
+                integer: Some(forms),
+                ..
+            }) => forms,
+            other => panic!("{} evaluated to {:?}", input, other),
+        }
+    }
+
+    #[test]
+    fn test_bitwise_operations() {
+        assert_eq!(forms("0xff & 0b1010").decimal, "10");
+        assert_eq!(forms("0xf0 | 0x0f").decimal, "255");
+        assert_eq!(forms("0xff xor 0x0f").decimal, "240");
+        assert_eq!(forms("~0").decimal, "-1");
+        assert_eq!(forms("1 << 12").decimal, "4096");
+        assert_eq!(forms("0o777 >> 3").decimal, "63");
+        assert_eq!(forms("0xffff_0000 and not 0xff000000").hex, "0xff0000");
+        // `^` is a power, as in the rest of the calculator
+        assert_eq!(forms("2^4 | 1").decimal, "17");
+    }
+
+    #[test]
+    fn test_result_forms() {
+        let result = evaluate_integer("1 << 12 in hex").unwrap();
+        assert_eq!(result.display(), "0x1000");
+        assert_eq!(result.clipboard(), Some("0x1000"));
+
+        let negative = forms("0b1010 - 0xff");
+        assert_eq!(negative.decimal, "-245");
+        assert_eq!(negative.hex, "-0xf5");
+        assert_eq!(negative.binary, "-0b11110101");
+        assert_eq!(
+            negative.others().collect::<Vec<_>>(),
+            ["-0xf5", "-0b11110101"]
+        );
+
+        // Integers are not limited to 64 bits
+        let result = evaluate_integer("1 << 100").unwrap();
+        assert_eq!(
+            result.display(),
+            "1,267,650,600,228,229,401,496,703,205,376"
+        );
+        assert_eq!(forms("255 in bin").binary, "0b11111111");
+    }
+
+    #[test]
+    fn test_errors() {
+        let result = evaluate_integer("0xff / 0").unwrap();
+        assert!(!result.is_success());
+        assert_eq!(result.display(), "Division by zero");
+        assert!(!evaluate_integer("1 << 99999999").unwrap().is_success());
+
+        // Not integer expressions
+        assert!(evaluate_integer("2 + 2").is_none());
+        assert!(evaluate_integer("0xff & ").is_none());
+        assert!(evaluate_integer("rock and roll").is_none());
+        assert!(!is_integer_expression("0.5 & 1"));
+    }
+}
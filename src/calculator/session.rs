@@ -0,0 +1,398 @@
+//! Calculator state kept across queries.
+//!
+//! `ans` and `_` refer to the last copied result, and copying an
+//! assignment like `r = 4.2` defines `r` until the daemon exits. Constants
+//! and functions from the `[calculator]` config table are available too,
+//! and numbers are read and shown in its locale.
+//! Copied results are kept in a history stored as JSON in
+//! `$XDG_STATE_HOME/zlaunch/calculator.json`.
+
+use super::datetime::evaluate_datetime;
+use super::detection::looks_like_expression;
+use super::evaluation::{CalcResult, evaluate_expression_in};
+use super::exact::{self, Number, Scope, format_fraction, parse_fraction};
+use super::locale::NumberFormat;
+use super::programmer::evaluate_integer;
+use super::units::evaluate_conversion;
+use crate::config::{CalculatorConfig, config};
+use crate::history::{history_path, write_json_atomic};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::warn;
+
+/// Maximum number of calculations kept in the history.
+const MAX_ENTRIES: usize = 100;
+
+/// Names referring to the last copied result.
+const ANSWER_NAMES: [&str; 2] = ["ans", "_"];
+
+lazy_static! {
+    /// Matches an assignment like `r = 4.2`, but not a comparison like `r == 4`.
+    static ref ASSIGNMENT: Regex =
+        Regex::new(r"^\s*([A-Za-z_][A-Za-z0-9_]*)\s*=\s*([^=].*)$").unwrap();
+
+    /// Global calculator session, with the history loaded on first use.
+    static ref SESSION: Mutex<Session> =
+        Mutex::new(Session::new(CalculationHistory::load(&calculator_history_path())));
+}
+
+/// Split an assignment into the lowercase variable name and the expression.
+fn split_assignment(input: &str) -> Option<(String, &str)> {
+    let captures = ASSIGNMENT.captures(input)?;
+    let name = captures[1].to_lowercase();
+    if ANSWER_NAMES.contains(&name.as_str()) {
+        return None;
+    }
+    Some((name, captures.get(2)?.as_str()))
+}
+
+/// A copied calculation as stored in the history file.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Calculation {
+    expression: String,
+    display_result: String,
+    clipboard_result: String,
+    value: f64,
+    /// The exact value as written by `format_fraction`, if there is one.
+    #[serde(default)]
+    exact: Option<String>,
+}
+
+impl Calculation {
+    fn from_result(result: &CalcResult) -> Option<Self> {
+        match result {
+            CalcResult::Success {
+                expression,
+                value,
+                display_result,
+                clipboard_result,
+                exact,
+                ..
+            } => Some(Self {
+                expression: expression.clone(),
+                display_result: display_result.clone(),
+                clipboard_result: clipboard_result.clone(),
+                value: *value,
+                exact: exact.as_ref().map(format_fraction),
+            }),
+            CalcResult::Error { .. } => None,
+        }
+    }
+
+    fn to_result(&self) -> CalcResult {
+        CalcResult::Success {
+            expression: self.expression.clone(),
+            value: self.value,
+            display_result: self.display_result.clone(),
+            clipboard_result: self.clipboard_result.clone(),
+            exact: self.exact.as_deref().and_then(parse_fraction),
+            integer: None,
+        }
+    }
+}
+
+/// Copied calculations, most recent first.
+#[derive(Debug, Default)]
+pub struct CalculationHistory {
+    entries: Vec<Calculation>,
+    /// Where the history is persisted. None keeps it in memory only.
+    path: Option<PathBuf>,
+}
+
+impl CalculationHistory {
+    /// Load the history from a file.
+    ///
+    /// A missing or unreadable file yields an empty history that will be
+    /// written to `path` on the next save.
+    pub fn load(path: &Path) -> Self {
+        let entries = match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                warn!(%e, path = %path.display(), "Ignoring corrupt calculator history file");
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+
+        Self {
+            entries,
+            path: Some(path.to_path_buf()),
+        }
+    }
+
+    /// Add a calculation, replacing an earlier one with the same expression.
+    fn push(&mut self, calculation: Calculation) {
+        self.entries
+            .retain(|entry| entry.expression != calculation.expression);
+        self.entries.insert(0, calculation);
+        self.entries.truncate(MAX_ENTRIES);
+    }
+
+    /// Persist the history, if it has a path.
+    fn save(&self) -> anyhow::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        write_json_atomic(path, &self.entries, None)
+    }
+}
+
+/// Variables, the last copied result and the history of one daemon run.
+#[derive(Debug, Default)]
+pub struct Session {
+    /// The last copied result, available as `ans` and `_`
+    answer: Option<Number>,
+    /// Variables assigned by copying `name = expression`
+    variables: HashMap<String, Number>,
+    history: CalculationHistory,
+}
+
+impl Session {
+    /// Create a session with no variables.
+    pub fn new(history: CalculationHistory) -> Self {
+        Self {
+            history,
+            ..Self::default()
+        }
+    }
+
+    /// Build the names available to expressions. Session variables shadow
+    /// config constants, and `ans` shadows both.
+    fn scope(&self, config: &CalculatorConfig) -> Scope {
+        let mut scope = Scope::default();
+        for (name, value) in &config.constants {
+            // Parse the shortest decimal form so `0.1` stays exactly 1/10
+            let value = match exact::evaluate(&value.to_string(), &Scope::default()) {
+                Ok(number) => number,
+                Err(_) => Number::Approx(*value),
+            };
+            scope.variables.insert(name.clone(), value);
+        }
+        scope.variables.extend(self.variables.clone());
+        if let Some(answer) = &self.answer {
+            for name in ANSWER_NAMES {
+                scope.variables.insert(name.to_string(), answer.clone());
+            }
+        }
+        scope.functions = config
+            .functions
+            .iter()
+            .map(|function| (function.name.clone(), function.clone()))
+            .collect();
+        scope
+    }
+
+    /// Evaluate a query, which may be an assignment like `r = 4.2`.
+    pub fn evaluate(&self, input: &str, config: &CalculatorConfig) -> Option<CalcResult> {
+        let scope = self.scope(config);
+        let format = NumberFormat::new(config.locale.as_deref());
+
+        // Anything after `name =` is meant for the calculator
+        let result = match split_assignment(input) {
+            Some((_, expression)) => evaluate_in(&format.delocalize(expression), &scope)?,
+            None => {
+                let expression = format.delocalize(input);
+                if !looks_like_expression(&expression) {
+                    return None;
+                }
+                evaluate_in(&expression, &scope)?
+            }
+        };
+
+        let result = result.with_expression(input.trim());
+        Some(format.localize_result(result, config.clipboard_format))
+    }
+
+    /// Remember a copied result as `ans`, apply it if it is an assignment,
+    /// and add it to the history if `record` is set.
+    pub fn remember(&mut self, result: &CalcResult, record: bool) {
+        let Some(number) = result.number() else {
+            return;
+        };
+
+        if let Some((name, _)) = split_assignment(result.expression()) {
+            self.variables.insert(name, number.clone());
+        }
+        self.answer = Some(number);
+
+        if record && let Some(calculation) = Calculation::from_result(result) {
+            self.history.push(calculation);
+            if let Err(e) = self.history.save() {
+                warn!(%e, "Failed to save calculator history");
+            }
+        }
+    }
+
+    /// Get the copied results, most recent first.
+    pub fn history(&self) -> Vec<CalcResult> {
+        self.history
+            .entries
+            .iter()
+            .map(Calculation::to_result)
+            .collect()
+    }
+}
+
+/// Evaluate input in the notation of the evaluators with every evaluator.
+fn evaluate_in(input: &str, scope: &Scope) -> Option<CalcResult> {
+    evaluate_integer(input)
+        .or_else(|| evaluate_datetime(input))
+        .or_else(|| evaluate_expression_in(input, scope))
+        .or_else(|| evaluate_conversion(input))
+}
+
+/// Get the path of the calculator history file, next to the launch history.
+pub fn calculator_history_path() -> PathBuf {
+    history_path().with_file_name("calculator.json")
+}
+
+/// Evaluate a query with the session variables, `ans` and the configured
+/// constants and functions.
+///
+/// Returns None if the query is not a calculation.
+pub fn evaluate(input: &str) -> Option<CalcResult> {
+    SESSION
+        .lock()
+        .unwrap()
+        .evaluate(input, &config().calculator)
+}
+
+/// Remember a copied result as `ans` and in the history.
+///
+/// The history is not recorded if history is disabled in the config.
+pub fn remember(result: &CalcResult) {
+    SESSION
+        .lock()
+        .unwrap()
+        .remember(result, config().history.enabled);
+}
+
+/// Get the copied results, most recent first.
+pub fn recent_calculations() -> Vec<CalcResult> {
+    SESSION.lock().unwrap().history()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{CalculatorFunction, ClipboardFormat};
+
+    /// A config independent of the locale tests run in.
+    fn english() -> CalculatorConfig {
+        CalculatorConfig {
+            locale: Some("en_US".to_string()),
+            ..CalculatorConfig::default()
+        }
+    }
+
+    fn display(session: &Session, config: &CalculatorConfig, input: &str) -> String {
+        session
+            .evaluate(input, config)
+            .map(|result| result.display().to_string())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_answer_and_assignments() {
+        let config = english();
+        let mut session = Session::default();
+        assert_eq!(display(&session, &config, "ans * 2"), "");
+
+        let result = session.evaluate("r = 1/3", &config).unwrap();
+        assert_eq!(result.expression(), "r = 1/3");
+        assert_eq!(result.display(), "0.3333333333");
+        // Typing an assignment does not define anything until it is copied
+        assert_eq!(display(&session, &config, "r * 3"), "");
+
+        session.remember(&result, false);
+        assert_eq!(display(&session, &config, "R * 3"), "1");
+        assert_eq!(display(&session, &config, "ans + _"), "0.6666666667");
+
+        let result = session.evaluate("0xff | 1", &config).unwrap();
+        session.remember(&result, false);
+        assert_eq!(display(&session, &config, "ans + 1"), "256");
+
+        // `ans` cannot be assigned, and comparisons are not assignments
+        assert_eq!(display(&session, &config, "ans = 3"), "");
+        assert!(split_assignment("r == 4").is_none());
+        assert!(session.history().is_empty());
+    }
+
+    #[test]
+    fn test_config_constants_and_functions() {
+        let config = CalculatorConfig {
+            constants: [("g".to_string(), 9.80665), ("tax".to_string(), 0.2)].into(),
+            functions: vec![CalculatorFunction {
+                name: "gross".to_string(),
+                params: vec!["net".to_string()],
+                body: "net * (1 + tax)".to_string(),
+            }],
+            ..english()
+        };
+        let session = Session::default();
+
+        assert_eq!(display(&session, &config, "2 * g"), "19.6133");
+        assert_eq!(display(&session, &config, "gross(100) as fraction"), "120");
+    }
+
+    #[test]
+    fn test_locale() {
+        let mut config = CalculatorConfig {
+            locale: Some("de_DE.UTF-8".to_string()),
+            ..CalculatorConfig::default()
+        };
+        let session = Session::default();
+        let clipboard = |config: &CalculatorConfig, input: &str| {
+            let result = session.evaluate(input, config).unwrap();
+            result.clipboard().unwrap_or_default().to_string()
+        };
+
+        let result = session.evaluate("1.234,5 * 2", &config).unwrap();
+        assert_eq!(result.expression(), "1.234,5 * 2");
+        assert_eq!(result.display(), "2.469");
+        assert_eq!(
+            display(&session, &config, "max(1,5; 0,25) + 1000"),
+            "1.001,5"
+        );
+        assert_eq!(display(&session, &config, "1.5 km in m"), "1.500 m");
+        assert_eq!(clipboard(&config, "1000,5 + 1"), "1001.5");
+
+        config.clipboard_format = ClipboardFormat::Locale;
+        assert_eq!(clipboard(&config, "1000,5 + 1"), "1001,5");
+        config.clipboard_format = ClipboardFormat::Display;
+        assert_eq!(clipboard(&config, "1000,5 + 1"), "1.001,5");
+        assert_eq!(clipboard(&config, "2/3"), "0,6666666667");
+        assert_eq!(clipboard(&config, "0xff | 1"), "255");
+    }
+
+    #[test]
+    fn test_history_roundtrip() {
+        let dir = std::env::temp_dir().join(format!(
+            "zlaunch-calculator-history-test-{}",
+            std::process::id()
+        ));
+        let path = dir.join("calculator.json");
+        let config = english();
+
+        let mut session = Session::new(CalculationHistory::load(&path));
+        for input in ["1/3 + 1", "2^10", "1/3 + 1"] {
+            let result = session.evaluate(input, &config).unwrap();
+            session.remember(&result, true);
+        }
+
+        let loaded = Session::new(CalculationHistory::load(&path)).history();
+        let expressions: Vec<_> = loaded.iter().map(CalcResult::expression).collect();
+        assert_eq!(expressions, ["1/3 + 1", "2^10"]);
+        assert_eq!(loaded[0].display(), "1.3333333333");
+        assert_eq!(
+            loaded[0].exact().map(format_fraction).as_deref(),
+            Some("4/3")
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,619 @@
+//! Unit-aware evaluation of quantities and conversions.
+//!
+//! Handles input like `5 mi in km`, `72 F to C`, `3 GiB in MB` and
+//! `2h 30m in minutes`. A quantity is a sum of numbers with units of the
+//! same dimension; without a target unit the result is given in the unit of
+//! the first term, so `1 km + 300 m` reads as `1.3 km`.
+
+use super::evaluation::{CalcResult, format_clipboard, format_display};
+use Dimension::*;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    /// Matches the keyword separating a quantity from its target unit.
+    static ref TARGET_KEYWORD: Regex = Regex::new(r"\b(?:in|into|to|as)\b|->|=>").unwrap();
+}
+
+/// A physical dimension. Units convert only within the same dimension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Dimension {
+    Length,
+    Mass,
+    Temperature,
+    Data,
+    Time,
+    Area,
+    Volume,
+    Speed,
+}
+
+/// Which prefixes a unit accepts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Prefixes {
+    None,
+    /// SI prefixes (`km`, `kilometre`)
+    Si,
+    /// Decimal and binary prefixes for data sizes (`MB`, `MiB`)
+    Data,
+}
+
+/// A unit as a linear map to the base unit of its dimension.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Unit {
+    dimension: Dimension,
+    /// Size of the unit in base units
+    factor: f64,
+    /// Base value of the unit's zero, only non-zero for temperatures
+    offset: f64,
+}
+
+impl Unit {
+    fn to_base(self, value: f64) -> f64 {
+        value * self.factor + self.offset
+    }
+
+    fn of_base(self, value: f64) -> f64 {
+        (value - self.offset) / self.factor
+    }
+
+    fn scaled(self, scale: f64) -> Self {
+        Self {
+            factor: self.factor * scale,
+            ..self
+        }
+    }
+}
+
+/// A known unit and the names it is written as.
+struct UnitDef {
+    names: &'static [&'static str],
+    dimension: Dimension,
+    factor: f64,
+    offset: f64,
+    prefixes: Prefixes,
+}
+
+const fn unit(
+    names: &'static [&'static str],
+    dimension: Dimension,
+    factor: f64,
+    prefixes: Prefixes,
+) -> UnitDef {
+    UnitDef {
+        names,
+        dimension,
+        factor,
+        offset: 0.0,
+        prefixes,
+    }
+}
+
+impl UnitDef {
+    fn unit(&self) -> Unit {
+        Unit {
+            dimension: self.dimension,
+            factor: self.factor,
+            offset: self.offset,
+        }
+    }
+}
+
+/// Known units. Base units are the metre, gram, kelvin, byte, second,
+/// square metre, cubic metre and metre per second.
+///
+/// Names may be shared: `m` is a metre or, in `2h 30m`, a minute. The first
+/// unit listed wins when the other terms do not decide.
+const UNITS: &[UnitDef] = &[
+    // Length
+    unit(
+        &["m", "metre", "metres", "meter", "meters"],
+        Length,
+        1.0,
+        Prefixes::Si,
+    ),
+    unit(&["in", "inch", "inches"], Length, 0.0254, Prefixes::None),
+    unit(&["ft", "foot", "feet"], Length, 0.3048, Prefixes::None),
+    unit(&["yd", "yard", "yards"], Length, 0.9144, Prefixes::None),
+    unit(&["mi", "mile", "miles"], Length, 1609.344, Prefixes::None),
+    unit(&["nmi"], Length, 1852.0, Prefixes::None),
+    // Mass
+    unit(&["g", "gram", "grams"], Mass, 1.0, Prefixes::Si),
+    unit(&["t", "tonne", "tonnes"], Mass, 1e6, Prefixes::None),
+    unit(
+        &["lb", "lbs", "pound", "pounds"],
+        Mass,
+        453.59237,
+        Prefixes::None,
+    ),
+    unit(
+        &["oz", "ounce", "ounces"],
+        Mass,
+        28.349523125,
+        Prefixes::None,
+    ),
+    unit(&["st", "stone", "stones"], Mass, 6350.29318, Prefixes::None),
+    // Temperature
+    unit(&["K", "kelvin"], Temperature, 1.0, Prefixes::None),
+    UnitDef {
+        names: &["C", "°C", "celsius"],
+        dimension: Temperature,
+        factor: 1.0,
+        offset: 273.15,
+        prefixes: Prefixes::None,
+    },
+    UnitDef {
+        names: &["F", "°F", "fahrenheit"],
+        dimension: Temperature,
+        factor: 5.0 / 9.0,
+        offset: 459.67 * 5.0 / 9.0,
+        prefixes: Prefixes::None,
+    },
+    // Data sizes
+    unit(&["B", "byte", "bytes"], Data, 1.0, Prefixes::Data),
+    unit(&["b", "bit", "bits"], Data, 0.125, Prefixes::Data),
+    // Time
+    unit(
+        &["s", "sec", "secs", "second", "seconds"],
+        Time,
+        1.0,
+        Prefixes::Si,
+    ),
+    unit(
+        &["min", "mins", "minute", "minutes", "m"],
+        Time,
+        60.0,
+        Prefixes::None,
+    ),
+    unit(
+        &["h", "hr", "hrs", "hour", "hours"],
+        Time,
+        3600.0,
+        Prefixes::None,
+    ),
+    unit(&["d", "day", "days"], Time, 86400.0, Prefixes::None),
+    unit(&["wk", "week", "weeks"], Time, 604800.0, Prefixes::None),
+    unit(&["yr", "year", "years"], Time, 31557600.0, Prefixes::None),
+    // Area, besides squared lengths
+    unit(&["ha", "hectare", "hectares"], Area, 1e4, Prefixes::None),
+    unit(&["acre", "acres"], Area, 4046.8564224, Prefixes::None),
+    // Volume, besides cubed lengths
+    unit(
+        &["l", "L", "litre", "litres", "liter", "liters"],
+        Volume,
+        1e-3,
+        Prefixes::Si,
+    ),
+    unit(
+        &["gal", "gallon", "gallons"],
+        Volume,
+        3.785411784e-3,
+        Prefixes::None,
+    ),
+    unit(
+        &["qt", "quart", "quarts"],
+        Volume,
+        9.46352946e-4,
+        Prefixes::None,
+    ),
+    unit(
+        &["pt", "pint", "pints"],
+        Volume,
+        4.73176473e-4,
+        Prefixes::None,
+    ),
+    unit(&["cup", "cups"], Volume, 2.365882365e-4, Prefixes::None),
+    unit(&["floz"], Volume, 2.95735295625e-5, Prefixes::None),
+    unit(&["tbsp"], Volume, 1.478676478125e-5, Prefixes::None),
+    unit(&["tsp"], Volume, 4.92892159375e-6, Prefixes::None),
+    // Speed, besides length per time
+    unit(&["mph"], Speed, 0.44704, Prefixes::None),
+    unit(&["kph", "kmh"], Speed, 1.0 / 3.6, Prefixes::None),
+    unit(
+        &["kn", "knot", "knots"],
+        Speed,
+        1852.0 / 3600.0,
+        Prefixes::None,
+    ),
+];
+
+/// SI prefixes as written before symbols and before full names.
+const SI_PREFIXES: &[(&str, &str, f64)] = &[
+    ("P", "peta", 1e15),
+    ("T", "tera", 1e12),
+    ("G", "giga", 1e9),
+    ("M", "mega", 1e6),
+    ("k", "kilo", 1e3),
+    ("h", "hecto", 1e2),
+    ("da", "deca", 1e1),
+    ("d", "deci", 1e-1),
+    ("c", "centi", 1e-2),
+    ("m", "milli", 1e-3),
+    ("u", "micro", 1e-6),
+    ("µ", "micro", 1e-6),
+    ("n", "nano", 1e-9),
+    ("p", "pico", 1e-12),
+];
+
+/// Decimal and IEC binary prefixes for data sizes. `K` is accepted as the
+/// common spelling of kilo in `KB`.
+const DATA_PREFIXES: &[(&str, &str, f64)] = &[
+    ("Ki", "kibi", 1024.0),
+    ("Mi", "mebi", 1048576.0),
+    ("Gi", "gibi", 1073741824.0),
+    ("Ti", "tebi", 1099511627776.0),
+    ("Pi", "pebi", 1125899906842624.0),
+    ("k", "kilo", 1e3),
+    ("K", "kilo", 1e3),
+    ("M", "mega", 1e6),
+    ("G", "giga", 1e9),
+    ("T", "tera", 1e12),
+    ("P", "peta", 1e15),
+];
+
+/// Find the units a name may refer to, most likely first.
+fn lookup(name: &str) -> Vec<Unit> {
+    let units = lookup_exact(name, false);
+    if !units.is_empty() {
+        return units;
+    }
+
+    let units = lookup_prefixed(name);
+    if !units.is_empty() {
+        return units;
+    }
+
+    // Squared and cubed lengths: m2, m², m^2, sqft
+    for (suffix, power, dimension) in [
+        ("^2", 2, Area),
+        ("2", 2, Area),
+        ("²", 2, Area),
+        ("^3", 3, Volume),
+        ("3", 3, Volume),
+        ("³", 3, Volume),
+    ] {
+        if let Some(base) = name.strip_suffix(suffix) {
+            return powered(base, power, dimension);
+        }
+    }
+    if let Some(base) = name.strip_prefix("sq") {
+        return powered(base, 2, Area);
+    }
+
+    // Length per time: km/h, m/s, ft/s
+    if let Some((length, time)) = name.split_once('/') {
+        let length = lookup_single(length, Length);
+        let time = lookup_single(time, Time);
+        if let (Some(length), Some(time)) = (length, time) {
+            return vec![Unit {
+                dimension: Speed,
+                factor: length.factor / time.factor,
+                offset: 0.0,
+            }];
+        }
+        return Vec::new();
+    }
+
+    // Forgive the case of full names and temperature symbols: Celsius, f
+    lookup_exact(name, true)
+}
+
+/// Find units by their exact name.
+fn lookup_exact(name: &str, ignore_case: bool) -> Vec<Unit> {
+    UNITS
+        .iter()
+        .filter(|def| {
+            def.names.iter().any(|n| {
+                if ignore_case {
+                    n.eq_ignore_ascii_case(name)
+                } else {
+                    *n == name
+                }
+            })
+        })
+        .map(UnitDef::unit)
+        .collect()
+}
+
+/// Find units written with a prefix, such as `km`, `kilometre` or `GiB`.
+fn lookup_prefixed(name: &str) -> Vec<Unit> {
+    let mut units = Vec::new();
+    for def in UNITS {
+        let prefixes = match def.prefixes {
+            Prefixes::None => continue,
+            Prefixes::Si => SI_PREFIXES,
+            Prefixes::Data => DATA_PREFIXES,
+        };
+        for (symbol, word, scale) in prefixes {
+            let matches = |prefix: &str| {
+                name.strip_prefix(prefix)
+                    .is_some_and(|rest| def.names.contains(&rest))
+            };
+            if matches(symbol) || matches(word) {
+                units.push(def.unit().scaled(*scale));
+            }
+        }
+    }
+    units
+}
+
+/// Find the unit of a given dimension a name refers to.
+fn lookup_single(name: &str, dimension: Dimension) -> Option<Unit> {
+    lookup(name).into_iter().find(|u| u.dimension == dimension)
+}
+
+/// Raise a length unit to a power.
+fn powered(base: &str, power: i32, dimension: Dimension) -> Vec<Unit> {
+    lookup_single(base, Length)
+        .map(|length| Unit {
+            dimension,
+            factor: length.factor.powi(power),
+            offset: 0.0,
+        })
+        .into_iter()
+        .collect()
+}
+
+/// A token of a quantity.
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Number(f64),
+    Unit(&'a str),
+    Plus,
+    Minus,
+}
+
+/// Split a quantity like `2h 30m` or `1 km + 300 m` into tokens.
+fn tokenize(input: &str) -> Option<Vec<Token<'_>>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '+' {
+            chars.next();
+            tokens.push(Token::Plus);
+        } else if c == '-' {
+            chars.next();
+            tokens.push(Token::Minus);
+        } else if c.is_ascii_digit() || c == '.' {
+            let mut end = start;
+            while let Some(&(i, c)) = chars.peek() {
+                if !(c.is_ascii_digit() || c == '.') {
+                    break;
+                }
+                end = i + c.len_utf8();
+                chars.next();
+            }
+            tokens.push(Token::Number(input[start..end].parse().ok()?));
+        } else if c.is_alphabetic() || c == '°' {
+            let end = unit_end(input, start);
+            while chars.peek().is_some_and(|&(i, _)| i < end) {
+                chars.next();
+            }
+            tokens.push(Token::Unit(&input[start..end]));
+        } else {
+            return None;
+        }
+    }
+
+    Some(tokens)
+}
+
+/// Find the end of the unit name starting at `start`.
+///
+/// Digits only belong to the unit as a power (`m2`, `m^3`), so the `30` in
+/// `2h30m` starts the next term.
+fn unit_end(input: &str, start: usize) -> usize {
+    let bytes = input.as_bytes();
+    let mut end = start;
+    for (i, c) in input[start..].char_indices() {
+        let i = start + i;
+        let power = match c {
+            '^' => true,
+            '2' | '3' => {
+                let next = bytes.get(i + 1).copied().unwrap_or(b' ');
+                i > start && !next.is_ascii_alphanumeric() && next != b'.'
+            }
+            _ => false,
+        };
+        if !(c.is_alphabetic() || matches!(c, '°' | '/' | '²' | '³') || power) {
+            break;
+        }
+        end = i + c.len_utf8();
+    }
+    end
+}
+
+/// A number with a unit name.
+struct Term<'a> {
+    value: f64,
+    unit: &'a str,
+}
+
+/// Group tokens into signed terms. Terms without an operator between them
+/// are added, as in `2h 30m`.
+fn terms<'a>(tokens: &[Token<'a>]) -> Option<Vec<Term<'a>>> {
+    let mut terms = Vec::new();
+    let mut tokens = tokens.iter().peekable();
+
+    while tokens.peek().is_some() {
+        let sign = match tokens.peek() {
+            Some(Token::Plus) => {
+                tokens.next();
+                1.0
+            }
+            Some(Token::Minus) => {
+                tokens.next();
+                -1.0
+            }
+            _ => 1.0,
+        };
+        let Some(Token::Number(value)) = tokens.next() else {
+            return None;
+        };
+        let Some(Token::Unit(unit)) = tokens.next() else {
+            return None;
+        };
+        terms.push(Term {
+            value: sign * value,
+            unit,
+        });
+    }
+
+    Some(terms)
+}
+
+/// A parsed conversion.
+#[derive(Debug, PartialEq)]
+struct Conversion<'a> {
+    /// Value in the target unit
+    value: f64,
+    /// Target unit as written
+    unit: &'a str,
+}
+
+/// Evaluate a quantity in a target unit, if both parse and are compatible.
+fn convert<'a>(quantity: &'a str, target: Option<&'a str>) -> Option<Conversion<'a>> {
+    let terms = terms(&tokenize(quantity)?)?;
+    let first = terms.first()?;
+    let target_name = target.unwrap_or(first.unit);
+    if target_name.is_empty() || target_name.contains(char::is_whitespace) {
+        return None;
+    }
+
+    let candidates: Vec<Vec<Unit>> = terms.iter().map(|t| lookup(t.unit)).collect();
+    let target_candidates = lookup(target_name);
+
+    // Use the first dimension of the first term every other unit can have
+    let dimension = candidates[0].iter().map(|u| u.dimension).find(|d| {
+        candidates
+            .iter()
+            .chain(std::iter::once(&target_candidates))
+            .all(|units| units.iter().any(|u| u.dimension == *d))
+    })?;
+    let pick = |units: &[Unit]| units.iter().copied().find(|u| u.dimension == dimension);
+
+    let units: Vec<Unit> = candidates.iter().map(|c| pick(c)).collect::<Option<_>>()?;
+    let target_unit = pick(&target_candidates)?;
+
+    // Sums of temperatures have no meaning with offset scales
+    if terms.len() > 1 && dimension == Temperature {
+        return None;
+    }
+
+    let base: f64 = terms
+        .iter()
+        .zip(&units)
+        .map(|(term, unit)| unit.to_base(term.value))
+        .sum();
+
+    Some(Conversion {
+        value: target_unit.of_base(base),
+        unit: target_name,
+    })
+}
+
+/// Parse a conversion or quantity sum.
+///
+/// Without a target unit, only sums of several terms count, so that a
+/// search like `7 days` is not mistaken for a calculation.
+fn parse(input: &str) -> Option<Conversion<'_>> {
+    let input = input.trim();
+
+    // The last keyword that leaves a valid target wins, so `5 ft in in` works
+    let keywords: Vec<_> = TARGET_KEYWORD.find_iter(input).collect();
+    for keyword in keywords.iter().rev() {
+        let quantity = input[..keyword.start()].trim();
+        let target = input[keyword.end()..].trim();
+        if let Some(conversion) = convert(quantity, Some(target)) {
+            return Some(conversion);
+        }
+    }
+
+    let tokens = tokenize(input)?;
+    if terms(&tokens)?.len() < 2 {
+        return None;
+    }
+    convert(input, None)
+}
+
+/// Check if the input is a unit conversion or sum of quantities.
+pub fn is_conversion(input: &str) -> bool {
+    parse(input).is_some()
+}
+
+/// Evaluate a unit conversion like `5 mi in km` or a sum like `2h 30m`.
+///
+/// Returns None if the input is not a conversion between known units.
+pub fn evaluate_conversion(input: &str) -> Option<CalcResult> {
+    let conversion = parse(input)?;
+    let expression = input.trim().to_string();
+
+    if !conversion.value.is_finite() {
+        return Some(CalcResult::Error {
+            expression,
+            message: "Not a Number".to_string(),
+        });
+    }
+
+    Some(CalcResult::Success {
+        expression,
+        display_result: format!("{} {}", format_display(conversion.value), conversion.unit),
+        clipboard_result: format_clipboard(conversion.value),
+        value: conversion.value,
+        exact: None,
+        integer: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn display(input: &str) -> String {
+        evaluate_conversion(input)
+            .map(|result| result.display().to_string())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(display("5 mi in km"), "8.04672 km");
+        assert_eq!(display("72 F to C"), "22.2222222222 C");
+        assert_eq!(display("-40 °C in °F"), "-40 °F");
+        assert_eq!(display("3 GiB in MB"), "3,221.225472 MB");
+        assert_eq!(display("1 MB to KiB"), "976.5625 KiB");
+        assert_eq!(display("100 Mbit in MB"), "12.5 MB");
+        assert_eq!(display("10 kg as lb"), "22.0462262185 lb");
+        assert_eq!(display("1 acre in m2"), "4,046.8564224 m2");
+        assert_eq!(display("2 L -> cm³"), "2,000 cm³");
+        assert_eq!(display("100 km/h in mph"), "62.1371192237 mph");
+        assert_eq!(display("5 ft in in"), "60 in");
+        assert_eq!(display("3 kilometres in miles"), "1.8641135767 miles");
+    }
+
+    #[test]
+    fn test_sums() {
+        // `m` is a minute next to hours and a metre next to kilometres
+        assert_eq!(display("2h 30m in minutes"), "150 minutes");
+        assert_eq!(display("2h30m"), "2.5 h");
+        assert_eq!(display("1 km + 300 m"), "1.3 km");
+        assert_eq!(display("1 day - 90 min in h"), "22.5 h");
+        assert_eq!(
+            evaluate_conversion("1h 30m").unwrap().clipboard(),
+            Some("1.5")
+        );
+    }
+
+    #[test]
+    fn test_non_conversions_rejected() {
+        assert!(!is_conversion("7 days"));
+        assert!(!is_conversion("5 km in kg"));
+        assert!(!is_conversion("10 C + 5 C"));
+        assert!(!is_conversion("2 + 2"));
+        assert!(!is_conversion("log in to firefox"));
+        assert!(!is_conversion("5 furlongs in m"));
+    }
+}
@@ -1,7 +1,9 @@
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
+use std::io::Read;
 
-use crate::ipc::{Command, client};
+use crate::dmenu::{DmenuRequest, parse_entries};
+use crate::ipc::{Command, LaunchMode, Response, ShowOptions, client};
 
 #[derive(Parser)]
 #[command(name = "zlaunch")]
@@ -14,23 +16,105 @@ pub struct Cli {
 #[derive(Subcommand)]
 pub enum Commands {
     /// Show the launcher window
-    Show,
+    Show(ShowArgs),
     /// Hide the launcher window
     Hide,
     /// Toggle the launcher window visibility
-    Toggle,
+    Toggle(ShowArgs),
+    /// Reload the config file and rescan applications
+    Reload,
+    /// Forget the launch history used for ranking
+    ResetHistory,
     /// Quit the daemon
     Quit,
+    /// Show the daemon status
+    Status {
+        /// Print the raw JSON response
+        #[arg(long)]
+        json: bool,
+    },
+    /// List the applications known to the daemon
+    ListApplications {
+        /// Print the raw JSON response
+        #[arg(long)]
+        json: bool,
+    },
+    /// List the open windows reported by the compositor
+    ListWindows {
+        /// Print the raw JSON response
+        #[arg(long)]
+        json: bool,
+    },
+    /// Pick from lines read on stdin and print the selection, like dmenu
+    Dmenu(DmenuArgs),
+}
+
+/// Options for opening the launcher.
+#[derive(Args, Debug, Default)]
+pub struct ShowArgs {
+    /// Open directly into a view: apps, windows, emojis, calc or a submenu id
+    #[arg(long)]
+    pub mode: Option<LaunchMode>,
+    /// Prefill the search input
+    #[arg(long)]
+    pub query: Option<String>,
+}
+
+/// Options for `zlaunch dmenu`.
+#[derive(Args, Debug, Default)]
+pub struct DmenuArgs {
+    /// Text shown in the empty search input
+    #[arg(short, long)]
+    pub prompt: Option<String>,
+    /// Mark several entries with shift-enter before confirming
+    #[arg(long)]
+    pub multi_select: bool,
+    /// Print the typed text when it matches no entry
+    #[arg(long)]
+    pub allow_custom: bool,
+    /// Print the index of the chosen lines instead of their text
+    #[arg(long)]
+    pub index: bool,
+}
+
+impl ShowArgs {
+    fn to_options(&self) -> ShowOptions {
+        ShowOptions {
+            mode: self.mode.clone(),
+            query: self.query.clone(),
+        }
+    }
 }
 
 impl Commands {
     /// Convert to IPC command.
     pub fn to_ipc_command(&self) -> Command {
         match self {
-            Commands::Show => Command::Show,
+            Commands::Show(args) => Command::Show(args.to_options()),
             Commands::Hide => Command::Hide,
-            Commands::Toggle => Command::Toggle,
+            Commands::Toggle(args) => Command::Toggle(args.to_options()),
+            Commands::Reload => Command::Reload,
+            Commands::ResetHistory => Command::ResetHistory,
             Commands::Quit => Command::Quit,
+            Commands::Status { .. } => Command::Status,
+            Commands::ListApplications { .. } => Command::ListApplications,
+            Commands::ListWindows { .. } => Command::ListWindows,
+            Commands::Dmenu(args) => Command::Dmenu(DmenuRequest {
+                entries: Vec::new(),
+                prompt: args.prompt.clone(),
+                multi_select: args.multi_select,
+                allow_custom: args.allow_custom,
+            }),
+        }
+    }
+
+    /// Whether the response should be printed as JSON.
+    fn json(&self) -> bool {
+        match self {
+            Commands::Status { json }
+            | Commands::ListApplications { json }
+            | Commands::ListWindows { json } => *json,
+            _ => false,
         }
     }
 }
@@ -41,6 +125,107 @@ pub fn handle_client_command(cmd: Commands) -> Result<()> {
         anyhow::bail!("zlaunch daemon is not running. Start it first by running: zlaunch");
     }
 
-    client::send_command(cmd.to_ipc_command())?;
+    let mut command = cmd.to_ipc_command();
+    if let Command::Dmenu(ref mut request) = command {
+        let mut input = Vec::new();
+        std::io::stdin().read_to_end(&mut input)?;
+        request.entries = parse_entries(&String::from_utf8_lossy(&input));
+    }
+
+    let response = client::send_command(command)?;
+    match response {
+        Response::Error(e) => anyhow::bail!(e),
+        // Scripts rely on a non-zero exit status to detect a cancelled menu
+        Response::Cancelled => std::process::exit(1),
+        _ => {}
+    }
+
+    if cmd.json() {
+        println!("{}", serde_json::to_string_pretty(&response)?);
+    } else {
+        print_response(&response, &cmd);
+    }
     Ok(())
 }
+
+/// Print a response in a human-readable, tab-separated form.
+fn print_response(response: &Response, cmd: &Commands) {
+    match response {
+        Response::Ok | Response::Error(_) | Response::Cancelled => {}
+        Response::Status(status) => {
+            println!("version\t{}", status.version);
+            println!("visible\t{}", status.visible);
+            println!("compositor\t{}", status.compositor);
+            println!("applications\t{}", status.applications);
+            match status.windows {
+                Some(windows) => println!("windows\t{}", windows),
+                None => println!("windows\tunavailable"),
+            }
+            println!("actions\t{}", status.actions);
+        }
+        Response::Applications(apps) => {
+            for app in apps {
+                println!("{}\t{}", app.id, app.name);
+            }
+        }
+        Response::Windows(windows) => {
+            for window in windows {
+                println!(
+                    "{}\t{}\t{}\t{}",
+                    window.address, window.workspace, window.class, window.title
+                );
+            }
+        }
+        Response::Selected(selection) => {
+            let print_index = matches!(cmd, Commands::Dmenu(args) if args.index);
+            for entry in selection {
+                println!("{}", entry.format(print_index));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_show_with_mode_and_query() {
+        let cli = Cli::try_parse_from(["zlaunch", "show", "--mode", "windows", "--query", "term"])
+            .unwrap();
+        match cli.command.unwrap().to_ipc_command() {
+            Command::Show(options) => {
+                assert_eq!(options.mode, Some(LaunchMode::Windows));
+                assert_eq!(options.query.as_deref(), Some("term"));
+            }
+            _ => panic!("expected show command"),
+        }
+
+        let cli = Cli::try_parse_from(["zlaunch", "toggle"]).unwrap();
+        match cli.command.unwrap().to_ipc_command() {
+            Command::Toggle(options) => assert_eq!(options, ShowOptions::default()),
+            _ => panic!("expected toggle command"),
+        }
+    }
+
+    #[test]
+    fn test_dmenu_options() {
+        let cli = Cli::try_parse_from([
+            "zlaunch",
+            "dmenu",
+            "-p",
+            "Pick",
+            "--multi-select",
+            "--index",
+        ])
+        .unwrap();
+        match cli.command.unwrap().to_ipc_command() {
+            Command::Dmenu(request) => {
+                assert_eq!(request.prompt.as_deref(), Some("Pick"));
+                assert!(request.multi_select);
+                assert!(!request.allow_custom);
+            }
+            _ => panic!("expected dmenu command"),
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! Clipboard history.
+//!
+//! When enabled in the `[clipboard]` config table, the daemon watches the
+//! Wayland clipboard through wlr-data-control and records every text and
+//! image copied, except content a password manager marks as sensitive. The
+//! history is bounded by `max_entries`; pinned entries are kept regardless.
+//!
+//! Entries are stored in `$XDG_STATE_HOME/zlaunch/clipboard/`.
+
+mod store;
+#[cfg(unix)]
+mod watcher;
+
+pub use store::{Clip, ClipboardContent, ClipboardEntry, ClipboardHistory};
+
+use crate::calculator::copy_to_clipboard;
+use crate::config::config;
+use crate::history::{history_path, now};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tracing::warn;
+
+lazy_static::lazy_static! {
+    /// Global clipboard history, loaded on first use.
+    static ref HISTORY: Mutex<ClipboardHistory> =
+        Mutex::new(ClipboardHistory::load(&clipboard_dir()));
+}
+
+/// The clipboard watcher, once started on a compositor supporting it.
+#[cfg(unix)]
+static WATCHER: std::sync::OnceLock<watcher::ClipboardWatcher> = std::sync::OnceLock::new();
+
+/// Get the directory the clipboard history is stored in.
+pub fn clipboard_dir() -> PathBuf {
+    history_path().with_file_name("clipboard")
+}
+
+/// Start recording the clipboard, if enabled in the config and not started
+/// yet.
+///
+/// Does nothing on platforms or compositors without wlr-data-control.
+pub fn start_watcher() {
+    if !config().clipboard.enabled {
+        return;
+    }
+
+    #[cfg(unix)]
+    if WATCHER.get().is_some() {
+        return;
+    }
+
+    #[cfg(unix)]
+    match watcher::ClipboardWatcher::new(record) {
+        Some(watcher) => {
+            let _ = WATCHER.set(watcher);
+        }
+        None => tracing::info!("Clipboard history not available on this compositor"),
+    }
+}
+
+/// Record copied content and persist the history.
+///
+/// Does nothing if the clipboard history is disabled in the config.
+pub fn record(clip: Clip) {
+    let config = config();
+    if !config.clipboard.enabled {
+        return;
+    }
+
+    let mut history = HISTORY.lock().unwrap();
+    if let Err(e) = history.push(clip, now(), config.clipboard.max_entries) {
+        warn!(%e, "Failed to save clipboard history");
+    }
+}
+
+/// Get the recorded entries, pinned ones first.
+pub fn clipboard_entries() -> Vec<ClipboardEntry> {
+    HISTORY.lock().unwrap().entries()
+}
+
+/// Get the path of the file holding an entry's image.
+pub fn image_path(entry: &ClipboardEntry) -> Option<PathBuf> {
+    HISTORY.lock().unwrap().image_path(entry)
+}
+
+/// Pin or unpin an entry. Returns whether it is pinned now.
+pub fn toggle_pin(id: u64) -> anyhow::Result<bool> {
+    HISTORY.lock().unwrap().toggle_pin(id)
+}
+
+/// Delete an entry.
+pub fn delete(id: u64) -> anyhow::Result<()> {
+    HISTORY.lock().unwrap().delete(id)
+}
+
+/// Put an entry back on the clipboard.
+pub fn restore(id: u64) -> anyhow::Result<()> {
+    let clip = HISTORY.lock().unwrap().read(id)?;
+
+    #[cfg(unix)]
+    if let Some(watcher) = WATCHER.get() {
+        return watcher.set_selection(clip);
+    }
+
+    match clip {
+        Clip::Text(text) => copy_to_clipboard(&text).map_err(anyhow::Error::msg),
+        Clip::Image { .. } => {
+            anyhow::bail!("Restoring images needs a compositor supporting wlr-data-control")
+        }
+    }
+}
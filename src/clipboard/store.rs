@@ -0,0 +1,344 @@
+//! Persisted clipboard history.
+//!
+//! The index of entries is stored as JSON in `index.json`, and images in
+//! files named after their hash next to it. Pinned entries are listed first
+//! and never dropped to make room for new ones.
+
+use crate::history::{write_file, write_json_atomic};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Largest text or image that is recorded, in bytes.
+pub const MAX_ENTRY_BYTES: usize = 32 * 1024 * 1024;
+
+/// Content read from the clipboard, before it is recorded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Clip {
+    Text(String),
+    Image { mime_type: String, data: Vec<u8> },
+}
+
+/// Content of a recorded entry.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum ClipboardContent {
+    Text {
+        text: String,
+    },
+    /// An image stored in `file` in the history directory.
+    Image {
+        mime_type: String,
+        file: String,
+        /// Size of the image data in bytes
+        size: usize,
+        /// Hash of the image data, to recognize the same image copied again
+        hash: u64,
+    },
+}
+
+/// A recorded clipboard entry.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClipboardEntry {
+    pub id: u64,
+    pub content: ClipboardContent,
+    /// Unix time the content was last copied
+    pub copied_at: u64,
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+/// Recorded clipboard entries, most recently copied first.
+#[derive(Debug)]
+pub struct ClipboardHistory {
+    entries: Vec<ClipboardEntry>,
+    /// Directory with the index and the image files.
+    dir: PathBuf,
+}
+
+impl ClipboardHistory {
+    /// Load the history from a directory.
+    ///
+    /// A missing or unreadable index yields an empty history that will be
+    /// written to `dir` on the next change.
+    pub fn load(dir: &Path) -> Self {
+        let path = dir.join("index.json");
+        let entries = match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                warn!(%e, path = %path.display(), "Ignoring corrupt clipboard history file");
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+
+        Self {
+            entries,
+            dir: dir.to_path_buf(),
+        }
+    }
+
+    /// Get the entries, pinned ones first, each group most recent first.
+    pub fn entries(&self) -> Vec<ClipboardEntry> {
+        let (mut pinned, unpinned): (Vec<_>, Vec<_>) =
+            self.entries.iter().cloned().partition(|entry| entry.pinned);
+        pinned.extend(unpinned);
+        pinned
+    }
+
+    /// Get the path of the file holding an entry's image.
+    pub fn image_path(&self, entry: &ClipboardEntry) -> Option<PathBuf> {
+        match &entry.content {
+            ClipboardContent::Image { file, .. } => Some(self.dir.join(file)),
+            ClipboardContent::Text { .. } => None,
+        }
+    }
+
+    /// Read the content of an entry as its mime type and data.
+    pub fn read(&self, id: u64) -> anyhow::Result<Clip> {
+        let entry = self.find(id)?;
+        match &entry.content {
+            ClipboardContent::Text { text } => Ok(Clip::Text(text.clone())),
+            ClipboardContent::Image {
+                mime_type, file, ..
+            } => Ok(Clip::Image {
+                mime_type: mime_type.clone(),
+                data: std::fs::read(self.dir.join(file))?,
+            }),
+        }
+    }
+
+    /// Record copied content at unix time `now`, keeping at most
+    /// `max_entries` unpinned entries.
+    ///
+    /// Content that is already recorded moves to the top instead. Empty text
+    /// and content larger than [`MAX_ENTRY_BYTES`] are ignored.
+    pub fn push(&mut self, clip: Clip, now: u64, max_entries: usize) -> anyhow::Result<()> {
+        let existing = match &clip {
+            Clip::Text(text) if text.trim().is_empty() || text.len() > MAX_ENTRY_BYTES => {
+                return Ok(());
+            }
+            Clip::Image { data, .. } if data.is_empty() || data.len() > MAX_ENTRY_BYTES => {
+                return Ok(());
+            }
+            Clip::Text(text) => self.entries.iter().position(
+                |entry| matches!(&entry.content, ClipboardContent::Text { text: t } if t == text),
+            ),
+            Clip::Image { data, .. } => {
+                let hash = fnv1a(data);
+                self.entries.iter().position(|entry| {
+                    matches!(entry.content, ClipboardContent::Image { size, hash: h, .. }
+                        if size == data.len() && h == hash)
+                })
+            }
+        };
+
+        let entry = match existing {
+            Some(index) => {
+                let mut entry = self.entries.remove(index);
+                entry.copied_at = now;
+                entry
+            }
+            None => {
+                let id = self.entries.iter().map(|e| e.id + 1).max().unwrap_or(0);
+                let content = match clip {
+                    Clip::Text(text) => ClipboardContent::Text { text },
+                    Clip::Image { mime_type, data } => {
+                        // Named after the content, so a cached thumbnail of a
+                        // deleted image is never shown for a new one
+                        let hash = fnv1a(&data);
+                        let extension = mime_type.rsplit('/').next().unwrap_or("bin");
+                        let file = format!("{:016x}.{}", hash, extension);
+                        create_private_dir(&self.dir)?;
+                        write_file(&self.dir.join(&file), &data, Some(0o600))?;
+                        ClipboardContent::Image {
+                            mime_type,
+                            file,
+                            size: data.len(),
+                            hash,
+                        }
+                    }
+                };
+                ClipboardEntry {
+                    id,
+                    content,
+                    copied_at: now,
+                    pinned: false,
+                }
+            }
+        };
+        self.entries.insert(0, entry);
+
+        // Drop the oldest unpinned entries beyond the limit
+        let mut unpinned = 0;
+        let mut dropped = Vec::new();
+        self.entries.retain(|entry| {
+            if entry.pinned {
+                return true;
+            }
+            unpinned += 1;
+            if unpinned > max_entries {
+                dropped.push(entry.clone());
+                return false;
+            }
+            true
+        });
+        for entry in &dropped {
+            self.remove_file(entry);
+        }
+
+        self.save()
+    }
+
+    /// Pin or unpin an entry. Returns whether it is pinned now.
+    pub fn toggle_pin(&mut self, id: u64) -> anyhow::Result<bool> {
+        let entry = self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.id == id)
+            .ok_or_else(|| anyhow::anyhow!("No clipboard entry with id {}", id))?;
+        entry.pinned = !entry.pinned;
+        let pinned = entry.pinned;
+
+        self.save()?;
+        Ok(pinned)
+    }
+
+    /// Delete an entry and its image.
+    pub fn delete(&mut self, id: u64) -> anyhow::Result<()> {
+        let entry = self.find(id)?.clone();
+        self.entries.retain(|e| e.id != id);
+        self.remove_file(&entry);
+        self.save()
+    }
+
+    fn find(&self, id: u64) -> anyhow::Result<&ClipboardEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.id == id)
+            .ok_or_else(|| anyhow::anyhow!("No clipboard entry with id {}", id))
+    }
+
+    fn remove_file(&self, entry: &ClipboardEntry) {
+        if let Some(path) = self.image_path(entry)
+            && let Err(e) = std::fs::remove_file(&path)
+        {
+            warn!(%e, path = %path.display(), "Failed to remove clipboard image");
+        }
+    }
+
+    /// Persist the index.
+    fn save(&self) -> anyhow::Result<()> {
+        create_private_dir(&self.dir)?;
+        write_json_atomic(&self.dir.join("index.json"), &self.entries, Some(0o600))
+    }
+}
+
+/// Create a directory only the user can read, with its missing parents.
+fn create_private_dir(dir: &Path) -> std::io::Result<()> {
+    let mut builder = std::fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder.create(dir)
+}
+
+/// 64-bit FNV-1a hash, stable across builds unlike the std hasher.
+fn fnv1a(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("zlaunch-clipboard-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn texts(history: &ClipboardHistory) -> Vec<String> {
+        history
+            .entries()
+            .into_iter()
+            .map(|entry| match entry.content {
+                ClipboardContent::Text { text } => text,
+                ClipboardContent::Image { file, .. } => file,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_push_dedup_and_limit() {
+        let dir = test_dir("push");
+        let mut history = ClipboardHistory::load(&dir);
+        for (now, text) in ["a", "b", "a", "c", "  "].into_iter().enumerate() {
+            history
+                .push(Clip::Text(text.to_string()), now as u64, 2)
+                .unwrap();
+        }
+        assert_eq!(texts(&history), ["c", "a"]);
+        assert_eq!(history.entries()[1].copied_at, 2);
+
+        let loaded = ClipboardHistory::load(&dir);
+        assert_eq!(texts(&loaded), ["c", "a"]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_pinned_entries_are_kept() {
+        let dir = test_dir("pin");
+        let mut history = ClipboardHistory::load(&dir);
+        history.push(Clip::Text("keep".to_string()), 0, 1).unwrap();
+        let id = history.entries()[0].id;
+        assert!(history.toggle_pin(id).unwrap());
+
+        history.push(Clip::Text("b".to_string()), 1, 1).unwrap();
+        history.push(Clip::Text("c".to_string()), 2, 1).unwrap();
+        assert_eq!(texts(&history), ["keep", "c"]);
+
+        history.delete(id).unwrap();
+        assert_eq!(texts(&ClipboardHistory::load(&dir)), ["c"]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_images() {
+        let dir = test_dir("image");
+        let mut history = ClipboardHistory::load(&dir);
+        let image = Clip::Image {
+            mime_type: "image/png".to_string(),
+            data: vec![0x89, b'P', b'N', b'G'],
+        };
+        history.push(image.clone(), 0, 10).unwrap();
+        history.push(Clip::Text("text".to_string()), 1, 10).unwrap();
+        history.push(image.clone(), 2, 10).unwrap();
+        let file = format!("{:016x}.png", fnv1a(&[0x89, b'P', b'N', b'G']));
+        assert_eq!(texts(&history), [file.as_str(), "text"]);
+
+        let entry = &history.entries()[0];
+        assert_eq!(history.read(entry.id).unwrap(), image);
+        let path = history.image_path(entry).unwrap();
+        assert!(path.exists());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = |path: &Path| std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+            assert_eq!(mode(&dir), 0o700);
+            assert_eq!(mode(&path), 0o600);
+            assert_eq!(mode(&dir.join("index.json")), 0o600);
+        }
+
+        // Dropping an image removes its file
+        history.push(Clip::Text("other".to_string()), 3, 1).unwrap();
+        assert!(!path.exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
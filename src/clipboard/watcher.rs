@@ -0,0 +1,468 @@
+//! Clipboard access using the wlr-data-control protocol.
+//!
+//! Supported by wlroots compositors, KWin and others. Unlike the regular
+//! data device, data control lets a client without a focused surface see
+//! every selection change and set the selection itself.
+//!
+//! A background thread keeps dispatching protocol events. Each new selection
+//! is read on its own thread and passed to a callback, so a slow source
+//! cannot hold up the event loop.
+
+use super::store::{Clip, MAX_ENTRY_BYTES};
+use crate::compositor::wayland::{self, IgnoreEvents};
+use anyhow::{Context, Result};
+use std::io::{Read, Write};
+use std::os::fd::AsFd;
+use std::sync::{Arc, Mutex};
+use tracing::debug;
+use wayland_client::globals::GlobalListContents;
+use wayland_client::protocol::{wl_registry::WlRegistry, wl_seat::WlSeat};
+use wayland_client::{
+    Connection, Dispatch, Proxy, QueueHandle, delegate_dispatch, event_created_child,
+};
+use wayland_protocols_wlr::data_control::v1::client::{
+    zwlr_data_control_device_v1::{self, ZwlrDataControlDeviceV1},
+    zwlr_data_control_manager_v1::{self, ZwlrDataControlManagerV1},
+    zwlr_data_control_offer_v1::{self, ZwlrDataControlOfferV1},
+    zwlr_data_control_source_v1::{self, ZwlrDataControlSourceV1},
+};
+
+/// Mime type password managers add to entries that must not be recorded.
+const PASSWORD_HINT: &str = "x-kde-passwordManagerHint";
+
+/// Text mime types, in order of preference.
+const TEXT_MIME_TYPES: &[&str] = &[
+    "text/plain;charset=utf-8",
+    "UTF8_STRING",
+    "text/plain",
+    "STRING",
+    "TEXT",
+];
+
+/// Called with each new clipboard content.
+type ClipHandler = Arc<dyn Fn(Clip) + Send + Sync>;
+
+/// Wayland client watching and setting the clipboard.
+pub struct ClipboardWatcher {
+    connection: Connection,
+    manager: ZwlrDataControlManagerV1,
+    device: ZwlrDataControlDeviceV1,
+    qh: QueueHandle<WatcherState>,
+}
+
+impl ClipboardWatcher {
+    /// Start watching the clipboard, calling `on_clip` with each new content.
+    ///
+    /// Returns None if there is no Wayland display or the compositor
+    /// does not support the protocol.
+    pub fn new(on_clip: impl Fn(Clip) + Send + Sync + 'static) -> Option<Self> {
+        wayland::connect("wlr-data-control", |connection| {
+            Self::from_connection(connection, Arc::new(on_clip))
+        })
+    }
+
+    /// Bind the data control manager and start dispatching its events.
+    fn from_connection(connection: Connection, on_clip: ClipHandler) -> Result<Self> {
+        let (globals, queue) = wayland::init_registry::<WatcherState>(&connection)?;
+        let qh = queue.handle();
+
+        let manager: ZwlrDataControlManagerV1 = globals
+            .bind(&qh, 1..=2, ())
+            .context("Compositor does not support wlr-data-control")?;
+        let seat = wayland::bind_seat(&globals, &qh)?;
+        let device = manager.get_data_device(&seat, &qh, ());
+
+        let state = WatcherState { on_clip };
+        wayland::dispatch_in_background(queue, state, "clipboard no longer watched");
+
+        Ok(Self {
+            connection,
+            manager,
+            device,
+            qh,
+        })
+    }
+
+    /// Make `clip` the clipboard content.
+    pub fn set_selection(&self, clip: Clip) -> Result<()> {
+        let (mime_types, data) = match clip {
+            Clip::Text(text) => (
+                TEXT_MIME_TYPES.iter().map(|m| m.to_string()).collect(),
+                text.into_bytes(),
+            ),
+            Clip::Image { mime_type, data } => (vec![mime_type], data),
+        };
+
+        let source = self.manager.create_data_source(&self.qh, Arc::new(data));
+        for mime_type in mime_types {
+            source.offer(mime_type);
+        }
+        self.device.set_selection(Some(&source));
+        self.connection
+            .flush()
+            .context("Failed to send set_selection request")?;
+        Ok(())
+    }
+}
+
+/// Pick the mime type to read from an offer, preferring text.
+fn preferred_mime_type(mime_types: &[String]) -> Option<&str> {
+    TEXT_MIME_TYPES
+        .iter()
+        .find_map(|preferred| mime_types.iter().find(|m| m == preferred))
+        .or_else(|| mime_types.iter().find(|m| *m == "image/png"))
+        .or_else(|| mime_types.iter().find(|m| m.starts_with("image/")))
+        .map(String::as_str)
+}
+
+/// Read the content of an offer on a background thread.
+fn receive(
+    offer: &ZwlrDataControlOfferV1,
+    mime_type: &str,
+    connection: &Connection,
+    on_clip: ClipHandler,
+) -> Result<()> {
+    let (mut reader, writer) = std::io::pipe().context("Failed to create pipe")?;
+    offer.receive(mime_type.to_string(), writer.as_fd());
+    // The request holds its own copy of the fd, so the read ends once the
+    // source closes it
+    drop(writer);
+    connection
+        .flush()
+        .context("Failed to send receive request")?;
+
+    let mime_type = mime_type.to_string();
+    std::thread::spawn(move || {
+        let mut data = Vec::new();
+        if let Err(e) = (&mut reader)
+            .take(MAX_ENTRY_BYTES as u64 + 1)
+            .read_to_end(&mut data)
+        {
+            debug!(%e, "Failed to read clipboard content");
+            return;
+        }
+
+        let clip = if mime_type.starts_with("image/") {
+            Clip::Image { mime_type, data }
+        } else {
+            Clip::Text(String::from_utf8_lossy(&data).into_owned())
+        };
+        on_clip(clip);
+    });
+    Ok(())
+}
+
+/// Passes each new selection to the handler.
+struct WatcherState {
+    on_clip: ClipHandler,
+}
+
+delegate_dispatch!(WatcherState: [WlRegistry: GlobalListContents] => IgnoreEvents);
+delegate_dispatch!(WatcherState: [WlSeat: ()] => IgnoreEvents);
+
+impl Dispatch<ZwlrDataControlManagerV1, ()> for WatcherState {
+    fn event(
+        _: &mut Self,
+        _: &ZwlrDataControlManagerV1,
+        _: zwlr_data_control_manager_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<ZwlrDataControlDeviceV1, ()> for WatcherState {
+    fn event(
+        state: &mut Self,
+        device: &ZwlrDataControlDeviceV1,
+        event: zwlr_data_control_device_v1::Event,
+        _: &(),
+        connection: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        use zwlr_data_control_device_v1::Event;
+
+        match event {
+            Event::Selection { id: Some(offer) } => {
+                let mime_types = offer
+                    .data::<Mutex<Vec<String>>>()
+                    .map(|m| m.lock().unwrap_or_else(|e| e.into_inner()).clone())
+                    .unwrap_or_default();
+
+                if mime_types.iter().any(|m| m == PASSWORD_HINT) {
+                    debug!("Skipping clipboard content marked as a password");
+                } else if let Some(mime_type) = preferred_mime_type(&mime_types)
+                    && let Err(e) = receive(&offer, mime_type, connection, state.on_clip.clone())
+                {
+                    debug!(%e, "Failed to receive clipboard content");
+                }
+                offer.destroy();
+            }
+            // Only the clipboard is recorded, not the primary selection
+            Event::PrimarySelection { id: Some(offer) } => offer.destroy(),
+            Event::Finished => {
+                debug!("Clipboard data device finished");
+                device.destroy();
+            }
+            _ => {}
+        }
+    }
+
+    event_created_child!(WatcherState, ZwlrDataControlDeviceV1, [
+        zwlr_data_control_device_v1::EVT_DATA_OFFER_OPCODE => (ZwlrDataControlOfferV1, Mutex::new(Vec::new())),
+    ]);
+}
+
+impl Dispatch<ZwlrDataControlOfferV1, Mutex<Vec<String>>> for WatcherState {
+    fn event(
+        _: &mut Self,
+        _: &ZwlrDataControlOfferV1,
+        event: zwlr_data_control_offer_v1::Event,
+        mime_types: &Mutex<Vec<String>>,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        if let zwlr_data_control_offer_v1::Event::Offer { mime_type } = event {
+            mime_types
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(mime_type);
+        }
+    }
+}
+
+impl Dispatch<ZwlrDataControlSourceV1, Arc<Vec<u8>>> for WatcherState {
+    fn event(
+        _: &mut Self,
+        source: &ZwlrDataControlSourceV1,
+        event: zwlr_data_control_source_v1::Event,
+        data: &Arc<Vec<u8>>,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        match event {
+            zwlr_data_control_source_v1::Event::Send { fd, .. } => {
+                let data = data.clone();
+                std::thread::spawn(move || {
+                    if let Err(e) = std::fs::File::from(fd).write_all(&data) {
+                        debug!(%e, "Failed to send clipboard content");
+                    }
+                });
+            }
+            zwlr_data_control_source_v1::Event::Cancelled => source.destroy(),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compositor::test_display::{TestDisplay, TestSeat, start_display};
+    use std::sync::mpsc;
+    use std::time::Duration;
+    use wayland_protocols_wlr::data_control::v1::server::{
+        zwlr_data_control_device_v1 as server_device,
+        zwlr_data_control_manager_v1 as server_manager, zwlr_data_control_offer_v1 as server_offer,
+        zwlr_data_control_source_v1 as server_source,
+    };
+    use wayland_server::protocol::wl_seat;
+    use wayland_server::{Client, DataInit, DisplayHandle, GlobalDispatch, New, Resource};
+
+    /// A headless compositor that only implements data control.
+    ///
+    /// It offers each of `selections` in turn when the data device is
+    /// created, and reads back every selection the client sets.
+    struct TestCompositor {
+        /// Mime types and content of each selection
+        selections: Vec<(Vec<&'static str>, &'static str)>,
+        /// Reports the text of each selection set by the client
+        restored: mpsc::Sender<String>,
+    }
+
+    impl GlobalDispatch<server_manager::ZwlrDataControlManagerV1, ()> for TestCompositor {
+        fn bind(
+            _: &mut Self,
+            _: &DisplayHandle,
+            _: &Client,
+            resource: New<server_manager::ZwlrDataControlManagerV1>,
+            _: &(),
+            data_init: &mut DataInit<'_, Self>,
+        ) {
+            data_init.init(resource, ());
+        }
+    }
+
+    impl wayland_server::Dispatch<server_manager::ZwlrDataControlManagerV1, ()> for TestCompositor {
+        fn request(
+            state: &mut Self,
+            client: &Client,
+            _: &server_manager::ZwlrDataControlManagerV1,
+            request: server_manager::Request,
+            _: &(),
+            dh: &DisplayHandle,
+            data_init: &mut DataInit<'_, Self>,
+        ) {
+            match request {
+                server_manager::Request::GetDataDevice { id, .. } => {
+                    let device = data_init.init(id, ());
+                    for (mime_types, content) in &state.selections {
+                        let offer = client
+                            .create_resource::<server_offer::ZwlrDataControlOfferV1, &'static str, Self>(
+                                dh,
+                                device.version(),
+                                content,
+                            )
+                            .unwrap();
+                        device.data_offer(&offer);
+                        for mime_type in mime_types {
+                            offer.offer(mime_type.to_string());
+                        }
+                        device.selection(Some(&offer));
+                    }
+                }
+                server_manager::Request::CreateDataSource { id } => {
+                    data_init.init(id, ());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    impl wayland_server::Dispatch<server_device::ZwlrDataControlDeviceV1, ()> for TestCompositor {
+        fn request(
+            state: &mut Self,
+            _: &Client,
+            _: &server_device::ZwlrDataControlDeviceV1,
+            request: server_device::Request,
+            _: &(),
+            _: &DisplayHandle,
+            _: &mut DataInit<'_, Self>,
+        ) {
+            if let server_device::Request::SetSelection {
+                source: Some(source),
+            } = request
+            {
+                let (mut reader, writer) = std::io::pipe().unwrap();
+                source.send("text/plain;charset=utf-8".to_string(), writer.as_fd());
+                drop(writer);
+                let restored = state.restored.clone();
+                std::thread::spawn(move || {
+                    let mut text = String::new();
+                    reader.read_to_string(&mut text).unwrap();
+                    let _ = restored.send(text);
+                });
+            }
+        }
+    }
+
+    impl wayland_server::Dispatch<server_offer::ZwlrDataControlOfferV1, &'static str>
+        for TestCompositor
+    {
+        fn request(
+            _: &mut Self,
+            _: &Client,
+            _: &server_offer::ZwlrDataControlOfferV1,
+            request: server_offer::Request,
+            content: &&'static str,
+            _: &DisplayHandle,
+            _: &mut DataInit<'_, Self>,
+        ) {
+            if let server_offer::Request::Receive { fd, .. } = request {
+                std::fs::File::from(fd)
+                    .write_all(content.as_bytes())
+                    .unwrap();
+            }
+        }
+    }
+
+    impl wayland_server::Dispatch<server_source::ZwlrDataControlSourceV1, ()> for TestCompositor {
+        fn request(
+            _: &mut Self,
+            _: &Client,
+            _: &server_source::ZwlrDataControlSourceV1,
+            _: server_source::Request,
+            _: &(),
+            _: &DisplayHandle,
+            _: &mut DataInit<'_, Self>,
+        ) {
+        }
+    }
+
+    wayland_server::delegate_global_dispatch!(TestCompositor: [wl_seat::WlSeat: ()] => TestSeat);
+    wayland_server::delegate_dispatch!(TestCompositor: [wl_seat::WlSeat: ()] => TestSeat);
+
+    /// A running test compositor.
+    struct Running {
+        /// Text of each selection set by the client
+        restored: mpsc::Receiver<String>,
+        _display: TestDisplay,
+    }
+
+    /// Run a test compositor offering `selections` and connect to it.
+    fn start_compositor(
+        selections: Vec<(Vec<&'static str>, &'static str)>,
+    ) -> (Connection, Running) {
+        let (restored, restored_rx) = mpsc::channel();
+
+        let (connection, display) = start_display(move |dh| {
+            dh.create_global::<TestCompositor, server_manager::ZwlrDataControlManagerV1, ()>(2, ());
+            dh.create_global::<TestCompositor, wl_seat::WlSeat, ()>(1, ());
+            TestCompositor {
+                selections,
+                restored,
+            }
+        });
+
+        let running = Running {
+            restored: restored_rx,
+            _display: display,
+        };
+        (connection, running)
+    }
+
+    #[test]
+    fn test_watch_and_restore() {
+        let (connection, compositor) = start_compositor(vec![
+            (vec!["text/plain", PASSWORD_HINT], "hunter2"),
+            (vec!["text/html", "text/plain;charset=utf-8"], "hello"),
+        ]);
+        let (clips, clips_rx) = mpsc::channel();
+        let on_clip = move |clip| {
+            let _ = clips.send(clip);
+        };
+        let watcher = ClipboardWatcher::from_connection(connection, Arc::new(on_clip)).unwrap();
+
+        // The password is skipped
+        let timeout = Duration::from_secs(5);
+        let clip = clips_rx.recv_timeout(timeout);
+        assert_eq!(clip, Ok(Clip::Text("hello".to_string())));
+
+        watcher
+            .set_selection(Clip::Text("restored".to_string()))
+            .unwrap();
+        assert_eq!(
+            compositor.restored.recv_timeout(timeout).as_deref(),
+            Ok("restored")
+        );
+    }
+
+    fn mime_types(types: &[&str]) -> Vec<String> {
+        types.iter().map(|m| m.to_string()).collect()
+    }
+
+    #[test]
+    fn test_preferred_mime_type() {
+        let offered = mime_types(&["image/png", "text/html", "UTF8_STRING", "text/plain"]);
+        assert_eq!(preferred_mime_type(&offered), Some("UTF8_STRING"));
+
+        let offered = mime_types(&["image/jpeg", "image/png", "text/html"]);
+        assert_eq!(preferred_mime_type(&offered), Some("image/png"));
+
+        let offered = mime_types(&["text/html", "image/bmp"]);
+        assert_eq!(preferred_mime_type(&offered), Some("image/bmp"));
+
+        assert_eq!(preferred_mime_type(&mime_types(&["text/html"])), None);
+    }
+}
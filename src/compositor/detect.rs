@@ -7,6 +7,12 @@ use super::noop::NoopCompositor;
 use super::hyprland::HyprlandCompositor;
 #[cfg(unix)]
 use super::kwin::KwinCompositor;
+#[cfg(unix)]
+use super::niri::NiriCompositor;
+#[cfg(unix)]
+use super::sway::SwayCompositor;
+#[cfg(unix)]
+use super::wlr::WlrCompositor;
 
 use tracing::{info, warn};
 
@@ -14,8 +20,11 @@ use tracing::{info, warn};
 ///
 /// Detection order (Unix only):
 /// 1. Hyprland (via HYPRLAND_INSTANCE_SIGNATURE env var)
-/// 2. KDE/KWin (via KDE_SESSION_VERSION env var)
-/// 3. Fallback to NoopCompositor
+/// 2. Sway or i3 (via SWAYSOCK or I3SOCK env var)
+/// 3. Niri (via NIRI_SOCKET env var)
+/// 4. KDE/KWin (via KDE_SESSION_VERSION env var)
+/// 5. Any Wayland compositor supporting wlr-foreign-toplevel-management
+/// 6. Fallback to NoopCompositor
 ///
 /// On Windows, always returns NoopCompositor as window switching
 /// is not yet supported.
@@ -30,12 +39,30 @@ pub fn detect_compositor() -> Box<dyn Compositor> {
         return Box::new(compositor);
     }
 
+    // Try Sway, then i3
+    if let Some(compositor) = SwayCompositor::new() {
+        info!("Detected {} compositor", compositor.name());
+        return Box::new(compositor);
+    }
+
+    // Try Niri
+    if let Some(compositor) = NiriCompositor::new() {
+        info!("Detected Niri compositor");
+        return Box::new(compositor);
+    }
+
     // Try KWin
     if let Some(compositor) = KwinCompositor::new() {
         info!("Detected KWin compositor");
         return Box::new(compositor);
     }
 
+    // Try the generic Wayland protocol, e.g. river, labwc or wayfire
+    if let Some(compositor) = WlrCompositor::new() {
+        info!("Detected wlr-foreign-toplevel-management support");
+        return Box::new(compositor);
+    }
+
     // Fallback to no-op
     warn!("No supported compositor detected, window switching disabled");
     Box::new(NoopCompositor)
@@ -0,0 +1,398 @@
+//! Window list kept current from Hyprland's event socket (`.socket2.sock`).
+//!
+//! Hyprland writes one `EVENT>>DATA` line per event. The `v2` variants are
+//! used where available as they carry window addresses and workspace ids.
+//! Addresses are sent without the `0x` prefix that `j/clients` uses.
+
+use crate::compositor::WindowInfo;
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::debug;
+
+/// How long to wait before reconnecting to the event socket.
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+
+/// The window cache, None while the event socket is not connected.
+pub type SharedCache = Arc<Mutex<Option<WindowCache>>>;
+
+/// A Hyprland event relevant to the window list.
+#[derive(Debug, PartialEq, Eq)]
+enum Event {
+    /// `openwindow>>ADDRESS,WORKSPACENAME,CLASS,TITLE`
+    OpenWindow {
+        address: String,
+        workspace: String,
+        class: String,
+        title: String,
+    },
+    /// `closewindow>>ADDRESS`
+    CloseWindow { address: String },
+    /// `windowtitlev2>>ADDRESS,TITLE`
+    WindowTitle { address: String, title: String },
+    /// `activewindowv2>>ADDRESS`, empty when no window has focus
+    ActiveWindow { address: Option<String> },
+    /// `movewindowv2>>ADDRESS,WORKSPACEID,WORKSPACENAME`
+    MoveWindow { address: String, workspace: i32 },
+    /// `createworkspacev2>>ID,NAME` or `renameworkspace>>ID,NAME`
+    NameWorkspace { id: i32, name: String },
+}
+
+impl Event {
+    /// Parse an event line, returning None for events the cache ignores.
+    fn parse(line: &str) -> Option<Self> {
+        let (event, data) = line.split_once(">>")?;
+        match event {
+            "openwindow" => {
+                // The title is last as it may contain commas
+                let mut fields = data.splitn(4, ',');
+                Some(Self::OpenWindow {
+                    address: address(fields.next()?),
+                    workspace: fields.next()?.to_string(),
+                    class: fields.next()?.to_string(),
+                    title: fields.next().unwrap_or_default().to_string(),
+                })
+            }
+            "closewindow" => Some(Self::CloseWindow {
+                address: address(data),
+            }),
+            "windowtitlev2" => {
+                let (addr, title) = data.split_once(',')?;
+                Some(Self::WindowTitle {
+                    address: address(addr),
+                    title: title.to_string(),
+                })
+            }
+            "activewindowv2" => Some(Self::ActiveWindow {
+                address: match data.trim_matches(',') {
+                    "" => None,
+                    addr => Some(address(addr)),
+                },
+            }),
+            "movewindowv2" => {
+                let mut fields = data.splitn(3, ',');
+                Some(Self::MoveWindow {
+                    address: address(fields.next()?),
+                    workspace: fields.next()?.parse().ok()?,
+                })
+            }
+            "createworkspacev2" | "renameworkspace" => {
+                let (id, name) = data.split_once(',')?;
+                Some(Self::NameWorkspace {
+                    id: id.parse().ok()?,
+                    name: name.to_string(),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Add the `0x` prefix to an event address.
+fn address(addr: &str) -> String {
+    format!("0x{}", addr.trim_start_matches("0x"))
+}
+
+/// Open windows in most recently focused order.
+#[derive(Debug, Default)]
+pub struct WindowCache {
+    windows: Vec<WindowInfo>,
+    /// Workspace ids by name, as window events only name the workspace
+    workspace_ids: HashMap<String, i32>,
+}
+
+impl WindowCache {
+    /// Create a cache from windows in focus order and the workspaces.
+    pub fn new(
+        windows: Vec<WindowInfo>,
+        workspaces: impl IntoIterator<Item = (i32, String)>,
+    ) -> Self {
+        Self {
+            windows,
+            workspace_ids: workspaces
+                .into_iter()
+                .map(|(id, name)| (name, id))
+                .collect(),
+        }
+    }
+
+    /// Get the windows, most recently focused first.
+    pub fn windows(&self) -> Vec<WindowInfo> {
+        self.windows.clone()
+    }
+
+    fn apply(&mut self, event: Event) {
+        match event {
+            Event::OpenWindow {
+                address,
+                workspace,
+                class,
+                title,
+            } => {
+                // Skip special windows and zlaunch itself, like `j/clients`
+                if class.is_empty() || class.to_lowercase() == "zlaunch" {
+                    return;
+                }
+                let workspace = self
+                    .workspace_ids
+                    .get(&workspace)
+                    .copied()
+                    .or_else(|| workspace.parse().ok())
+                    .unwrap_or(0);
+                let window = WindowInfo {
+                    address,
+                    title: if title.is_empty() {
+                        class.clone()
+                    } else {
+                        title
+                    },
+                    class,
+                    workspace,
+                    focused: false,
+                    scratchpad: false,
+                };
+
+                // Never focused windows go last, activewindowv2 follows if it gets focus
+                match self
+                    .windows
+                    .iter_mut()
+                    .find(|w| w.address == window.address)
+                {
+                    Some(existing) => *existing = window,
+                    None => self.windows.push(window),
+                }
+            }
+            Event::CloseWindow { address } => {
+                self.windows.retain(|w| w.address != address);
+            }
+            Event::WindowTitle { address, title } => {
+                if let Some(window) = self.windows.iter_mut().find(|w| w.address == address) {
+                    window.title = if title.is_empty() {
+                        window.class.clone()
+                    } else {
+                        title
+                    };
+                }
+            }
+            Event::ActiveWindow { address } => {
+                for window in &mut self.windows {
+                    window.focused = Some(&window.address) == address.as_ref();
+                }
+                if let Some(pos) = self.windows.iter().position(|w| w.focused) {
+                    let window = self.windows.remove(pos);
+                    self.windows.insert(0, window);
+                }
+            }
+            Event::MoveWindow { address, workspace } => {
+                if let Some(window) = self.windows.iter_mut().find(|w| w.address == address) {
+                    window.workspace = workspace;
+                }
+            }
+            Event::NameWorkspace { id, name } => {
+                self.workspace_ids.retain(|_, &mut existing| existing != id);
+                self.workspace_ids.insert(name, id);
+            }
+        }
+    }
+}
+
+/// Keep `cache` current from the event socket on a background thread.
+///
+/// `load` fetches the full window list each time the socket (re)connects.
+/// While disconnected the cache is cleared so callers query Hyprland instead.
+pub fn spawn_listener(
+    event_socket: PathBuf,
+    load: impl Fn() -> Result<WindowCache> + Send + 'static,
+    cache: SharedCache,
+) {
+    std::thread::spawn(move || {
+        loop {
+            if let Err(e) = listen(&event_socket, &load, &cache) {
+                debug!(%e, "Hyprland event socket unavailable, window list not cached");
+            }
+            *cache.lock().unwrap_or_else(|e| e.into_inner()) = None;
+            std::thread::sleep(RECONNECT_DELAY);
+        }
+    });
+}
+
+/// Apply events to the cache until the socket closes.
+fn listen(
+    event_socket: &Path,
+    load: &impl Fn() -> Result<WindowCache>,
+    cache: &SharedCache,
+) -> Result<()> {
+    // Connect before loading so no event in between is missed
+    let stream = UnixStream::connect(event_socket).with_context(|| {
+        format!(
+            "Failed to connect to Hyprland event socket: {:?}",
+            event_socket
+        )
+    })?;
+    *cache.lock().unwrap_or_else(|e| e.into_inner()) = Some(load()?);
+
+    let mut reader = BufReader::new(stream);
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        let len = reader
+            .read_until(b'\n', &mut line)
+            .context("Failed to read from Hyprland event socket")?;
+        if len == 0 {
+            return Ok(());
+        }
+
+        // Titles are not guaranteed to be valid UTF-8
+        let text = String::from_utf8_lossy(&line);
+        if let Some(event) = Event::parse(text.trim_end_matches('\n'))
+            && let Some(cache) = cache.lock().unwrap_or_else(|e| e.into_inner()).as_mut()
+        {
+            cache.apply(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::os::unix::net::UnixListener;
+    use std::time::Instant;
+
+    fn window(address: &str, class: &str, workspace: i32, focused: bool) -> WindowInfo {
+        WindowInfo {
+            address: address.to_string(),
+            title: class.to_string(),
+            class: class.to_string(),
+            workspace,
+            focused,
+            scratchpad: false,
+        }
+    }
+
+    fn addresses(cache: &WindowCache) -> Vec<&str> {
+        cache.windows.iter().map(|w| w.address.as_str()).collect()
+    }
+
+    #[test]
+    fn test_parse_events() {
+        assert_eq!(
+            Event::parse("openwindow>>5678abcd,web,firefox,Docs, Mozilla Firefox"),
+            Some(Event::OpenWindow {
+                address: "0x5678abcd".to_string(),
+                workspace: "web".to_string(),
+                class: "firefox".to_string(),
+                title: "Docs, Mozilla Firefox".to_string(),
+            })
+        );
+        assert_eq!(
+            Event::parse("activewindowv2>>"),
+            Some(Event::ActiveWindow { address: None })
+        );
+        assert_eq!(
+            Event::parse("movewindowv2>>5678abcd,-1337,web"),
+            Some(Event::MoveWindow {
+                address: "0x5678abcd".to_string(),
+                workspace: -1337,
+            })
+        );
+        // v1 variants and unrelated events are ignored
+        assert_eq!(Event::parse("activewindow>>firefox,Docs"), None);
+        assert_eq!(Event::parse("workspace>>2"), None);
+        assert_eq!(Event::parse("garbage"), None);
+    }
+
+    #[test]
+    fn test_cache_tracks_focus_order() {
+        let mut cache = WindowCache::new(
+            vec![
+                window("0x1", "Alacritty", 1, true),
+                window("0x2", "firefox", 2, false),
+            ],
+            [(1, "1".to_string()), (2, "2".to_string())],
+        );
+
+        for line in [
+            "createworkspacev2>>-1337,web",
+            "openwindow>>3,web,org.gnome.Nautilus,",
+            "activewindowv2>>3",
+            "activewindowv2>>2",
+            "windowtitlev2>>2,Docs, Mozilla Firefox",
+            "movewindowv2>>1,2,2",
+            "closewindow>>3",
+            "openwindow>>4,1,zlaunch,zlaunch",
+        ] {
+            cache.apply(Event::parse(line).unwrap());
+        }
+
+        assert_eq!(addresses(&cache), ["0x2", "0x1"]);
+        assert!(cache.windows[0].focused);
+        assert!(!cache.windows[1].focused);
+        assert_eq!(cache.windows[0].title, "Docs, Mozilla Firefox");
+        assert_eq!(cache.windows[1].workspace, 2);
+
+        // New windows land on the named workspace and last until focused
+        cache.apply(Event::parse("openwindow>>5,web,kitty,").unwrap());
+        assert_eq!(addresses(&cache), ["0x2", "0x1", "0x5"]);
+        assert_eq!(cache.windows[2].workspace, -1337);
+        assert_eq!(cache.windows[2].title, "kitty");
+    }
+
+    #[test]
+    fn test_listener() {
+        let path = std::env::temp_dir().join(format!(
+            "zlaunch-hyprland-events-test-{}.sock",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+
+        let cache = SharedCache::default();
+        spawn_listener(
+            path.clone(),
+            || {
+                Ok(WindowCache::new(
+                    vec![window("0x1", "Alacritty", 1, true)],
+                    [(1, "1".to_string())],
+                ))
+            },
+            cache.clone(),
+        );
+
+        let (mut stream, _) = listener.accept().unwrap();
+        stream
+            .write_all(b"openwindow>>2,1,firefox,Firefox\nactivewindowv2>>2\n")
+            .unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            let current = cache
+                .lock()
+                .unwrap()
+                .as_ref()
+                .map(|cache| addresses(cache).join(" "));
+            if current.as_deref() == Some("0x2 0x1") {
+                break;
+            }
+            assert!(
+                Instant::now() < deadline,
+                "cache not updated: {:?}",
+                current
+            );
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        // Closing the socket drops the cache until the listener reconnects
+        drop(stream);
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while cache.lock().unwrap().is_some() {
+            assert!(Instant::now() < deadline, "cache kept after disconnect");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        let _ = std::fs::remove_file(&path);
+    }
+}
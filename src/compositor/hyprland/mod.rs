@@ -0,0 +1,476 @@
+//! Hyprland compositor implementation using IPC socket.
+//!
+//! The window list is served from a cache kept current by the event socket,
+//! so opening the launcher does not wait for a round trip to Hyprland.
+
+mod events;
+
+use super::{Compositor, OutputInfo, WindowCapabilities, WindowInfo, WorkspaceInfo};
+use anyhow::{Context, Result, anyhow, bail};
+use events::{SharedCache, WindowCache};
+use serde::Deserialize;
+use std::io::{Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+
+/// Hyprland compositor client using IPC socket communication.
+pub struct HyprlandCompositor {
+    socket_path: PathBuf,
+    /// Windows in focus order, None when the event socket is not connected
+    cache: SharedCache,
+}
+
+impl HyprlandCompositor {
+    /// Create a new Hyprland compositor client.
+    ///
+    /// Returns None if the required environment variables are not set.
+    pub fn new() -> Option<Self> {
+        let signature = std::env::var("HYPRLAND_INSTANCE_SIGNATURE").ok()?;
+        let runtime_dir = std::env::var("XDG_RUNTIME_DIR").unwrap_or_else(|_| "/tmp".to_string());
+
+        let socket_dir = PathBuf::from(format!("{}/hypr/{}", runtime_dir, signature));
+
+        let compositor = Self::with_socket(socket_dir.join(".socket.sock"));
+        let loader = Self::with_socket(socket_dir.join(".socket.sock"));
+        events::spawn_listener(
+            socket_dir.join(".socket2.sock"),
+            move || loader.load_cache(),
+            compositor.cache.clone(),
+        );
+
+        Some(compositor)
+    }
+
+    fn with_socket(socket_path: impl Into<PathBuf>) -> Self {
+        Self {
+            socket_path: socket_path.into(),
+            cache: SharedCache::default(),
+        }
+    }
+
+    /// Send a command to Hyprland and receive the response.
+    fn send_command(&self, cmd: &str) -> Result<String> {
+        let mut stream = UnixStream::connect(&self.socket_path).with_context(|| {
+            format!(
+                "Failed to connect to Hyprland socket: {:?}",
+                self.socket_path
+            )
+        })?;
+
+        stream
+            .write_all(cmd.as_bytes())
+            .context("Failed to write command to Hyprland socket")?;
+
+        let mut response = String::new();
+        stream
+            .read_to_string(&mut response)
+            .context("Failed to read response from Hyprland socket")?;
+
+        Ok(response)
+    }
+
+    /// Query the windows, most recently focused first.
+    fn query_windows(&self) -> Result<Vec<WindowInfo>> {
+        // j/clients returns JSON output
+        let json = self.send_command("j/clients")?;
+        let mut clients: Vec<HyprlandClient> =
+            serde_json::from_str(&json).context("Failed to parse Hyprland clients JSON")?;
+        // Windows that were never focused have no focus history and go last
+        clients.sort_by_key(|c| (c.focus_history_id < 0, c.focus_history_id));
+
+        let windows = clients
+            .into_iter()
+            // Filter out special windows
+            .filter(|c| {
+                // Exclude unmapped or hidden windows
+                if !c.mapped || c.hidden {
+                    return false;
+                }
+                // Exclude zlaunch itself
+                if c.class.to_lowercase() == "zlaunch" {
+                    return false;
+                }
+                // Exclude windows with empty class (usually special windows)
+                if c.class.is_empty() {
+                    return false;
+                }
+                true
+            })
+            .map(|c| {
+                let focused = c.is_focused();
+                let workspace = c.workspace.id;
+                WindowInfo {
+                    address: c.address,
+                    title: if c.title.is_empty() {
+                        c.class.clone()
+                    } else {
+                        c.title
+                    },
+                    class: c.class,
+                    workspace,
+                    focused,
+                    scratchpad: false,
+                }
+            })
+            .collect();
+
+        Ok(windows)
+    }
+
+    /// Query the windows and workspace names to start the event cache from.
+    fn load_cache(&self) -> Result<WindowCache> {
+        let windows = self.query_windows()?;
+        let json = self.send_command("j/workspaces")?;
+        let workspaces: Vec<HyprlandWorkspaceDetails> =
+            serde_json::from_str(&json).context("Failed to parse Hyprland workspaces JSON")?;
+
+        Ok(WindowCache::new(
+            windows,
+            workspaces.into_iter().map(|w| (w.id, w.name)),
+        ))
+    }
+
+    /// Run a dispatcher, failing unless Hyprland replies "ok".
+    fn dispatch(&self, dispatcher: &str) -> Result<()> {
+        let response = self.send_command(&format!("dispatch {}", dispatcher))?;
+        if response.trim() != "ok" {
+            bail!("Hyprland dispatch failed: {}", response.trim());
+        }
+        Ok(())
+    }
+
+    /// Get the dispatcher argument selecting a workspace.
+    ///
+    /// Hyprland reads negative numbers as relative moves, so named
+    /// workspaces are selected by their name.
+    fn workspace_selector(&self, workspace: i32) -> Result<String> {
+        if workspace > 0 {
+            return Ok(workspace.to_string());
+        }
+
+        let name = self
+            .list_workspaces()?
+            .into_iter()
+            .find(|w| w.id == workspace)
+            .map(|w| w.name)
+            .ok_or_else(|| anyhow!("No Hyprland workspace with id {}", workspace))?;
+        Ok(format!("name:{}", name))
+    }
+}
+
+impl Compositor for HyprlandCompositor {
+    fn list_windows(&self) -> Result<Vec<WindowInfo>> {
+        if let Some(cache) = self
+            .cache
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+        {
+            return Ok(cache.windows());
+        }
+        self.query_windows()
+    }
+
+    fn focus_window(&self, window_id: &str) -> Result<()> {
+        let cmd = format!("dispatch focuswindow address:{}", window_id);
+        self.send_command(&cmd)?;
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "Hyprland"
+    }
+
+    fn list_outputs(&self) -> Result<Vec<OutputInfo>> {
+        let json = self.send_command("j/monitors")?;
+        let monitors: Vec<HyprlandMonitor> =
+            serde_json::from_str(&json).context("Failed to parse Hyprland monitors JSON")?;
+
+        Ok(monitors
+            .into_iter()
+            .filter(|m| !m.disabled)
+            .map(HyprlandMonitor::into_output_info)
+            .collect())
+    }
+
+    fn has_workspaces(&self) -> bool {
+        true
+    }
+
+    fn list_workspaces(&self) -> Result<Vec<WorkspaceInfo>> {
+        let json = self.send_command("j/workspaces")?;
+        let workspaces: Vec<HyprlandWorkspaceDetails> =
+            serde_json::from_str(&json).context("Failed to parse Hyprland workspaces JSON")?;
+        let json = self.send_command("j/activeworkspace")?;
+        let active: HyprlandWorkspace = serde_json::from_str(&json)
+            .context("Failed to parse Hyprland active workspace JSON")?;
+
+        let mut workspaces: Vec<WorkspaceInfo> = workspaces
+            .into_iter()
+            // Special workspaces are toggled, not switched to
+            .filter(|w| !w.name.starts_with("special:"))
+            .map(|w| WorkspaceInfo {
+                focused: w.id == active.id,
+                id: w.id,
+                name: w.name,
+                windows: w.windows,
+            })
+            .collect();
+        // Numbered workspaces first, then named ones, which have negative ids
+        workspaces.sort_by_key(|w| (w.id < 0, w.id.abs()));
+
+        Ok(workspaces)
+    }
+
+    fn focus_workspace(&self, workspace: i32) -> Result<()> {
+        let workspace = self.workspace_selector(workspace)?;
+        self.dispatch(&format!("workspace {}", workspace))
+    }
+
+    fn capabilities(&self) -> WindowCapabilities {
+        WindowCapabilities {
+            close: true,
+            move_to_workspace: true,
+            fullscreen: true,
+            floating: true,
+            pin: true,
+        }
+    }
+
+    fn close_window(&self, window_id: &str) -> Result<()> {
+        self.dispatch(&format!("closewindow address:{}", window_id))
+    }
+
+    fn move_window_to_workspace(&self, window_id: &str, workspace: i32) -> Result<()> {
+        let workspace = self.workspace_selector(workspace)?;
+        self.dispatch(&format!(
+            "movetoworkspacesilent {},address:{}",
+            workspace, window_id
+        ))
+    }
+
+    fn toggle_fullscreen(&self, window_id: &str) -> Result<()> {
+        // The fullscreen dispatcher only acts on the active window
+        self.dispatch(&format!("focuswindow address:{}", window_id))?;
+        self.dispatch("fullscreen 0")
+    }
+
+    fn toggle_floating(&self, window_id: &str) -> Result<()> {
+        self.dispatch(&format!("togglefloating address:{}", window_id))
+    }
+
+    fn pin(&self, window_id: &str) -> Result<()> {
+        self.dispatch(&format!("pin address:{}", window_id))
+    }
+}
+
+/// Hyprland client (window) information from IPC.
+#[derive(Debug, Deserialize)]
+struct HyprlandClient {
+    address: String,
+    title: String,
+    class: String,
+    workspace: HyprlandWorkspace,
+    /// 0 means currently focused, higher numbers mean less recently focused
+    #[serde(rename = "focusHistoryID")]
+    focus_history_id: i32,
+    #[serde(default)]
+    mapped: bool,
+    #[serde(default)]
+    hidden: bool,
+}
+
+impl HyprlandClient {
+    /// Check if this window is currently focused (focusHistoryID == 0)
+    fn is_focused(&self) -> bool {
+        self.focus_history_id == 0
+    }
+}
+
+/// Hyprland workspace information.
+#[derive(Debug, Deserialize)]
+struct HyprlandWorkspace {
+    id: i32,
+}
+
+/// Hyprland workspace information from `j/workspaces`.
+#[derive(Debug, Deserialize)]
+struct HyprlandWorkspaceDetails {
+    id: i32,
+    name: String,
+    windows: usize,
+}
+
+/// Hyprland monitor information from `j/monitors`.
+#[derive(Debug, Deserialize)]
+struct HyprlandMonitor {
+    name: String,
+    /// Position in the layout, in logical pixels
+    x: i32,
+    y: i32,
+    /// Mode size in physical pixels
+    width: i32,
+    height: i32,
+    scale: f64,
+    /// wl_output transform, odd values are rotated by 90 or 270 degrees
+    transform: i32,
+    #[serde(default)]
+    disabled: bool,
+}
+
+impl HyprlandMonitor {
+    /// Convert to OutputInfo with the logical size.
+    fn into_output_info(self) -> OutputInfo {
+        let scale = if self.scale > 0.0 { self.scale } else { 1.0 };
+        let width = (f64::from(self.width) / scale).round() as i32;
+        let height = (f64::from(self.height) / scale).round() as i32;
+        let (width, height) = if self.transform % 2 == 1 {
+            (height, width)
+        } else {
+            (width, height)
+        };
+
+        OutputInfo {
+            name: self.name,
+            x: self.x,
+            y: self.y,
+            width,
+            height,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compositor::WindowAction;
+    use crate::compositor::test_server;
+    use std::thread::JoinHandle;
+
+    /// Serve `replies` in order, returning the commands received.
+    fn fake_server(name: &str, replies: Vec<&'static str>) -> (PathBuf, JoinHandle<Vec<String>>) {
+        test_server::fake_server(&format!("hyprland-{}", name), replies, |stream, reply| {
+            let mut buf = [0u8; 256];
+            let len = stream.read(&mut buf).unwrap();
+            stream.write_all(reply.as_bytes()).unwrap();
+            String::from_utf8_lossy(&buf[..len]).into_owned()
+        })
+    }
+
+    #[test]
+    fn test_window_actions() {
+        let (path, server) = fake_server("actions", vec!["ok"; 6]);
+        let hyprland = HyprlandCompositor::with_socket(&path);
+
+        let address = "0x5678abcd";
+        for action in [
+            WindowAction::Close,
+            WindowAction::MoveToWorkspace(3),
+            WindowAction::ToggleFullscreen,
+            WindowAction::ToggleFloating,
+            WindowAction::Pin,
+        ] {
+            assert!(hyprland.capabilities().supports(action));
+            hyprland.run_window_action(address, action).unwrap();
+        }
+
+        let commands = server.join().unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(
+            commands,
+            [
+                "dispatch closewindow address:0x5678abcd",
+                "dispatch movetoworkspacesilent 3,address:0x5678abcd",
+                "dispatch focuswindow address:0x5678abcd",
+                "dispatch fullscreen 0",
+                "dispatch togglefloating address:0x5678abcd",
+                "dispatch pin address:0x5678abcd",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_workspaces() {
+        let (path, server) = fake_server(
+            "workspaces",
+            vec![
+                include_str!("../fixtures/hyprland-workspaces.json"),
+                r#"{"id": 2, "name": "2", "windows": 1}"#,
+                "ok",
+                include_str!("../fixtures/hyprland-workspaces.json"),
+                r#"{"id": 2, "name": "2", "windows": 1}"#,
+                "ok",
+                include_str!("../fixtures/hyprland-workspaces.json"),
+                r#"{"id": 2, "name": "2", "windows": 1}"#,
+                "ok",
+            ],
+        );
+        let hyprland = HyprlandCompositor::with_socket(&path);
+
+        let workspaces = hyprland.list_workspaces().unwrap();
+        let summary: Vec<(i32, &str, usize, bool)> = workspaces
+            .iter()
+            .map(|w| (w.id, w.name.as_str(), w.windows, w.focused))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (1, "1", 3, false),
+                (2, "2", 1, true),
+                (5, "5", 2, false),
+                // Named workspaces come last, special ones are skipped
+                (-1337, "web", 1, false),
+            ]
+        );
+
+        hyprland.focus_workspace(5).unwrap();
+        hyprland.focus_workspace(-1337).unwrap();
+        hyprland
+            .move_window_to_workspace("0x5678abcd", -1337)
+            .unwrap();
+
+        let commands = server.join().unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(
+            commands,
+            [
+                "j/workspaces",
+                "j/activeworkspace",
+                "dispatch workspace 5",
+                "j/workspaces",
+                "j/activeworkspace",
+                "dispatch workspace name:web",
+                "j/workspaces",
+                "j/activeworkspace",
+                "dispatch movetoworkspacesilent name:web,address:0x5678abcd",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_outputs() {
+        let (path, server) = fake_server(
+            "outputs",
+            vec![include_str!("../fixtures/hyprland-monitors.json")],
+        );
+        let hyprland = HyprlandCompositor::with_socket(&path);
+
+        let outputs = hyprland.list_outputs().unwrap();
+        assert_eq!(server.join().unwrap(), ["j/monitors"]);
+        let _ = std::fs::remove_file(&path);
+
+        let summary: Vec<(&str, i32, i32, i32, i32)> = outputs
+            .iter()
+            .map(|o| (o.name.as_str(), o.x, o.y, o.width, o.height))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                // Scaled outputs report their logical size
+                ("eDP-1", 0, 0, 1536, 960),
+                // Rotated outputs swap width and height
+                ("DP-1", 1536, 0, 1440, 2560),
+            ]
+        );
+    }
+}
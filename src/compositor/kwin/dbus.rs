@@ -0,0 +1,182 @@
+//! Session bus client that runs KWin scripts and collects their reports.
+
+use super::KwinBus;
+use anyhow::{Context, Result, anyhow};
+use std::io::Write;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use zbus::blocking::Connection;
+
+const KWIN_SERVICE: &str = "org.kde.KWin";
+const SCRIPTING_PATH: &str = "/Scripting";
+const SCRIPTING_INTERFACE: &str = "org.kde.kwin.Scripting";
+const SCRIPT_INTERFACE: &str = "org.kde.kwin.Script";
+
+/// Object exported by zlaunch that scripts report to.
+const REPORT_PATH: &str = "/dev/zlaunch/KWinReport";
+const REPORT_INTERFACE: &str = "dev.zlaunch.KWinReport";
+
+/// How long to wait for a script to report back.
+const REPORT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Counter for unique script file and plugin names.
+static SCRIPT_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Receives the JSON reported by running scripts.
+struct Reporter {
+    reports: flume::Sender<String>,
+}
+
+#[zbus::interface(name = "dev.zlaunch.KWinReport")]
+impl Reporter {
+    fn report(&self, json: String) {
+        let _ = self.reports.send(json);
+    }
+}
+
+/// KWin scripting over the session bus.
+pub struct DbusKwin {
+    connection: Connection,
+    reports: flume::Receiver<String>,
+    /// Whether KWin 5 is running, which names script objects differently
+    kde5: bool,
+    /// Only one script runs at a time so reports are not mixed up
+    lock: Mutex<()>,
+}
+
+impl DbusKwin {
+    /// Connect to the session bus and export the report object.
+    pub fn connect(kde5: bool) -> Result<Self> {
+        let connection = Connection::session().context("Failed to connect to the session bus")?;
+
+        let (reports_tx, reports) = flume::unbounded();
+        connection
+            .object_server()
+            .at(
+                REPORT_PATH,
+                Reporter {
+                    reports: reports_tx,
+                },
+            )
+            .context("Failed to export the KWin report object")?;
+
+        Ok(Self {
+            connection,
+            reports,
+            kde5,
+            lock: Mutex::new(()),
+        })
+    }
+
+    /// Object path of a loaded script.
+    fn script_path(&self, id: i32) -> String {
+        if self.kde5 {
+            format!("/{}", id)
+        } else {
+            format!("{}/Script{}", SCRIPTING_PATH, id)
+        }
+    }
+
+    /// Script prelude defining `report()` in terms of `callDBus`.
+    fn prelude(&self) -> Result<String> {
+        let name = self
+            .connection
+            .unique_name()
+            .context("Session bus connection has no unique name")?;
+
+        Ok(format!(
+            "function report(value) {{ callDBus(\"{}\", \"{}\", \"{}\", \"Report\", JSON.stringify(value)); }}\n",
+            name.as_str(),
+            REPORT_PATH,
+            REPORT_INTERFACE
+        ))
+    }
+
+    /// Load, run and unload a script file, waiting for its report.
+    fn run_script_file(&self, file: &Path, plugin: &str) -> Result<String> {
+        let id: i32 = self
+            .connection
+            .call_method(
+                Some(KWIN_SERVICE),
+                SCRIPTING_PATH,
+                Some(SCRIPTING_INTERFACE),
+                "loadScript",
+                &(file.to_string_lossy().as_ref(), plugin),
+            )
+            .context("Failed to load KWin script")?
+            .body()
+            .deserialize()?;
+
+        let result = self
+            .connection
+            .call_method(
+                Some(KWIN_SERVICE),
+                self.script_path(id).as_str(),
+                Some(SCRIPT_INTERFACE),
+                "run",
+                &(),
+            )
+            .context("Failed to run KWin script")
+            .and_then(|_| {
+                self.reports
+                    .recv_timeout(REPORT_TIMEOUT)
+                    .map_err(|_| anyhow!("KWin script did not report back"))
+            });
+
+        let _ = self.connection.call_method(
+            Some(KWIN_SERVICE),
+            SCRIPTING_PATH,
+            Some(SCRIPTING_INTERFACE),
+            "unloadScript",
+            &(plugin,),
+        );
+
+        result
+    }
+}
+
+impl KwinBus for DbusKwin {
+    fn is_available(&self) -> bool {
+        self.connection
+            .call_method(
+                Some("org.freedesktop.DBus"),
+                "/org/freedesktop/DBus",
+                Some("org.freedesktop.DBus"),
+                "NameHasOwner",
+                &(KWIN_SERVICE,),
+            )
+            .and_then(|reply| reply.body().deserialize::<bool>())
+            .unwrap_or(false)
+    }
+
+    fn run_script(&self, script: &str) -> Result<String> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+
+        // Drop reports left over from scripts that timed out
+        while self.reports.try_recv().is_ok() {}
+
+        let n = SCRIPT_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let plugin = format!("zlaunch-{}-{}", std::process::id(), n);
+        // KWin reads the script from a file, which only the user may create
+        // or read so other users cannot swap in their own script
+        let file = dirs::runtime_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join(format!("{}.js", plugin));
+
+        let source = self.prelude()? + script;
+        std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(&file)
+            .and_then(|mut f| f.write_all(source.as_bytes()))
+            .context("Failed to write KWin script")?;
+
+        let result = self.run_script_file(&file, &plugin);
+        let _ = std::fs::remove_file(&file);
+        result
+    }
+}
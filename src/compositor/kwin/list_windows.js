@@ -0,0 +1,27 @@
+// Report the open windows to zlaunch.
+// Supports both the KWin 6 (windowList) and KWin 5 (clientList) APIs.
+const windows = workspace.windowList ? workspace.windowList() : workspace.clientList();
+const active = workspace.activeWindow !== undefined ? workspace.activeWindow : workspace.activeClient;
+
+function desktopNumber(window) {
+    if (window.onAllDesktops) {
+        return 0;
+    }
+    if (window.desktops !== undefined) {
+        return window.desktops.length > 0 ? window.desktops[0].x11DesktopNumber : 0;
+    }
+    return window.desktop;
+}
+
+report(windows.map(function (window) {
+    return {
+        id: String(window.internalId),
+        caption: window.caption,
+        resourceClass: String(window.resourceClass),
+        desktopFileName: window.desktopFileName || "",
+        desktop: desktopNumber(window),
+        active: window === active,
+        normal: window.normalWindow,
+        skipTaskbar: window.skipTaskbar,
+    };
+}));
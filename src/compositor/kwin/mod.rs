@@ -0,0 +1,327 @@
+//! KDE KWin compositor implementation using KWin scripts over DBus.
+//!
+//! KWin has no DBus method to list windows, so a small script is loaded
+//! through `org.kde.kwin.Scripting`. It reports back by calling a method on
+//! an object zlaunch exports on the session bus.
+
+mod dbus;
+
+use super::{Compositor, WindowCapabilities, WindowInfo};
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::debug;
+
+use dbus::DbusKwin;
+
+const LIST_WINDOWS_SCRIPT: &str = include_str!("list_windows.js");
+const WINDOW_ACTION_SCRIPT: &str = include_str!("window_action.js");
+
+/// DBus access needed by the KWin backend.
+///
+/// Scripts call `report(value)` to send a JSON-serializable value back.
+pub trait KwinBus: Send + Sync {
+    /// Check that KWin is running on the session bus.
+    fn is_available(&self) -> bool;
+
+    /// Run a KWin script and return the JSON it reported.
+    fn run_script(&self, script: &str) -> Result<String>;
+}
+
+/// KWin compositor client using KWin scripts over DBus.
+pub struct KwinCompositor {
+    bus: Arc<dyn KwinBus>,
+}
+
+impl KwinCompositor {
+    /// Create a new KWin compositor client.
+    ///
+    /// Returns None if KDE session is not detected.
+    pub fn new() -> Option<Self> {
+        // Check if we're in a KDE session
+        let version = std::env::var("KDE_SESSION_VERSION").ok()?;
+
+        let bus = match DbusKwin::connect(version.trim() == "5") {
+            Ok(bus) => bus,
+            Err(e) => {
+                debug!(%e, "Failed to connect to the session bus");
+                return None;
+            }
+        };
+
+        // Verify KWin is available on the bus
+        if !bus.is_available() {
+            return None;
+        }
+
+        Some(Self::with_bus(Arc::new(bus)))
+    }
+
+    fn with_bus(bus: Arc<dyn KwinBus>) -> Self {
+        Self { bus }
+    }
+
+    /// Run the window action script for one window.
+    ///
+    /// `desktop` is the target of the "desktop" action and ignored otherwise.
+    fn run_action_script(&self, window_id: &str, action: &str, desktop: i32) -> Result<()> {
+        // The id is pasted into the script, so only accept UUIDs like "{1b2c...}"
+        let is_uuid = !window_id.is_empty()
+            && window_id
+                .chars()
+                .all(|c| c.is_ascii_hexdigit() || matches!(c, '{' | '}' | '-'));
+        if !is_uuid {
+            bail!("Invalid KWin window id: {}", window_id);
+        }
+
+        let script = WINDOW_ACTION_SCRIPT
+            .replace("__WINDOW_ID__", window_id)
+            .replace("__ACTION__", action)
+            .replace("__DESKTOP__", &desktop.to_string());
+        let json = self.bus.run_script(&script)?;
+        let done: bool = serde_json::from_str(&json).context("Failed to parse KWin reply")?;
+        if !done {
+            bail!("KWin could not apply {} to window {}", action, window_id);
+        }
+
+        Ok(())
+    }
+}
+
+impl Compositor for KwinCompositor {
+    fn list_windows(&self) -> Result<Vec<WindowInfo>> {
+        let json = self.bus.run_script(LIST_WINDOWS_SCRIPT)?;
+        let windows: Vec<KwinWindow> =
+            serde_json::from_str(&json).context("Failed to parse KWin window list JSON")?;
+
+        Ok(windows
+            .into_iter()
+            .filter_map(KwinWindow::into_window_info)
+            .collect())
+    }
+
+    fn focus_window(&self, window_id: &str) -> Result<()> {
+        self.run_action_script(window_id, "focus", 0)
+    }
+
+    fn name(&self) -> &'static str {
+        "KWin"
+    }
+
+    fn capabilities(&self) -> WindowCapabilities {
+        WindowCapabilities {
+            close: true,
+            move_to_workspace: true,
+            fullscreen: true,
+            // KWin windows are floating unless tiled, and scripts cannot tile
+            floating: false,
+            pin: true,
+        }
+    }
+
+    fn close_window(&self, window_id: &str) -> Result<()> {
+        self.run_action_script(window_id, "close", 0)
+    }
+
+    fn move_window_to_workspace(&self, window_id: &str, workspace: i32) -> Result<()> {
+        if workspace < 1 {
+            bail!("Invalid KWin desktop number: {}", workspace);
+        }
+        self.run_action_script(window_id, "desktop", workspace)
+    }
+
+    fn toggle_fullscreen(&self, window_id: &str) -> Result<()> {
+        self.run_action_script(window_id, "fullscreen", 0)
+    }
+
+    fn pin(&self, window_id: &str) -> Result<()> {
+        self.run_action_script(window_id, "pin", 0)
+    }
+}
+
+/// A window as reported by the list script.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KwinWindow {
+    /// Internal UUID of the window
+    id: String,
+    caption: String,
+    resource_class: String,
+    /// App id derived from the desktop file, empty if unknown
+    desktop_file_name: String,
+    /// Virtual desktop number, 0 when on all desktops
+    desktop: i32,
+    active: bool,
+    normal: bool,
+    skip_taskbar: bool,
+}
+
+impl KwinWindow {
+    /// Convert to WindowInfo, skipping panels, popups and zlaunch itself.
+    fn into_window_info(self) -> Option<WindowInfo> {
+        if !self.normal || self.skip_taskbar {
+            return None;
+        }
+
+        let class = if self.desktop_file_name.is_empty() {
+            self.resource_class
+        } else {
+            self.desktop_file_name
+        };
+        if class.is_empty() || class.to_lowercase() == "zlaunch" {
+            return None;
+        }
+
+        Some(WindowInfo {
+            address: self.id,
+            title: if self.caption.is_empty() {
+                class.clone()
+            } else {
+                self.caption
+            },
+            class,
+            workspace: self.desktop,
+            focused: self.active,
+            scratchpad: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compositor::WindowAction;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    /// Replays canned script reports and records the scripts it was given.
+    #[derive(Default)]
+    struct MockBus {
+        reports: Mutex<VecDeque<String>>,
+        scripts: Mutex<Vec<String>>,
+    }
+
+    impl MockBus {
+        fn with_reports(reports: &[&str]) -> Arc<Self> {
+            Arc::new(Self {
+                reports: Mutex::new(reports.iter().map(|r| r.to_string()).collect()),
+                scripts: Mutex::default(),
+            })
+        }
+    }
+
+    impl KwinBus for MockBus {
+        fn is_available(&self) -> bool {
+            true
+        }
+
+        fn run_script(&self, script: &str) -> Result<String> {
+            self.scripts.lock().unwrap().push(script.to_string());
+            self.reports
+                .lock()
+                .unwrap()
+                .pop_front()
+                .context("Script did not report back")
+        }
+    }
+
+    const WINDOW_LIST: &str = r#"[
+        {"id": "{6f1d0c1e-7a4b-4c1e-9d2a-3b5e8f0a1c2d}", "caption": "zlaunch — Dolphin",
+         "resourceClass": "dolphin", "desktopFileName": "org.kde.dolphin", "desktop": 2,
+         "active": true, "normal": true, "skipTaskbar": false},
+        {"id": "{0a9e8d7c-6b5a-4f3e-8d2c-1b0a9f8e7d6c}", "caption": "",
+         "resourceClass": "xterm", "desktopFileName": "", "desktop": 0,
+         "active": false, "normal": true, "skipTaskbar": false},
+        {"id": "{11111111-2222-4333-8444-555555555555}", "caption": "Plasma",
+         "resourceClass": "plasmashell", "desktopFileName": "org.kde.plasmashell", "desktop": 1,
+         "active": false, "normal": false, "skipTaskbar": true},
+        {"id": "{22222222-3333-4444-8555-666666666666}", "caption": "zlaunch",
+         "resourceClass": "zlaunch", "desktopFileName": "", "desktop": 1,
+         "active": false, "normal": true, "skipTaskbar": false}
+    ]"#;
+
+    #[test]
+    fn test_list_windows() {
+        let bus = MockBus::with_reports(&[WINDOW_LIST]);
+        let kwin = KwinCompositor::with_bus(bus.clone());
+
+        let windows = kwin.list_windows().unwrap();
+        assert_eq!(
+            bus.scripts.lock().unwrap().as_slice(),
+            [LIST_WINDOWS_SCRIPT]
+        );
+
+        // Panels and zlaunch itself are skipped
+        assert_eq!(windows.len(), 2);
+
+        let dolphin = &windows[0];
+        assert_eq!(dolphin.address, "{6f1d0c1e-7a4b-4c1e-9d2a-3b5e8f0a1c2d}");
+        assert_eq!(dolphin.title, "zlaunch — Dolphin");
+        assert_eq!(dolphin.class, "org.kde.dolphin");
+        assert_eq!(dolphin.workspace, 2);
+        assert!(dolphin.focused);
+
+        // Falls back to the window class without a desktop file or caption
+        let xterm = &windows[1];
+        assert_eq!(xterm.class, "xterm");
+        assert_eq!(xterm.title, "xterm");
+        assert_eq!(xterm.workspace, 0);
+        assert!(!xterm.focused);
+    }
+
+    #[test]
+    fn test_focus_window() {
+        let id = "{6f1d0c1e-7a4b-4c1e-9d2a-3b5e8f0a1c2d}";
+        let bus = MockBus::with_reports(&["true", "false"]);
+        let kwin = KwinCompositor::with_bus(bus.clone());
+
+        kwin.focus_window(id).unwrap();
+        assert!(kwin.focus_window(id).is_err());
+
+        let scripts = bus.scripts.lock().unwrap();
+        assert_eq!(scripts.len(), 2);
+        assert!(scripts[0].contains(&format!("const target = \"{}\";", id)));
+        assert!(scripts[0].contains("const action = \"focus\";"));
+    }
+
+    #[test]
+    fn test_window_actions() {
+        let id = "{6f1d0c1e-7a4b-4c1e-9d2a-3b5e8f0a1c2d}";
+        let bus = MockBus::with_reports(&["true", "true"]);
+        let kwin = KwinCompositor::with_bus(bus.clone());
+
+        kwin.run_window_action(id, WindowAction::MoveToWorkspace(3))
+            .unwrap();
+        kwin.run_window_action(id, WindowAction::Close).unwrap();
+
+        // Unsupported and invalid actions fail without running a script
+        assert!(!kwin.capabilities().supports(WindowAction::ToggleFloating));
+        assert!(kwin.toggle_floating(id).is_err());
+        assert!(kwin.move_window_to_workspace(id, -1).is_err());
+
+        let scripts = bus.scripts.lock().unwrap();
+        assert_eq!(scripts.len(), 2);
+        assert!(scripts[0].contains("const action = \"desktop\";"));
+        assert!(scripts[0].contains("const desktopNumber = 3;"));
+        assert!(scripts[1].contains("const action = \"close\";"));
+    }
+
+    #[test]
+    fn test_focus_rejects_invalid_id() {
+        let bus = MockBus::with_reports(&["true"]);
+        let kwin = KwinCompositor::with_bus(bus.clone());
+
+        assert!(
+            kwin.focus_window("\"; workspace.slotKillWindow(); \"")
+                .is_err()
+        );
+        assert!(kwin.focus_window("").is_err());
+        assert!(bus.scripts.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_script_failure() {
+        let kwin = KwinCompositor::with_bus(MockBus::with_reports(&[]));
+        assert!(kwin.list_windows().is_err());
+    }
+}
@@ -0,0 +1,50 @@
+// Apply an action to the window with the given internal id and report whether it succeeded.
+const target = "__WINDOW_ID__";
+const action = "__ACTION__";
+const desktopNumber = __DESKTOP__;
+const windows = workspace.windowList ? workspace.windowList() : workspace.clientList();
+
+// Returns false if there is no such desktop.
+function moveToDesktop(window, number) {
+    if (typeof workspace.desktops !== "number") {
+        // KWin 6 lists desktops as objects, KWin 5 only counts them
+        const desktop = workspace.desktops.find(function (d) { return d.x11DesktopNumber === number; });
+        if (desktop === undefined) {
+            return false;
+        }
+        window.desktops = [desktop];
+        return true;
+    }
+    if (number > workspace.desktops) {
+        return false;
+    }
+    window.desktop = number;
+    return true;
+}
+
+let done = false;
+for (const window of windows) {
+    if (String(window.internalId) !== target) {
+        continue;
+    }
+    done = true;
+
+    if (action === "focus") {
+        if (workspace.activeWindow !== undefined) {
+            workspace.activeWindow = window;
+        } else {
+            workspace.activeClient = window;
+        }
+    } else if (action === "close") {
+        window.closeWindow();
+    } else if (action === "desktop") {
+        done = moveToDesktop(window, desktopNumber);
+    } else if (action === "fullscreen") {
+        window.fullScreen = !window.fullScreen;
+    } else if (action === "pin") {
+        window.onAllDesktops = !window.onAllDesktops;
+    }
+    break;
+}
+
+report(done);
@@ -2,7 +2,9 @@
 //!
 //! This module provides a trait-based abstraction for interacting with
 //! window managers to list windows and switch focus. On Unix/Linux,
-//! implementations are provided for Hyprland (IPC socket) and KDE/KWin (DBus).
+//! implementations are provided for Hyprland (IPC socket), Sway and i3
+//! (i3 IPC socket), Niri (JSON IPC socket) and KDE/KWin (scripting over DBus),
+//! with wlr-foreign-toplevel-management for other wlroots compositors.
 //! On Windows, a NoopCompositor is used as window switching is not yet supported.
 
 mod detect;
@@ -10,14 +12,27 @@ mod detect;
 mod hyprland;
 #[cfg(unix)]
 mod kwin;
+#[cfg(unix)]
+mod niri;
 mod noop;
+#[cfg(unix)]
+mod sway;
+#[cfg(all(test, unix))]
+pub(crate) mod test_display;
+#[cfg(all(test, unix))]
+mod test_server;
+#[cfg(unix)]
+pub(crate) mod wayland;
+#[cfg(unix)]
+mod wlr;
 
 pub use detect::detect_compositor;
 
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Information about an open window from the compositor.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WindowInfo {
     /// Unique window identifier (compositor-specific, e.g., "0x5678abcd" for Hyprland)
     pub address: String,
@@ -29,14 +44,98 @@ pub struct WindowInfo {
     pub workspace: i32,
     /// Whether this window is currently focused
     pub focused: bool,
+    /// Whether the window is hidden in the scratchpad (Sway, i3)
+    #[serde(default)]
+    pub scratchpad: bool,
+}
+
+/// Information about a workspace from the compositor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceInfo {
+    /// Workspace number, matching `WindowInfo::workspace`
+    pub id: i32,
+    /// Workspace name, the number as text for unnamed workspaces
+    pub name: String,
+    /// Number of windows on the workspace
+    pub windows: usize,
+    /// Whether this is the active workspace
+    pub focused: bool,
+}
+
+/// Information about an output (monitor) from the compositor.
+///
+/// Positions and sizes are in logical pixels, after the output's scale
+/// and rotation are applied.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputInfo {
+    /// Output name (e.g., "DP-1", "eDP-1")
+    pub name: String,
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+/// Window operations a compositor supports besides listing and focusing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WindowCapabilities {
+    pub close: bool,
+    pub move_to_workspace: bool,
+    pub fullscreen: bool,
+    pub floating: bool,
+    pub pin: bool,
+}
+
+impl WindowCapabilities {
+    /// Check if any operation is supported.
+    pub fn any(&self) -> bool {
+        self.close || self.move_to_workspace || self.fullscreen || self.floating || self.pin
+    }
+
+    /// Check if an action is supported.
+    pub fn supports(&self, action: WindowAction) -> bool {
+        match action {
+            WindowAction::Close => self.close,
+            WindowAction::MoveToWorkspace(_) => self.move_to_workspace,
+            WindowAction::ToggleFullscreen => self.fullscreen,
+            WindowAction::ToggleFloating => self.floating,
+            WindowAction::Pin => self.pin,
+        }
+    }
+}
+
+/// An operation on a window, offered as a secondary action in the launcher.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowAction {
+    Close,
+    MoveToWorkspace(i32),
+    ToggleFullscreen,
+    ToggleFloating,
+    /// Show the window on all workspaces
+    Pin,
+}
+
+impl fmt::Display for WindowAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Close => write!(f, "Close Window"),
+            Self::MoveToWorkspace(workspace) => write!(f, "Move to Workspace {}", workspace),
+            Self::ToggleFullscreen => write!(f, "Toggle Fullscreen"),
+            Self::ToggleFloating => write!(f, "Toggle Floating"),
+            Self::Pin => write!(f, "Toggle Pin"),
+        }
+    }
 }
 
 /// Trait for compositor window management operations.
 ///
 /// Implementations must be thread-safe (Send + Sync) as the compositor
 /// may be accessed from different threads in the daemon.
+///
+/// Operations other than focusing are optional. Backends report the ones
+/// they implement in [`Compositor::capabilities`], the others fail.
 pub trait Compositor: Send + Sync {
-    /// List all open windows.
+    /// List all open windows, most recently focused first where known.
     ///
     /// Returns only "normal" user windows - layer shell windows (panels, bars),
     /// the launcher itself, and other special windows should be filtered out.
@@ -49,6 +148,77 @@ pub trait Compositor: Send + Sync {
 
     /// Get the compositor name for logging/debugging.
     fn name(&self) -> &'static str;
+
+    /// List the outputs, used to pick the one the launcher opens on.
+    ///
+    /// Compositors that do not report outputs return none, leaving the
+    /// choice to the compositor.
+    fn list_outputs(&self) -> anyhow::Result<Vec<OutputInfo>> {
+        Ok(Vec::new())
+    }
+
+    /// Check if the compositor has workspaces to list and switch to.
+    ///
+    /// Answered without asking the compositor, as the launcher checks it on open.
+    fn has_workspaces(&self) -> bool {
+        false
+    }
+
+    /// List the workspaces.
+    ///
+    /// Only workspaces the compositor knows about are listed, which may not
+    /// include empty ones. Compositors without workspaces return none.
+    fn list_workspaces(&self) -> anyhow::Result<Vec<WorkspaceInfo>> {
+        Ok(Vec::new())
+    }
+
+    /// Switch to a workspace by its number, creating it if needed.
+    fn focus_workspace(&self, _workspace: i32) -> anyhow::Result<()> {
+        anyhow::bail!("{} cannot switch workspaces", self.name())
+    }
+
+    /// Get the optional window operations this compositor supports.
+    fn capabilities(&self) -> WindowCapabilities {
+        WindowCapabilities::default()
+    }
+
+    /// Close a window by its address.
+    fn close_window(&self, _window_id: &str) -> anyhow::Result<()> {
+        anyhow::bail!("{} cannot close windows", self.name())
+    }
+
+    /// Move a window to a workspace without following it.
+    fn move_window_to_workspace(&self, _window_id: &str, _workspace: i32) -> anyhow::Result<()> {
+        anyhow::bail!("{} cannot move windows between workspaces", self.name())
+    }
+
+    /// Toggle fullscreen on a window.
+    fn toggle_fullscreen(&self, _window_id: &str) -> anyhow::Result<()> {
+        anyhow::bail!("{} cannot make windows fullscreen", self.name())
+    }
+
+    /// Toggle whether a window is floating.
+    fn toggle_floating(&self, _window_id: &str) -> anyhow::Result<()> {
+        anyhow::bail!("{} cannot make windows floating", self.name())
+    }
+
+    /// Toggle whether a window is shown on all workspaces.
+    fn pin(&self, _window_id: &str) -> anyhow::Result<()> {
+        anyhow::bail!("{} cannot pin windows", self.name())
+    }
+
+    /// Perform a window action with the matching operation.
+    fn run_window_action(&self, window_id: &str, action: WindowAction) -> anyhow::Result<()> {
+        match action {
+            WindowAction::Close => self.close_window(window_id),
+            WindowAction::MoveToWorkspace(workspace) => {
+                self.move_window_to_workspace(window_id, workspace)
+            }
+            WindowAction::ToggleFullscreen => self.toggle_fullscreen(window_id),
+            WindowAction::ToggleFloating => self.toggle_floating(window_id),
+            WindowAction::Pin => self.pin(window_id),
+        }
+    }
 }
 
 impl fmt::Debug for dyn Compositor {
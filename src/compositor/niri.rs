@@ -0,0 +1,309 @@
+//! Niri compositor implementation using its JSON IPC socket.
+//!
+//! Each request is a single line of JSON and gets a single line reply,
+//! either `{"Ok": ...}` or `{"Err": "message"}`.
+
+use super::{Compositor, OutputInfo, WindowInfo};
+use anyhow::{Context, Result, anyhow, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+
+/// Niri compositor client using IPC socket communication.
+pub struct NiriCompositor {
+    socket_path: PathBuf,
+}
+
+impl NiriCompositor {
+    /// Create a new Niri compositor client.
+    ///
+    /// Returns None if NIRI_SOCKET is not set.
+    pub fn new() -> Option<Self> {
+        let socket = std::env::var("NIRI_SOCKET").ok()?;
+        Some(Self::with_socket(socket))
+    }
+
+    fn with_socket(socket_path: impl Into<PathBuf>) -> Self {
+        Self {
+            socket_path: socket_path.into(),
+        }
+    }
+
+    /// Send a request to Niri and receive its reply.
+    fn send_request(&self, request: &Request) -> Result<Reply> {
+        let stream = UnixStream::connect(&self.socket_path)
+            .with_context(|| format!("Failed to connect to Niri socket: {:?}", self.socket_path))?;
+
+        let mut line = serde_json::to_string(request)?;
+        line.push('\n');
+        (&stream)
+            .write_all(line.as_bytes())
+            .context("Failed to write request to Niri socket")?;
+
+        let mut reply = String::new();
+        BufReader::new(&stream)
+            .read_line(&mut reply)
+            .context("Failed to read reply from Niri socket")?;
+
+        let reply: Result<Reply, String> =
+            serde_json::from_str(&reply).context("Failed to parse Niri reply JSON")?;
+        reply.map_err(|e| anyhow!("Niri request failed: {}", e))
+    }
+}
+
+impl Compositor for NiriCompositor {
+    fn list_windows(&self) -> Result<Vec<WindowInfo>> {
+        let Reply::Windows(windows) = self.send_request(&Request::Windows)? else {
+            bail!("Unexpected reply to Niri Windows request");
+        };
+        let Reply::Workspaces(workspaces) = self.send_request(&Request::Workspaces)? else {
+            bail!("Unexpected reply to Niri Workspaces request");
+        };
+
+        // Windows only know their workspace id, show the index users see instead
+        let indices: HashMap<u64, i32> = workspaces.iter().map(|w| (w.id, w.idx)).collect();
+
+        Ok(windows
+            .into_iter()
+            .filter_map(|window| {
+                let workspace = window
+                    .workspace_id
+                    .and_then(|id| indices.get(&id).copied())
+                    .unwrap_or(0);
+                window.into_window_info(workspace)
+            })
+            .collect())
+    }
+
+    fn focus_window(&self, window_id: &str) -> Result<()> {
+        let id = window_id
+            .parse()
+            .with_context(|| format!("Invalid Niri window id: {}", window_id))?;
+        self.send_request(&Request::Action(Action::FocusWindow { id }))?;
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "Niri"
+    }
+
+    fn list_outputs(&self) -> Result<Vec<OutputInfo>> {
+        let Reply::Outputs(outputs) = self.send_request(&Request::Outputs)? else {
+            bail!("Unexpected reply to Niri Outputs request");
+        };
+        let mut outputs: Vec<OutputInfo> = outputs
+            .into_values()
+            // Disabled outputs have no logical position
+            .filter_map(|output| {
+                let logical = output.logical?;
+                Some(OutputInfo {
+                    name: output.name,
+                    x: logical.x,
+                    y: logical.y,
+                    width: logical.width,
+                    height: logical.height,
+                })
+            })
+            .collect();
+        // Outputs come in a map, list them in a stable order
+        outputs.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(outputs)
+    }
+}
+
+/// A request sent to Niri.
+#[derive(Debug, Serialize)]
+enum Request {
+    Windows,
+    Workspaces,
+    Outputs,
+    Action(Action),
+}
+
+/// An action requested from Niri.
+#[derive(Debug, Serialize)]
+enum Action {
+    FocusWindow { id: u64 },
+}
+
+/// The successful reply to a request.
+#[derive(Debug, Deserialize)]
+enum Reply {
+    /// The action was performed
+    Handled,
+    Windows(Vec<NiriWindow>),
+    Workspaces(Vec<NiriWorkspace>),
+    Outputs(HashMap<String, NiriOutput>),
+}
+
+/// Window information from Niri's Windows reply.
+#[derive(Debug, Deserialize)]
+struct NiriWindow {
+    id: u64,
+    title: Option<String>,
+    app_id: Option<String>,
+    workspace_id: Option<u64>,
+    is_focused: bool,
+}
+
+impl NiriWindow {
+    /// Convert to WindowInfo, skipping zlaunch itself.
+    fn into_window_info(self, workspace: i32) -> Option<WindowInfo> {
+        let class = self.app_id.unwrap_or_default();
+        if class.is_empty() || class.to_lowercase() == "zlaunch" {
+            return None;
+        }
+
+        let title = match self.title {
+            Some(title) if !title.is_empty() => title,
+            _ => class.clone(),
+        };
+
+        Some(WindowInfo {
+            address: self.id.to_string(),
+            title,
+            class,
+            workspace,
+            focused: self.is_focused,
+            scratchpad: false,
+        })
+    }
+}
+
+/// Workspace information from Niri's Workspaces reply.
+#[derive(Debug, Deserialize)]
+struct NiriWorkspace {
+    id: u64,
+    /// Index of the workspace on its output, starting at 1
+    idx: i32,
+}
+
+/// Output information from Niri's Outputs reply.
+#[derive(Debug, Deserialize)]
+struct NiriOutput {
+    name: String,
+    /// None for disabled outputs
+    logical: Option<NiriLogicalOutput>,
+}
+
+/// Position and size of an output in the logical layout.
+#[derive(Debug, Deserialize)]
+struct NiriLogicalOutput {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compositor::test_server;
+    use std::thread::JoinHandle;
+
+    const WINDOWS: &str = include_str!("fixtures/niri-windows.json");
+    const WORKSPACES: &str = include_str!("fixtures/niri-workspaces.json");
+
+    /// Serve `replies` in order, returning the request lines received.
+    fn fake_server(name: &str, replies: Vec<&'static str>) -> (PathBuf, JoinHandle<Vec<String>>) {
+        test_server::fake_server(&format!("niri-{}", name), replies, |stream, reply| {
+            let mut request = String::new();
+            BufReader::new(&*stream).read_line(&mut request).unwrap();
+
+            // Replies are a single line, so compact the pretty fixtures
+            let reply: serde_json::Value = serde_json::from_str(reply).unwrap();
+            writeln!(stream, "{}", reply).unwrap();
+            request.trim_end().to_string()
+        })
+    }
+
+    #[test]
+    fn test_list_windows() {
+        let (path, server) = fake_server("list", vec![WINDOWS, WORKSPACES]);
+        let niri = NiriCompositor::with_socket(&path);
+
+        let windows = niri.list_windows().unwrap();
+        assert_eq!(server.join().unwrap(), [r#""Windows""#, r#""Workspaces""#]);
+        let _ = std::fs::remove_file(&path);
+
+        let summary: Vec<(&str, &str, i32, bool)> = windows
+            .iter()
+            .map(|w| (w.address.as_str(), w.class.as_str(), w.workspace, w.focused))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("3", "Alacritty", 1, true),
+                // Workspace ids map to the index on their output
+                ("7", "firefox", 1, false),
+                ("9", "org.gnome.Nautilus", 1, false),
+            ]
+        );
+        assert_eq!(windows[0].title, "~/src/zlaunch");
+        // Untitled windows are named after their app id
+        assert_eq!(windows[2].title, "org.gnome.Nautilus");
+    }
+
+    #[test]
+    fn test_focus_window() {
+        let (path, server) = fake_server(
+            "focus",
+            vec![r#"{"Ok":"Handled"}"#, r#"{"Err":"window not found"}"#],
+        );
+        let niri = NiriCompositor::with_socket(&path);
+
+        niri.focus_window("7").unwrap();
+        let err = niri.focus_window("99").unwrap_err();
+        assert!(err.to_string().contains("window not found"));
+
+        let requests = server.join().unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(
+            requests,
+            [
+                r#"{"Action":{"FocusWindow":{"id":7}}}"#,
+                r#"{"Action":{"FocusWindow":{"id":99}}}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_outputs() {
+        let (path, server) = fake_server(
+            "outputs",
+            vec![
+                r#"{"Ok": {"Outputs": {
+                    "HDMI-A-1": {"name": "HDMI-A-1", "make": "", "model": "", "logical": null},
+                    "DP-1": {"name": "DP-1", "make": "Dell", "model": "U2723QE",
+                        "logical": {"x": 1280, "y": 0, "width": 2560, "height": 1440,
+                                    "scale": 1.5, "transform": "Normal"}},
+                    "eDP-1": {"name": "eDP-1", "make": "BOE", "model": "0x0BCA",
+                        "logical": {"x": 0, "y": 0, "width": 1280, "height": 800,
+                                    "scale": 2.0, "transform": "Normal"}}
+                }}}"#,
+            ],
+        );
+        let niri = NiriCompositor::with_socket(&path);
+
+        let outputs = niri.list_outputs().unwrap();
+        assert_eq!(server.join().unwrap(), [r#""Outputs""#]);
+        let _ = std::fs::remove_file(&path);
+
+        let summary: Vec<(&str, i32, i32)> = outputs
+            .iter()
+            .map(|o| (o.name.as_str(), o.x, o.width))
+            .collect();
+        // Disabled outputs are skipped
+        assert_eq!(summary, [("DP-1", 1280, 2560), ("eDP-1", 0, 1280)]);
+    }
+
+    #[test]
+    fn test_focus_rejects_invalid_id() {
+        let niri = NiriCompositor::with_socket("/nonexistent/niri.sock");
+        let err = niri.focus_window("0x1234").unwrap_err();
+        assert!(err.to_string().contains("Invalid Niri window id"));
+    }
+}
@@ -0,0 +1,420 @@
+//! Sway and i3 compositor implementation using the i3 IPC protocol.
+//!
+//! Every message is the magic string `i3-ipc`, followed by the payload
+//! length and the message type as native-endian u32, then the payload.
+
+use super::{Compositor, OutputInfo, WindowInfo};
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use std::io::{Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+
+const MAGIC: &[u8; 6] = b"i3-ipc";
+const HEADER_LEN: usize = 14;
+
+/// Run the payload as a command, replying with one result per command.
+const RUN_COMMAND: u32 = 0;
+/// Get the outputs as JSON.
+const GET_OUTPUTS: u32 = 3;
+/// Get the layout tree as JSON.
+const GET_TREE: u32 = 4;
+
+/// Name of the hidden workspace holding scratchpad windows.
+const SCRATCHPAD_WORKSPACE: &str = "__i3_scratch";
+
+/// Sway or i3 compositor client using i3 IPC socket communication.
+pub struct SwayCompositor {
+    socket_path: PathBuf,
+    name: &'static str,
+}
+
+impl SwayCompositor {
+    /// Create a new Sway or i3 compositor client.
+    ///
+    /// Returns None if neither SWAYSOCK nor I3SOCK is set.
+    pub fn new() -> Option<Self> {
+        if let Ok(socket) = std::env::var("SWAYSOCK") {
+            return Some(Self::with_socket(socket, "Sway"));
+        }
+        let socket = std::env::var("I3SOCK").ok()?;
+        Some(Self::with_socket(socket, "i3"))
+    }
+
+    fn with_socket(socket_path: impl Into<PathBuf>, name: &'static str) -> Self {
+        Self {
+            socket_path: socket_path.into(),
+            name,
+        }
+    }
+
+    /// Send a message and receive the reply payload.
+    fn send_message(&self, message_type: u32, payload: &str) -> Result<String> {
+        let mut stream = UnixStream::connect(&self.socket_path).with_context(|| {
+            format!(
+                "Failed to connect to {} socket: {:?}",
+                self.name, self.socket_path
+            )
+        })?;
+
+        write_message(&mut stream, message_type, payload.as_bytes())
+            .with_context(|| format!("Failed to write message to {} socket", self.name))?;
+        let (reply_type, reply) = read_message(&mut stream)
+            .with_context(|| format!("Failed to read reply from {} socket", self.name))?;
+
+        if reply_type != message_type {
+            bail!(
+                "Expected reply of type {}, got {}",
+                message_type,
+                reply_type
+            );
+        }
+        String::from_utf8(reply).context("Reply is not valid UTF-8")
+    }
+}
+
+impl Compositor for SwayCompositor {
+    fn list_windows(&self) -> Result<Vec<WindowInfo>> {
+        let json = self.send_message(GET_TREE, "")?;
+        let tree: Node = serde_json::from_str(&json)
+            .with_context(|| format!("Failed to parse {} tree JSON", self.name))?;
+
+        let mut windows = Vec::new();
+        collect_windows(&tree, 0, false, &mut windows);
+
+        Ok(windows)
+    }
+
+    fn focus_window(&self, window_id: &str) -> Result<()> {
+        let cmd = format!("[con_id={}] focus", window_id);
+        let json = self.send_message(RUN_COMMAND, &cmd)?;
+        let results: Vec<CommandResult> =
+            serde_json::from_str(&json).context("Failed to parse command reply JSON")?;
+
+        if let Some(failed) = results.into_iter().find(|r| !r.success) {
+            bail!(
+                "Failed to focus window {}: {}",
+                window_id,
+                failed.error.unwrap_or_else(|| "unknown error".to_string())
+            );
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn list_outputs(&self) -> Result<Vec<OutputInfo>> {
+        let json = self.send_message(GET_OUTPUTS, "")?;
+        let outputs: Vec<Output> = serde_json::from_str(&json)
+            .with_context(|| format!("Failed to parse {} outputs JSON", self.name))?;
+
+        Ok(outputs
+            .into_iter()
+            .filter(|o| o.active)
+            .map(|o| OutputInfo {
+                name: o.name,
+                x: o.rect.x,
+                y: o.rect.y,
+                width: o.rect.width,
+                height: o.rect.height,
+            })
+            .collect())
+    }
+}
+
+/// Write one message with its header.
+fn write_message(writer: &mut impl Write, message_type: u32, payload: &[u8]) -> Result<()> {
+    let mut message = Vec::with_capacity(HEADER_LEN + payload.len());
+    message.extend_from_slice(MAGIC);
+    message.extend_from_slice(&u32::try_from(payload.len())?.to_ne_bytes());
+    message.extend_from_slice(&message_type.to_ne_bytes());
+    message.extend_from_slice(payload);
+
+    writer.write_all(&message)?;
+    Ok(())
+}
+
+/// Read one message, returning its type and payload.
+fn read_message(reader: &mut impl Read) -> Result<(u32, Vec<u8>)> {
+    let mut header = [0u8; HEADER_LEN];
+    reader.read_exact(&mut header)?;
+
+    let (magic, rest) = header.split_at(MAGIC.len());
+    if magic != MAGIC {
+        bail!("Invalid i3 IPC message header");
+    }
+    let (len, message_type) = rest.split_at(4);
+    let len = u32::from_ne_bytes(len.try_into()?) as usize;
+    let message_type = u32::from_ne_bytes(message_type.try_into()?);
+
+    let mut payload = vec![0u8; len];
+    reader.read_exact(&mut payload)?;
+    Ok((message_type, payload))
+}
+
+/// Walk the tree and collect its windows.
+///
+/// `workspace` is the number of the workspace containing `node`, 0 for
+/// named workspaces and the scratchpad, which have no number.
+fn collect_windows(node: &Node, workspace: i32, scratchpad: bool, windows: &mut Vec<WindowInfo>) {
+    let (workspace, scratchpad) = match node.node_type.as_str() {
+        // Bars and other docked clients are not user windows
+        "dockarea" => return,
+        "workspace" if node.name.as_deref() == Some(SCRATCHPAD_WORKSPACE) => (0, true),
+        "workspace" => (node.num.filter(|&num| num > 0).unwrap_or(0), false),
+        _ => (workspace, scratchpad),
+    };
+
+    if let Some(window) = node.to_window_info(workspace, scratchpad) {
+        windows.push(window);
+        return;
+    }
+
+    for child in node.nodes.iter().chain(&node.floating_nodes) {
+        collect_windows(child, workspace, scratchpad, windows);
+    }
+}
+
+/// A node of the layout tree returned by GET_TREE.
+#[derive(Debug, Deserialize)]
+struct Node {
+    id: u64,
+    #[serde(rename = "type")]
+    node_type: String,
+    name: Option<String>,
+    /// Workspace number, -1 for named workspaces on Sway
+    num: Option<i32>,
+    #[serde(default)]
+    focused: bool,
+    /// Wayland app ID, Sway only
+    app_id: Option<String>,
+    /// X11 window ID
+    window: Option<u64>,
+    window_properties: Option<WindowProperties>,
+    #[serde(default)]
+    nodes: Vec<Node>,
+    #[serde(default)]
+    floating_nodes: Vec<Node>,
+}
+
+impl Node {
+    /// Convert a container holding a client window to WindowInfo.
+    fn to_window_info(&self, workspace: i32, scratchpad: bool) -> Option<WindowInfo> {
+        if !matches!(self.node_type.as_str(), "con" | "floating_con") {
+            return None;
+        }
+        if self.app_id.is_none() && self.window.is_none() {
+            return None;
+        }
+
+        let properties = self.window_properties.as_ref();
+        let class = self
+            .app_id
+            .clone()
+            .or_else(|| properties.and_then(|p| p.class.clone()))
+            .or_else(|| properties.and_then(|p| p.instance.clone()))
+            .unwrap_or_default();
+
+        // Exclude zlaunch itself
+        if class.to_lowercase() == "zlaunch" {
+            return None;
+        }
+
+        let title = match self.name.as_deref() {
+            Some(name) if !name.is_empty() => name.to_string(),
+            _ => class.clone(),
+        };
+
+        Some(WindowInfo {
+            address: self.id.to_string(),
+            title,
+            class,
+            workspace,
+            focused: self.focused,
+            scratchpad,
+        })
+    }
+}
+
+/// X11 properties of a window.
+#[derive(Debug, Deserialize)]
+struct WindowProperties {
+    class: Option<String>,
+    instance: Option<String>,
+}
+
+/// An output from the GET_OUTPUTS reply.
+#[derive(Debug, Deserialize)]
+struct Output {
+    name: String,
+    active: bool,
+    /// Position and logical size
+    rect: Rect,
+}
+
+#[derive(Debug, Deserialize)]
+struct Rect {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+}
+
+/// Result of a single command in a RUN_COMMAND reply.
+#[derive(Debug, Deserialize)]
+struct CommandResult {
+    success: bool,
+    error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compositor::test_server;
+    use std::thread::JoinHandle;
+
+    const SWAY_TREE: &str = include_str!("fixtures/sway-tree.json");
+    const I3_TREE: &str = include_str!("fixtures/i3-tree.json");
+
+    /// Serve `replies` in order, returning the messages received.
+    fn fake_server(
+        name: &str,
+        replies: Vec<&'static str>,
+    ) -> (PathBuf, JoinHandle<Vec<(u32, String)>>) {
+        test_server::fake_server(&format!("sway-{}", name), replies, |stream, reply| {
+            let (message_type, payload) = read_message(stream).unwrap();
+            write_message(stream, message_type, reply.as_bytes()).unwrap();
+            (message_type, String::from_utf8(payload).unwrap())
+        })
+    }
+
+    #[test]
+    fn test_list_windows_from_sway_tree() {
+        let (path, server) = fake_server("sway-tree", vec![SWAY_TREE]);
+        let sway = SwayCompositor::with_socket(&path, "Sway");
+
+        let windows = sway.list_windows().unwrap();
+        assert_eq!(server.join().unwrap(), [(GET_TREE, String::new())]);
+        let _ = std::fs::remove_file(&path);
+
+        let summary: Vec<(&str, &str, i32, bool, bool)> = windows
+            .iter()
+            .map(|w| {
+                let (address, class) = (w.address.as_str(), w.class.as_str());
+                (address, class, w.workspace, w.focused, w.scratchpad)
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                // Scratchpad windows are listed too
+                ("12", "org.pulseaudio.pavucontrol", 0, false, true),
+                ("8", "foot", 1, true, false),
+                ("9", "org.gnome.Nautilus", 1, false, false),
+                // XWayland windows fall back to the X11 class
+                ("10", "steam", 2, false, false),
+                // Named workspaces have no number
+                ("13", "thunderbird", 0, false, false),
+            ]
+        );
+        assert_eq!(windows[1].title, "~/src/zlaunch — fish");
+        // Untitled windows are named after their class
+        assert_eq!(windows[2].title, "org.gnome.Nautilus");
+    }
+
+    #[test]
+    fn test_list_windows_from_i3_tree() {
+        let (path, server) = fake_server("i3-tree", vec![I3_TREE]);
+        let i3 = SwayCompositor::with_socket(&path, "i3");
+
+        let windows = i3.list_windows().unwrap();
+        server.join().unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        // The i3bar dock client is skipped
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows[0].address, "94251016341136");
+        assert_eq!(windows[0].class, "firefox");
+        assert_eq!(windows[0].title, "GitHub - Mozilla Firefox");
+        assert_eq!(windows[0].workspace, 1);
+        assert!(windows[0].focused);
+    }
+
+    #[test]
+    fn test_focus_window() {
+        let (path, server) = fake_server(
+            "focus",
+            vec![
+                r#"[{"success":true}]"#,
+                r#"[{"success":false,"parse_error":false,"error":"No matching node."}]"#,
+            ],
+        );
+        let sway = SwayCompositor::with_socket(&path, "Sway");
+
+        sway.focus_window("8").unwrap();
+        let err = sway.focus_window("99").unwrap_err();
+        assert!(err.to_string().contains("No matching node."));
+
+        let requests = server.join().unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(
+            requests,
+            [
+                (RUN_COMMAND, "[con_id=8] focus".to_string()),
+                (RUN_COMMAND, "[con_id=99] focus".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_outputs() {
+        let (path, server) = fake_server(
+            "outputs",
+            vec![
+                r#"[
+                    {"name": "eDP-1", "active": true, "focused": false, "scale": 2.0,
+                     "rect": {"x": 0, "y": 0, "width": 1440, "height": 900}},
+                    {"name": "DP-2", "active": true, "focused": true, "scale": 1.0,
+                     "rect": {"x": 1440, "y": 0, "width": 2560, "height": 1440}},
+                    {"name": "HDMI-A-1", "active": false, "focused": false,
+                     "rect": {"x": 0, "y": 0, "width": 0, "height": 0}}
+                ]"#,
+            ],
+        );
+        let sway = SwayCompositor::with_socket(&path, "Sway");
+
+        let outputs = sway.list_outputs().unwrap();
+        assert_eq!(server.join().unwrap(), [(GET_OUTPUTS, String::new())]);
+        let _ = std::fs::remove_file(&path);
+
+        // Inactive outputs are skipped
+        assert_eq!(
+            outputs,
+            [
+                OutputInfo {
+                    name: "eDP-1".to_string(),
+                    x: 0,
+                    y: 0,
+                    width: 1440,
+                    height: 900,
+                },
+                OutputInfo {
+                    name: "DP-2".to_string(),
+                    x: 1440,
+                    y: 0,
+                    width: 2560,
+                    height: 1440,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_header_rejected() {
+        let mut reply = b"i3-ipX".to_vec();
+        reply.extend_from_slice(&[0; 8]);
+        assert!(read_message(&mut reply.as_slice()).is_err());
+    }
+}
@@ -0,0 +1,88 @@
+//! Headless Wayland compositor shared by the tests of the Wayland clients.
+
+use std::os::unix::net::UnixStream;
+use std::sync::{Arc, mpsc};
+use std::time::Duration;
+use wayland_client::Connection;
+use wayland_server::backend::ClientData;
+use wayland_server::protocol::wl_seat::{self, WlSeat};
+use wayland_server::{Client, DataInit, Dispatch, Display, DisplayHandle, GlobalDispatch, New};
+
+/// A test compositor running on a background thread.
+/// It stops when dropped.
+pub struct TestDisplay {
+    _stop: mpsc::Sender<()>,
+}
+
+/// Run a compositor serving a single client and connect to it.
+///
+/// `setup` creates the globals and returns the compositor state. It runs on
+/// the compositor thread, so the state does not need to be `Send`.
+pub fn start_display<D>(
+    setup: impl FnOnce(&DisplayHandle) -> D + Send + 'static,
+) -> (Connection, TestDisplay)
+where
+    D: 'static,
+{
+    let (client_socket, server_socket) = UnixStream::pair().unwrap();
+    let (stop, stop_rx) = mpsc::channel::<()>();
+
+    std::thread::spawn(move || {
+        let mut display = Display::<D>::new().unwrap();
+        let mut state = setup(&display.handle());
+        display
+            .handle()
+            .insert_client(server_socket, Arc::new(TestClient))
+            .unwrap();
+
+        while let Err(mpsc::TryRecvError::Empty) = stop_rx.try_recv() {
+            display.dispatch_clients(&mut state).unwrap();
+            display.flush_clients().unwrap();
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    });
+
+    let connection = Connection::from_socket(client_socket).unwrap();
+    (connection, TestDisplay { _stop: stop })
+}
+
+struct TestClient;
+impl ClientData for TestClient {}
+
+/// Seat global without any capabilities.
+///
+/// Test compositors delegate to it with `wayland_server::delegate_global_dispatch!`
+/// and `wayland_server::delegate_dispatch!`.
+pub struct TestSeat;
+
+impl<D> GlobalDispatch<WlSeat, (), D> for TestSeat
+where
+    D: GlobalDispatch<WlSeat, ()> + Dispatch<WlSeat, ()> + 'static,
+{
+    fn bind(
+        _: &mut D,
+        _: &DisplayHandle,
+        _: &Client,
+        resource: New<WlSeat>,
+        _: &(),
+        data_init: &mut DataInit<'_, D>,
+    ) {
+        data_init.init(resource, ());
+    }
+}
+
+impl<D> Dispatch<WlSeat, (), D> for TestSeat
+where
+    D: Dispatch<WlSeat, ()>,
+{
+    fn request(
+        _: &mut D,
+        _: &Client,
+        _: &WlSeat,
+        _: wl_seat::Request,
+        _: &(),
+        _: &DisplayHandle,
+        _: &mut DataInit<'_, D>,
+    ) {
+    }
+}
@@ -0,0 +1,37 @@
+//! Fake IPC socket shared by the tests of the socket-based backends.
+
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::thread::JoinHandle;
+
+/// Serve `replies` in order on a local socket, one connection each.
+///
+/// `exchange` frames a connection the way the backend does: it reads the
+/// request, writes the reply and returns the request. The thread returns
+/// the requests it received.
+pub fn fake_server<R, F>(
+    name: &str,
+    replies: Vec<&'static str>,
+    exchange: F,
+) -> (PathBuf, JoinHandle<Vec<R>>)
+where
+    R: Send + 'static,
+    F: Fn(&mut UnixStream, &str) -> R + Send + 'static,
+{
+    let path =
+        std::env::temp_dir().join(format!("zlaunch-test-{}-{}.sock", std::process::id(), name));
+    let _ = std::fs::remove_file(&path);
+    let listener = UnixListener::bind(&path).unwrap();
+
+    let handle = std::thread::spawn(move || {
+        replies
+            .into_iter()
+            .map(|reply| {
+                let (mut stream, _) = listener.accept().unwrap();
+                exchange(&mut stream, reply)
+            })
+            .collect()
+    });
+
+    (path, handle)
+}
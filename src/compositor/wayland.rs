@@ -0,0 +1,95 @@
+//! Wayland client setup shared by the protocols bound directly.
+//!
+//! Each client gets its own connection, binds a protocol manager and a seat
+//! from the registry, then hands its event queue to a background thread that
+//! dispatches events until the connection closes.
+
+use anyhow::{Context, Result};
+use tracing::debug;
+use wayland_client::globals::{GlobalList, GlobalListContents, registry_queue_init};
+use wayland_client::protocol::{
+    wl_registry::{self, WlRegistry},
+    wl_seat::{self, WlSeat},
+};
+use wayland_client::{Connection, Dispatch, EventQueue, QueueHandle};
+
+/// Connect to the Wayland display and set up a client on it.
+///
+/// Returns None if there is no Wayland display or `from_connection` fails,
+/// typically because the compositor does not support `protocol`.
+pub(crate) fn connect<T>(
+    protocol: &str,
+    from_connection: impl FnOnce(Connection) -> Result<T>,
+) -> Option<T> {
+    let connection = Connection::connect_to_env().ok()?;
+    match from_connection(connection) {
+        Ok(client) => Some(client),
+        Err(e) => {
+            debug!(%e, "{} not available", protocol);
+            None
+        }
+    }
+}
+
+/// Read the globals advertised on `connection`.
+pub(crate) fn init_registry<S>(connection: &Connection) -> Result<(GlobalList, EventQueue<S>)>
+where
+    S: Dispatch<WlRegistry, GlobalListContents> + 'static,
+{
+    registry_queue_init(connection).context("Failed to read Wayland globals")
+}
+
+/// Bind the seat that activation and selection requests refer to.
+pub(crate) fn bind_seat<S>(globals: &GlobalList, qh: &QueueHandle<S>) -> Result<WlSeat>
+where
+    S: Dispatch<WlSeat, ()> + 'static,
+{
+    globals
+        .bind(qh, 1..=1, ())
+        .context("Compositor has no seat")
+}
+
+/// Dispatch events into `state` on a background thread, which then owns it.
+/// Stops once the connection closes, logging `closed`.
+pub(crate) fn dispatch_in_background<S: Send + 'static>(
+    mut queue: EventQueue<S>,
+    mut state: S,
+    closed: &'static str,
+) {
+    std::thread::spawn(move || {
+        loop {
+            if let Err(e) = queue.blocking_dispatch(&mut state) {
+                debug!(%e, "Wayland connection closed, {}", closed);
+                break;
+            }
+        }
+    });
+}
+
+/// Ignores registry and seat events, which no client here needs.
+///
+/// Client states delegate to it with `wayland_client::delegate_dispatch!`.
+pub(crate) struct IgnoreEvents;
+
+impl<S> Dispatch<WlRegistry, GlobalListContents, S> for IgnoreEvents
+where
+    S: Dispatch<WlRegistry, GlobalListContents>,
+{
+    fn event(
+        _: &mut S,
+        _: &WlRegistry,
+        _: wl_registry::Event,
+        _: &GlobalListContents,
+        _: &Connection,
+        _: &QueueHandle<S>,
+    ) {
+    }
+}
+
+impl<S> Dispatch<WlSeat, (), S> for IgnoreEvents
+where
+    S: Dispatch<WlSeat, ()>,
+{
+    fn event(_: &mut S, _: &WlSeat, _: wl_seat::Event, _: &(), _: &Connection, _: &QueueHandle<S>) {
+    }
+}
@@ -0,0 +1,425 @@
+//! Generic Wayland implementation using the wlr-foreign-toplevel-management protocol.
+//!
+//! Used for wlroots compositors without a dedicated IPC, such as river,
+//! labwc and wayfire. The protocol has no notion of workspaces, so every
+//! window is reported on workspace 0.
+//!
+//! A background thread keeps dispatching protocol events so the toplevel
+//! list stays up to date between calls.
+
+use super::wayland::{self, IgnoreEvents};
+use super::{Compositor, WindowInfo};
+use anyhow::{Context, Result, anyhow};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use wayland_client::globals::GlobalListContents;
+use wayland_client::protocol::{wl_registry::WlRegistry, wl_seat::WlSeat};
+use wayland_client::{
+    Connection, Dispatch, Proxy, QueueHandle, delegate_dispatch, event_created_child,
+};
+use wayland_protocols_wlr::foreign_toplevel::v1::client::{
+    zwlr_foreign_toplevel_handle_v1::{self, ZwlrForeignToplevelHandleV1},
+    zwlr_foreign_toplevel_manager_v1::{self, ZwlrForeignToplevelManagerV1},
+};
+
+/// Value of the `activated` entry in a toplevel's state array.
+const STATE_ACTIVATED: u32 = 2;
+
+/// Toplevels by protocol object id.
+type Toplevels = Arc<Mutex<BTreeMap<u32, Toplevel>>>;
+
+/// Wayland compositor client using wlr-foreign-toplevel-management.
+pub struct WlrCompositor {
+    connection: Connection,
+    seat: WlSeat,
+    toplevels: Toplevels,
+}
+
+impl WlrCompositor {
+    /// Create a new foreign toplevel client.
+    ///
+    /// Returns None if there is no Wayland display or the compositor
+    /// does not support the protocol.
+    pub fn new() -> Option<Self> {
+        wayland::connect("wlr-foreign-toplevel-management", Self::from_connection)
+    }
+
+    /// Bind the toplevel manager and start dispatching its events.
+    fn from_connection(connection: Connection) -> Result<Self> {
+        let (globals, mut queue) = wayland::init_registry::<ToplevelState>(&connection)?;
+        let qh = queue.handle();
+
+        let _manager: ZwlrForeignToplevelManagerV1 = globals
+            .bind(&qh, 1..=3, ())
+            .context("Compositor does not support wlr-foreign-toplevel-management")?;
+        let seat = wayland::bind_seat(&globals, &qh)?;
+
+        // Wait for the initial toplevels so the first listing is complete
+        let toplevels = Toplevels::default();
+        let mut state = ToplevelState {
+            toplevels: toplevels.clone(),
+        };
+        queue
+            .roundtrip(&mut state)
+            .context("Failed to receive Wayland toplevels")?;
+
+        wayland::dispatch_in_background(queue, state, "toplevel list no longer updated");
+
+        Ok(Self {
+            connection,
+            seat,
+            toplevels,
+        })
+    }
+}
+
+impl Compositor for WlrCompositor {
+    fn list_windows(&self) -> Result<Vec<WindowInfo>> {
+        let toplevels = self.toplevels.lock().unwrap_or_else(|e| e.into_inner());
+
+        Ok(toplevels
+            .iter()
+            .filter_map(|(id, toplevel)| toplevel.current.as_ref()?.to_window_info(*id))
+            .collect())
+    }
+
+    fn focus_window(&self, window_id: &str) -> Result<()> {
+        let id: u32 = window_id
+            .parse()
+            .with_context(|| format!("Invalid toplevel id: {}", window_id))?;
+
+        let handle = self
+            .toplevels
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&id)
+            .map(|toplevel| toplevel.handle.clone())
+            .ok_or_else(|| anyhow!("No toplevel with id {}", window_id))?;
+
+        handle.activate(&self.seat);
+        self.connection
+            .flush()
+            .context("Failed to send activate request")?;
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "wlr-foreign-toplevel"
+    }
+}
+
+/// A toplevel and its double-buffered properties.
+struct Toplevel {
+    handle: ZwlrForeignToplevelHandleV1,
+    /// Properties applied by the last `done` event, None before the first
+    current: Option<ToplevelInfo>,
+    /// Properties received since the last `done` event
+    pending: ToplevelInfo,
+}
+
+#[derive(Debug, Clone, Default)]
+struct ToplevelInfo {
+    title: String,
+    app_id: String,
+    activated: bool,
+}
+
+impl ToplevelInfo {
+    /// Convert to WindowInfo, skipping zlaunch itself.
+    fn to_window_info(&self, id: u32) -> Option<WindowInfo> {
+        if self.app_id.is_empty() || self.app_id.to_lowercase() == "zlaunch" {
+            return None;
+        }
+
+        Some(WindowInfo {
+            address: id.to_string(),
+            title: if self.title.is_empty() {
+                self.app_id.clone()
+            } else {
+                self.title.clone()
+            },
+            class: self.app_id.clone(),
+            workspace: 0,
+            focused: self.activated,
+            scratchpad: false,
+        })
+    }
+}
+
+/// Keeps the shared toplevel list up to date from protocol events.
+struct ToplevelState {
+    toplevels: Toplevels,
+}
+
+delegate_dispatch!(ToplevelState: [WlRegistry: GlobalListContents] => IgnoreEvents);
+delegate_dispatch!(ToplevelState: [WlSeat: ()] => IgnoreEvents);
+
+impl Dispatch<ZwlrForeignToplevelManagerV1, ()> for ToplevelState {
+    fn event(
+        state: &mut Self,
+        _: &ZwlrForeignToplevelManagerV1,
+        event: zwlr_foreign_toplevel_manager_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        if let zwlr_foreign_toplevel_manager_v1::Event::Toplevel { toplevel } = event {
+            let id = toplevel.id().protocol_id();
+            state.lock().insert(
+                id,
+                Toplevel {
+                    handle: toplevel,
+                    current: None,
+                    pending: ToplevelInfo::default(),
+                },
+            );
+        }
+    }
+
+    event_created_child!(ToplevelState, ZwlrForeignToplevelManagerV1, [
+        zwlr_foreign_toplevel_manager_v1::EVT_TOPLEVEL_OPCODE => (ZwlrForeignToplevelHandleV1, ()),
+    ]);
+}
+
+impl Dispatch<ZwlrForeignToplevelHandleV1, ()> for ToplevelState {
+    fn event(
+        state: &mut Self,
+        handle: &ZwlrForeignToplevelHandleV1,
+        event: zwlr_foreign_toplevel_handle_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        use zwlr_foreign_toplevel_handle_v1::Event;
+
+        let id = handle.id().protocol_id();
+        let mut toplevels = state.lock();
+
+        if let Event::Closed = event {
+            toplevels.remove(&id);
+            handle.destroy();
+            return;
+        }
+
+        let Some(toplevel) = toplevels.get_mut(&id) else {
+            return;
+        };
+        match event {
+            Event::Title { title } => toplevel.pending.title = title,
+            Event::AppId { app_id } => toplevel.pending.app_id = app_id,
+            Event::State { state } => {
+                toplevel.pending.activated = state
+                    .chunks_exact(4)
+                    .filter_map(|value| value.try_into().ok())
+                    .any(|value| u32::from_ne_bytes(value) == STATE_ACTIVATED);
+            }
+            Event::Done => toplevel.current = Some(toplevel.pending.clone()),
+            _ => {}
+        }
+    }
+}
+
+impl ToplevelState {
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<u32, Toplevel>> {
+        self.toplevels.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compositor::test_display::{TestDisplay, TestSeat, start_display};
+    use std::sync::mpsc;
+    use std::time::{Duration, Instant};
+    use wayland_protocols_wlr::foreign_toplevel::v1::server::{
+        zwlr_foreign_toplevel_handle_v1 as server_handle,
+        zwlr_foreign_toplevel_manager_v1 as server_manager,
+    };
+    use wayland_server::protocol::wl_seat;
+    use wayland_server::{Client, DataInit, DisplayHandle, GlobalDispatch, New, Resource};
+
+    /// A headless compositor that only implements the foreign toplevel manager.
+    struct TestCompositor {
+        /// Title and app id of each window
+        windows: Vec<(&'static str, &'static str)>,
+        /// Index of the active window
+        active: usize,
+        handles: Vec<server_handle::ZwlrForeignToplevelHandleV1>,
+        /// Reports the index of each window activated by the client
+        activations: mpsc::Sender<usize>,
+    }
+
+    impl TestCompositor {
+        /// Send the current state of a window.
+        fn send_state(&self, index: usize) {
+            let handle = &self.handles[index];
+            let state = if index == self.active {
+                STATE_ACTIVATED.to_ne_bytes().to_vec()
+            } else {
+                Vec::new()
+            };
+            handle.state(state);
+            handle.done();
+        }
+    }
+
+    impl GlobalDispatch<server_manager::ZwlrForeignToplevelManagerV1, ()> for TestCompositor {
+        fn bind(
+            state: &mut Self,
+            dh: &DisplayHandle,
+            client: &Client,
+            resource: New<server_manager::ZwlrForeignToplevelManagerV1>,
+            _: &(),
+            data_init: &mut DataInit<'_, Self>,
+        ) {
+            let manager = data_init.init(resource, ());
+            for (index, (title, app_id)) in state.windows.clone().into_iter().enumerate() {
+                let handle = client
+                    .create_resource::<server_handle::ZwlrForeignToplevelHandleV1, usize, Self>(
+                        dh,
+                        manager.version(),
+                        index,
+                    )
+                    .unwrap();
+                manager.toplevel(&handle);
+                handle.title(title.to_string());
+                handle.app_id(app_id.to_string());
+                state.handles.push(handle);
+                state.send_state(index);
+            }
+        }
+    }
+
+    impl wayland_server::Dispatch<server_manager::ZwlrForeignToplevelManagerV1, ()> for TestCompositor {
+        fn request(
+            _: &mut Self,
+            _: &Client,
+            _: &server_manager::ZwlrForeignToplevelManagerV1,
+            _: server_manager::Request,
+            _: &(),
+            _: &DisplayHandle,
+            _: &mut DataInit<'_, Self>,
+        ) {
+        }
+    }
+
+    impl wayland_server::Dispatch<server_handle::ZwlrForeignToplevelHandleV1, usize>
+        for TestCompositor
+    {
+        fn request(
+            state: &mut Self,
+            _: &Client,
+            _: &server_handle::ZwlrForeignToplevelHandleV1,
+            request: server_handle::Request,
+            index: &usize,
+            _: &DisplayHandle,
+            _: &mut DataInit<'_, Self>,
+        ) {
+            if let server_handle::Request::Activate { .. } = request {
+                let previous = std::mem::replace(&mut state.active, *index);
+                state.send_state(previous);
+                state.send_state(*index);
+                let _ = state.activations.send(*index);
+            }
+        }
+    }
+
+    wayland_server::delegate_global_dispatch!(TestCompositor: [wl_seat::WlSeat: ()] => TestSeat);
+    wayland_server::delegate_dispatch!(TestCompositor: [wl_seat::WlSeat: ()] => TestSeat);
+
+    /// A running test compositor.
+    struct Running {
+        /// Index of each window activated by the client
+        activations: mpsc::Receiver<usize>,
+        _display: TestDisplay,
+    }
+
+    /// Run a test compositor and connect to it.
+    /// Without `globals`, it advertises neither the manager nor a seat.
+    fn start_compositor(
+        windows: &[(&'static str, &'static str)],
+        globals: bool,
+    ) -> (Connection, Running) {
+        let (activations, activations_rx) = mpsc::channel();
+        let windows = windows.to_vec();
+
+        let (connection, display) = start_display(move |dh| {
+            if globals {
+                dh.create_global::<TestCompositor, server_manager::ZwlrForeignToplevelManagerV1, ()>(
+                    3,
+                    (),
+                );
+                dh.create_global::<TestCompositor, wl_seat::WlSeat, ()>(1, ());
+            }
+            TestCompositor {
+                windows,
+                active: 0,
+                handles: Vec::new(),
+                activations,
+            }
+        });
+
+        let running = Running {
+            activations: activations_rx,
+            _display: display,
+        };
+        (connection, running)
+    }
+
+    const WINDOWS: [(&str, &str); 3] = [
+        ("~/src/zlaunch", "foot"),
+        ("", "org.gnome.Nautilus"),
+        ("zlaunch", "zlaunch"),
+    ];
+
+    #[test]
+    fn test_list_windows() {
+        let (connection, _compositor) = start_compositor(&WINDOWS, true);
+        let wlr = WlrCompositor::from_connection(connection).unwrap();
+
+        let windows = wlr.list_windows().unwrap();
+        let summary: Vec<(&str, &str, bool)> = windows
+            .iter()
+            .map(|w| (w.title.as_str(), w.class.as_str(), w.focused))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("~/src/zlaunch", "foot", true),
+                // Untitled windows are named after their app id
+                ("org.gnome.Nautilus", "org.gnome.Nautilus", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_focus_window() {
+        let (connection, compositor) = start_compositor(&WINDOWS, true);
+        let wlr = WlrCompositor::from_connection(connection).unwrap();
+
+        let nautilus = wlr.list_windows().unwrap()[1].address.clone();
+        wlr.focus_window(&nautilus).unwrap();
+        let activated = compositor.activations.recv_timeout(Duration::from_secs(5));
+        assert_eq!(activated, Ok(1));
+
+        // The list follows the compositor's state changes
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            let windows = wlr.list_windows().unwrap();
+            if windows[1].focused && !windows[0].focused {
+                break;
+            }
+            assert!(Instant::now() < deadline, "activation was not reported");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        assert!(wlr.focus_window("4242").is_err());
+        assert!(wlr.focus_window("foot").is_err());
+    }
+
+    #[test]
+    fn test_missing_protocol() {
+        let (connection, _compositor) = start_compositor(&WINDOWS, false);
+        assert!(WlrCompositor::from_connection(connection).is_err());
+    }
+}
@@ -0,0 +1,145 @@
+//! Calculator constants, functions and number formats from the
+//! `[calculator]` table.
+
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+
+/// Calculator settings from the `[calculator]` table.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CalculatorConfig {
+    /// Constants usable in expressions, such as `g = 9.80665`.
+    #[serde(deserialize_with = "deserialize_constants")]
+    pub constants: BTreeMap<String, f64>,
+    /// Functions written as `"name(a, b) = expression"`.
+    pub functions: Vec<CalculatorFunction>,
+    /// Locale for reading and showing numbers, such as `"de_DE"`. Defaults
+    /// to the `LC_NUMERIC` locale.
+    pub locale: Option<String>,
+    /// How results are written to the clipboard.
+    pub clipboard_format: ClipboardFormat,
+}
+
+/// How results are written to the clipboard, as `"plain"`, `"locale"` or
+/// `"display"`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClipboardFormat {
+    /// `1234.5`, whatever the locale.
+    #[default]
+    Plain,
+    /// `1234,5` with the decimal separator of the locale.
+    Locale,
+    /// `1.234,5` as shown in the launcher.
+    Display,
+}
+
+/// A user-defined calculator function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CalculatorFunction {
+    /// Lowercase function name.
+    pub name: String,
+    /// Lowercase parameter names.
+    pub params: Vec<String>,
+    /// Expression evaluated with the parameters bound to the arguments.
+    pub body: String,
+}
+
+impl CalculatorFunction {
+    /// Parse a definition like `hyp(a, b) = sqrt(a^2 + b^2)`.
+    fn parse(definition: &str) -> Result<Self, String> {
+        let invalid = || {
+            format!(
+                "invalid function `{}`, expected \"name(a, b) = expression\"",
+                definition
+            )
+        };
+
+        let (signature, body) = definition.split_once('=').ok_or_else(invalid)?;
+        let (name, params) = signature
+            .trim()
+            .strip_suffix(')')
+            .and_then(|s| s.split_once('('))
+            .ok_or_else(invalid)?;
+
+        let name = name.trim().to_lowercase();
+        let params: Vec<String> = params
+            .split(',')
+            .map(|p| p.trim().to_lowercase())
+            .filter(|p| !p.is_empty())
+            .collect();
+        let body = body.trim();
+
+        if !is_identifier(&name) || !params.iter().all(|p| is_identifier(p)) || body.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            name,
+            params,
+            body: body.to_string(),
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for CalculatorFunction {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let definition = String::deserialize(deserializer)?;
+        Self::parse(&definition).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Deserialize constants, checking that names are identifiers.
+fn deserialize_constants<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BTreeMap<String, f64>, D::Error> {
+    let constants = BTreeMap::<String, f64>::deserialize(deserializer)?;
+    constants
+        .into_iter()
+        .map(|(name, value)| {
+            if is_identifier(&name) {
+                Ok((name.to_lowercase(), value))
+            } else {
+                Err(serde::de::Error::custom(format!(
+                    "invalid constant name `{}`",
+                    name
+                )))
+            }
+        })
+        .collect()
+}
+
+/// Check if a name can be used in expressions: a letter or `_` followed by
+/// letters, digits or `_`.
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_function() {
+        assert_eq!(
+            CalculatorFunction::parse("Hyp(a, B) = sqrt(a^2 + b^2)"),
+            Ok(CalculatorFunction {
+                name: "hyp".to_string(),
+                params: vec!["a".to_string(), "b".to_string()],
+                body: "sqrt(a^2 + b^2)".to_string(),
+            })
+        );
+        assert_eq!(
+            CalculatorFunction::parse("answer() = 42").map(|f| f.params),
+            Ok(vec![])
+        );
+        assert!(CalculatorFunction::parse("tip(x)").is_err());
+        assert!(CalculatorFunction::parse("tip x = x * 0.15").is_err());
+        assert!(CalculatorFunction::parse("2x(x) = x").is_err());
+        assert!(CalculatorFunction::parse("f(x) = ").is_err());
+    }
+}
@@ -0,0 +1,145 @@
+//! Color values accepted in the config file.
+//!
+//! Colors can be written as hex (`#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`)
+//! or as CSS-style `hsl(...)` / `hsla(...)` with the hue in degrees and
+//! saturation/lightness as percentages or fractions.
+
+use serde::{Deserialize, Deserializer};
+
+/// A color in HSLA space with all components normalized to `0.0..=1.0`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Color {
+    pub h: f32,
+    pub s: f32,
+    pub l: f32,
+    pub a: f32,
+}
+
+impl Color {
+    /// Parse a color from its config string representation.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let trimmed = input.trim();
+
+        if let Some(hex) = trimmed.strip_prefix('#') {
+            return parse_hex(hex).ok_or_else(|| format!("invalid hex color `{}`", trimmed));
+        }
+
+        let lower = trimmed.to_ascii_lowercase();
+        if let Some(args) = lower
+            .strip_prefix("hsla(")
+            .or_else(|| lower.strip_prefix("hsl("))
+            .and_then(|rest| rest.strip_suffix(')'))
+        {
+            return parse_hsla_args(args)
+                .ok_or_else(|| format!("invalid hsla color `{}`", trimmed));
+        }
+
+        Err(format!(
+            "invalid color `{}`, expected `#rrggbb[aa]` or `hsla(h, s%, l%, a)`",
+            trimmed
+        ))
+    }
+
+    /// Create a color from 8-bit RGBA components.
+    pub fn from_rgba8(r: u8, g: u8, b: u8, a: u8) -> Self {
+        let r = r as f32 / 255.0;
+        let g = g as f32 / 255.0;
+        let b = b as f32 / 255.0;
+
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+        let delta = max - min;
+
+        let (h, s) = if delta == 0.0 {
+            (0.0, 0.0)
+        } else {
+            let s = delta / (1.0 - (2.0 * l - 1.0).abs());
+            let h = if max == r {
+                ((g - b) / delta).rem_euclid(6.0)
+            } else if max == g {
+                (b - r) / delta + 2.0
+            } else {
+                (r - g) / delta + 4.0
+            };
+            (h / 6.0, s)
+        };
+
+        Self {
+            h,
+            s,
+            l,
+            a: a as f32 / 255.0,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Color::parse(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Parse the digits of a hex color (without the leading `#`).
+fn parse_hex(hex: &str) -> Option<Color> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    // Expand short forms (#rgb, #rgba) to the long form
+    let expanded: String = match hex.len() {
+        3 | 4 => hex.chars().flat_map(|c| [c, c]).collect(),
+        6 | 8 => hex.to_string(),
+        _ => return None,
+    };
+
+    let channel = |i: usize| u8::from_str_radix(&expanded[i..i + 2], 16).ok();
+    let r = channel(0)?;
+    let g = channel(2)?;
+    let b = channel(4)?;
+    let a = if expanded.len() == 8 {
+        channel(6)?
+    } else {
+        255
+    };
+
+    Some(Color::from_rgba8(r, g, b, a))
+}
+
+/// Parse the comma-separated arguments of `hsl(...)` / `hsla(...)`.
+fn parse_hsla_args(args: &str) -> Option<Color> {
+    let parts: Vec<&str> = args.split(',').map(str::trim).collect();
+    if parts.len() != 3 && parts.len() != 4 {
+        return None;
+    }
+
+    let hue: f32 = parts[0]
+        .strip_suffix("deg")
+        .unwrap_or(parts[0])
+        .parse()
+        .ok()?;
+    let s = parse_fraction(parts[1])?;
+    let l = parse_fraction(parts[2])?;
+    let a = match parts.get(3) {
+        Some(alpha) => parse_fraction(alpha)?,
+        None => 1.0,
+    };
+
+    Some(Color {
+        h: hue.rem_euclid(360.0) / 360.0,
+        s,
+        l,
+        a,
+    })
+}
+
+/// Parse a percentage (`40%`) or a fraction (`0.4`) into `0.0..=1.0`.
+fn parse_fraction(value: &str) -> Option<f32> {
+    let parsed = match value.strip_suffix('%') {
+        Some(percent) => percent.trim().parse::<f32>().ok()? / 100.0,
+        None => value.parse::<f32>().ok()?,
+    };
+
+    (0.0..=1.0).contains(&parsed).then_some(parsed)
+}
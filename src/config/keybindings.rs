@@ -0,0 +1,167 @@
+//! Key bindings from the `[keybindings]` table.
+//!
+//! Each launcher action maps to one keystroke (`confirm = "enter"`) or a
+//! list of keystrokes (`select_next = ["down", "ctrl-n"]`). Keystrokes use
+//! GPUI syntax: modifiers joined to the key with `-`.
+
+use serde::{Deserialize, Deserializer};
+
+/// Modifiers understood by GPUI keystroke parsing.
+const MODIFIERS: &[&str] = &[
+    "ctrl",
+    "alt",
+    "shift",
+    "fn",
+    "cmd",
+    "super",
+    "win",
+    "secondary",
+    "platform",
+];
+
+/// One or more keystrokes bound to a single action.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Keys(pub Vec<String>);
+
+impl Keys {
+    fn new(keys: &[&str]) -> Self {
+        Self(keys.iter().map(|k| k.to_string()).collect())
+    }
+
+    /// Iterate over the keystrokes in this binding.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+}
+
+impl<'de> Deserialize<'de> for Keys {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            One(String),
+            Many(Vec<String>),
+        }
+
+        let keys = match Raw::deserialize(deserializer)? {
+            Raw::One(key) => vec![key],
+            Raw::Many(keys) => keys,
+        };
+
+        for key in &keys {
+            validate_keystroke(key).map_err(serde::de::Error::custom)?;
+        }
+
+        Ok(Keys(keys))
+    }
+}
+
+/// Key bindings for every launcher action.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeybindingsConfig {
+    pub select_next: Keys,
+    pub select_prev: Keys,
+    pub select_tab: Keys,
+    pub select_tab_prev: Keys,
+    pub confirm: Keys,
+    pub cancel: Keys,
+    pub go_back: Keys,
+    /// Open the secondary actions of the selected application.
+    pub show_actions: Keys,
+    /// Copy an integer calculator result in decimal.
+    pub copy_decimal: Keys,
+    /// Copy an integer calculator result in hex.
+    pub copy_hex: Keys,
+    /// Copy an integer calculator result in binary.
+    pub copy_binary: Keys,
+    /// Pin or unpin the selected clipboard entry.
+    pub toggle_pin: Keys,
+    /// Delete the selected clipboard entry.
+    pub delete_entry: Keys,
+}
+
+impl Default for KeybindingsConfig {
+    fn default() -> Self {
+        Self {
+            select_next: Keys::new(&["down"]),
+            select_prev: Keys::new(&["up"]),
+            select_tab: Keys::new(&["tab"]),
+            select_tab_prev: Keys::new(&["shift-tab"]),
+            confirm: Keys::new(&["enter"]),
+            cancel: Keys::new(&["escape"]),
+            go_back: Keys::new(&["backspace"]),
+            show_actions: Keys::new(&["shift-enter"]),
+            copy_decimal: Keys::new(&["alt-d"]),
+            copy_hex: Keys::new(&["alt-h"]),
+            copy_binary: Keys::new(&["alt-b"]),
+            toggle_pin: Keys::new(&["alt-p"]),
+            delete_entry: Keys::new(&["shift-delete"]),
+        }
+    }
+}
+
+impl KeybindingsConfig {
+    /// Iterate over every keystroke bound to any action.
+    pub fn all_keys(&self) -> impl Iterator<Item = &str> {
+        [
+            &self.select_next,
+            &self.select_prev,
+            &self.select_tab,
+            &self.select_tab_prev,
+            &self.confirm,
+            &self.cancel,
+            &self.go_back,
+            &self.show_actions,
+            &self.copy_decimal,
+            &self.copy_hex,
+            &self.copy_binary,
+            &self.toggle_pin,
+            &self.delete_entry,
+        ]
+        .into_iter()
+        .flat_map(Keys::iter)
+    }
+}
+
+/// Check that a keystroke sequence is well-formed.
+///
+/// A sequence is one or more space-separated keystrokes, each made of
+/// zero or more known modifiers followed by a key, joined with `-`.
+fn validate_keystroke(sequence: &str) -> Result<(), String> {
+    if sequence.trim().is_empty() {
+        return Err("empty keystroke".to_string());
+    }
+
+    for keystroke in sequence.split_whitespace() {
+        if keystroke == "-" {
+            continue;
+        }
+
+        // A trailing `-` is the minus key itself (e.g. "ctrl--")
+        let (modifiers, key) = match keystroke.strip_suffix("--") {
+            Some(prefix) => (prefix, "-"),
+            None => match keystroke.rsplit_once('-') {
+                Some((prefix, key)) => (prefix, key),
+                None => ("", keystroke),
+            },
+        };
+
+        if key.is_empty() {
+            return Err(format!("keystroke `{}` has no key", keystroke));
+        }
+
+        for modifier in modifiers.split('-').filter(|m| !m.is_empty()) {
+            if !MODIFIERS.contains(&modifier) {
+                return Err(format!(
+                    "unknown modifier `{}` in keystroke `{}`, expected one of: {}",
+                    modifier,
+                    keystroke,
+                    MODIFIERS.join(", ")
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
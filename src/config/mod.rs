@@ -0,0 +1,566 @@
+//! User configuration loaded from `~/.config/zlaunch/config.toml`.
+//!
+//! The daemon parses the file at startup into a typed [`Config`] and
+//! reloads it whenever the file changes.
+//! Missing files and missing keys fall back to the built-in defaults;
+//! unknown keys and invalid values are reported as [`ConfigError`]s with
+//! the line and column of the offending value.
+
+mod calculator;
+mod color;
+mod keybindings;
+mod theme;
+mod window;
+
+pub use calculator::{CalculatorConfig, CalculatorFunction, ClipboardFormat};
+pub use color::Color;
+pub use keybindings::{KeybindingsConfig, Keys};
+pub use theme::{Size, ThemeConfig};
+pub use window::{OutputChoice, WindowConfig, WindowPosition};
+
+use serde::Deserialize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+/// Top-level configuration file contents.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Overrides for the launcher theme.
+    pub theme: ThemeConfig,
+    /// Key bindings for launcher actions.
+    pub keybindings: KeybindingsConfig,
+    /// Output and position of the launcher.
+    pub window: WindowConfig,
+    /// General launcher behavior.
+    pub behavior: BehaviorConfig,
+    /// Calculator constants, functions and number formats.
+    pub calculator: CalculatorConfig,
+    /// Launch history used for ranking.
+    pub history: HistoryConfig,
+    /// Clipboard history.
+    pub clipboard: ClipboardConfig,
+    /// Built-in system actions (shutdown, reboot, ...).
+    pub actions: ActionsConfig,
+    /// User-defined commands from `[[commands]]` entries.
+    pub commands: Vec<CommandConfig>,
+}
+
+/// General launcher behavior from the `[behavior]` table.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BehaviorConfig {
+    /// Placeholder text shown in the empty search input.
+    pub search_placeholder: String,
+    /// Whether the built-in Emojis submenu is listed.
+    pub show_emojis: bool,
+}
+
+impl Default for BehaviorConfig {
+    fn default() -> Self {
+        Self {
+            search_placeholder: "Search applications...".to_string(),
+            show_emojis: true,
+        }
+    }
+}
+
+/// Launch history settings from the `[history]` table.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HistoryConfig {
+    /// Whether confirmed items are recorded and ranked by frecency.
+    pub enabled: bool,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// Clipboard history settings from the `[clipboard]` table.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClipboardConfig {
+    /// Whether the daemon records the clipboard and lists the Clipboard
+    /// submenu. Off by default, as the history is persisted to disk.
+    pub enabled: bool,
+    /// Number of entries kept, not counting pinned ones.
+    pub max_entries: usize,
+}
+
+impl Default for ClipboardConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_entries: 200,
+        }
+    }
+}
+
+/// System actions from the `[actions]` table.
+///
+/// Each command is run through the shell (`sh -c` on Unix, `cmd /C` on
+/// Windows). Setting a command to an empty string hides that action.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ActionsConfig {
+    /// Whether the system actions are listed at all.
+    pub enabled: bool,
+    /// Whether shutdown, reboot and logout must be confirmed by pressing
+    /// Enter a second time.
+    pub confirm: bool,
+    pub shutdown: String,
+    pub reboot: String,
+    pub suspend: String,
+    pub lock: String,
+    pub logout: String,
+}
+
+#[cfg(unix)]
+impl Default for ActionsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            confirm: true,
+            shutdown: "systemctl poweroff".to_string(),
+            reboot: "systemctl reboot".to_string(),
+            suspend: "systemctl suspend".to_string(),
+            lock: "loginctl lock-session".to_string(),
+            logout: "loginctl terminate-session \"$XDG_SESSION_ID\"".to_string(),
+        }
+    }
+}
+
+#[cfg(windows)]
+impl Default for ActionsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            confirm: true,
+            shutdown: "shutdown /s /t 0".to_string(),
+            reboot: "shutdown /r /t 0".to_string(),
+            suspend: "rundll32.exe powrprof.dll,SetSuspendState 0,1,0".to_string(),
+            lock: "rundll32.exe user32.dll,LockWorkStation".to_string(),
+            logout: "shutdown /l".to_string(),
+        }
+    }
+}
+
+/// A user-defined command from a `[[commands]]` entry.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "RawCommandConfig")]
+pub struct CommandConfig {
+    /// Name shown and searched in the launcher.
+    pub name: String,
+    pub description: Option<String>,
+    /// Icon name (e.g. "lock") or an emoji.
+    pub icon: Option<String>,
+    /// Shell command to run.
+    pub command: String,
+    /// Run the command in a terminal emulator.
+    pub terminal: bool,
+    /// Capture the command's output and show it in the launcher instead of
+    /// running it in the background. Cannot be combined with `terminal`.
+    pub show_output: bool,
+}
+
+/// A `[[commands]]` entry as written, before it is validated.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawCommandConfig {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    icon: Option<String>,
+    command: String,
+    #[serde(default)]
+    terminal: bool,
+    #[serde(default)]
+    show_output: bool,
+}
+
+impl TryFrom<RawCommandConfig> for CommandConfig {
+    type Error = String;
+
+    fn try_from(raw: RawCommandConfig) -> Result<Self, Self::Error> {
+        if raw.terminal && raw.show_output {
+            return Err(format!(
+                "command \"{}\" cannot set both terminal and show_output",
+                raw.name
+            ));
+        }
+
+        Ok(Self {
+            name: raw.name,
+            description: raw.description,
+            icon: raw.icon,
+            command: raw.command,
+            terminal: raw.terminal,
+            show_output: raw.show_output,
+        })
+    }
+}
+
+/// An error encountered while loading the config file.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The file exists but could not be read.
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// The file could not be parsed or contains invalid values.
+    Parse {
+        path: PathBuf,
+        /// 1-based line of the offending value, if known.
+        line: Option<usize>,
+        /// 1-based column of the offending value, if known.
+        column: Option<usize>,
+        message: String,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, source } => {
+                write!(f, "failed to read {}: {}", path.display(), source)
+            }
+            Self::Parse {
+                path,
+                line: Some(line),
+                column: Some(column),
+                message,
+            } => write!(f, "{}:{}:{}: {}", path.display(), line, column, message),
+            Self::Parse { path, message, .. } => write!(f, "{}: {}", path.display(), message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            Self::Parse { .. } => None,
+        }
+    }
+}
+
+/// Get the path of the config file.
+///
+/// Uses `$XDG_CONFIG_HOME/zlaunch/config.toml`, falling back to
+/// `~/.config/zlaunch/config.toml`. Returns `None` if neither directory is
+/// known, in which case the defaults are used.
+pub fn config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("zlaunch").join("config.toml"))
+}
+
+/// Load the config file from the default location.
+///
+/// A missing file is not an error and yields the default config.
+pub fn load_config() -> Result<Config, ConfigError> {
+    match config_path() {
+        Some(path) => load_config_from(&path),
+        None => Ok(Config::default()),
+    }
+}
+
+/// Load the config file from the given path.
+pub fn load_config_from(path: &Path) -> Result<Config, ConfigError> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => parse_config(path, &content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+        Err(source) => Err(ConfigError::Io {
+            path: path.to_path_buf(),
+            source,
+        }),
+    }
+}
+
+/// Parse config file contents. `path` is only used for diagnostics.
+pub fn parse_config(path: &Path, content: &str) -> Result<Config, ConfigError> {
+    toml::from_str(content).map_err(|e| {
+        let (line, column) = match e.span() {
+            Some(span) => {
+                let (line, column) = line_column(content, span.start);
+                (Some(line), Some(column))
+            }
+            None => (None, None),
+        };
+
+        ConfigError::Parse {
+            path: path.to_path_buf(),
+            line,
+            column,
+            message: e.message().to_string(),
+        }
+    })
+}
+
+/// Convert a byte offset into a 1-based line and column.
+fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+lazy_static::lazy_static! {
+    /// Global config instance, replaced by the daemon on startup and reload.
+    static ref CONFIG: RwLock<Arc<Config>> = RwLock::new(Arc::new(Config::default()));
+}
+
+/// Install a newly loaded config.
+///
+/// Readers that already hold the previous config keep it until they drop it.
+pub fn set_config(config: Config) {
+    *CONFIG.write().unwrap() = Arc::new(config);
+}
+
+/// Get the current global config, or the defaults if none was installed.
+pub fn config() -> Arc<Config> {
+    Arc::clone(&CONFIG.read().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> Result<Config, ConfigError> {
+        parse_config(Path::new("config.toml"), content)
+    }
+
+    #[test]
+    fn test_empty_config_uses_defaults() {
+        let config = parse("").unwrap();
+        assert!(config.behavior.show_emojis);
+        assert!(config.history.enabled);
+        assert!(!config.clipboard.enabled);
+        assert_eq!(config.behavior.search_placeholder, "Search applications...");
+        assert_eq!(config.keybindings, KeybindingsConfig::default());
+        assert!(config.theme.window_width.is_none());
+        assert_eq!(config.window, WindowConfig::default());
+    }
+
+    #[test]
+    fn test_full_config() {
+        let config = parse(
+            r##"
+            [theme]
+            window_width = 720
+            window_height = "480px"
+            item_border_radius = 4.5
+            window_background = "#101010b3"
+            item_title_color = "hsla(210, 60%, 50%, 0.9)"
+            emoji_columns = 10
+
+            [window]
+            output = "DP-1"
+            position = { x = 40, y = "20px" }
+
+            [keybindings]
+            select_next = ["down", "ctrl-n"]
+            confirm = "enter"
+
+            [behavior]
+            search_placeholder = "Run..."
+            show_emojis = false
+
+            [calculator]
+            constants = { g = 9.80665 }
+            functions = ["hyp(a, b) = sqrt(a^2 + b^2)"]
+            locale = "de_DE.UTF-8"
+            clipboard_format = "display"
+
+            [history]
+            enabled = false
+
+            [clipboard]
+            enabled = true
+            max_entries = 50
+
+            [actions]
+            confirm = false
+            lock = "swaylock -f"
+            suspend = ""
+
+            [[commands]]
+            name = "VPN up"
+            command = "nmcli connection up work-vpn"
+
+            [[commands]]
+            name = "Public IP"
+            description = "Show the public IP address"
+            icon = "🌐"
+            command = "curl -s https://ifconfig.me"
+            show_output = true
+            "##,
+        )
+        .unwrap();
+
+        assert_eq!(config.theme.window_width, Some(Size(720.0)));
+        assert_eq!(config.theme.window_height, Some(Size(480.0)));
+        assert_eq!(config.theme.item_border_radius, Some(Size(4.5)));
+        assert_eq!(config.theme.emoji_columns, Some(10));
+
+        assert_eq!(
+            config.window.output,
+            OutputChoice::Named("DP-1".to_string())
+        );
+        assert_eq!(
+            config.window.position,
+            WindowPosition::Offset {
+                x: Size(40.0),
+                y: Size(20.0)
+            }
+        );
+
+        let bg = config.theme.window_background.unwrap();
+        assert!((bg.l - 16.0 / 255.0).abs() < 1e-4);
+        assert!((bg.a - 0.702).abs() < 1e-3);
+
+        let title = config.theme.item_title_color.unwrap();
+        assert!((title.h - 210.0 / 360.0).abs() < 1e-4);
+        assert!((title.s - 0.6).abs() < 1e-4);
+
+        assert_eq!(
+            config.keybindings.select_next.iter().collect::<Vec<_>>(),
+            vec!["down", "ctrl-n"]
+        );
+        assert_eq!(
+            config.keybindings.cancel.iter().collect::<Vec<_>>(),
+            vec!["escape"]
+        );
+        assert_eq!(config.behavior.search_placeholder, "Run...");
+        assert!(!config.behavior.show_emojis);
+        assert_eq!(config.calculator.constants.get("g"), Some(&9.80665));
+        assert_eq!(config.calculator.functions[0].params, ["a", "b"]);
+        assert_eq!(config.calculator.locale.as_deref(), Some("de_DE.UTF-8"));
+        assert_eq!(config.calculator.clipboard_format, ClipboardFormat::Display);
+        assert!(!config.history.enabled);
+        assert!(config.clipboard.enabled);
+        assert_eq!(config.clipboard.max_entries, 50);
+        assert!(config.actions.enabled);
+        assert!(!config.actions.confirm);
+        assert_eq!(config.actions.lock, "swaylock -f");
+        assert!(config.actions.suspend.is_empty());
+        assert_eq!(config.actions.reboot, ActionsConfig::default().reboot);
+
+        assert_eq!(config.commands.len(), 2);
+        assert_eq!(config.commands[0].name, "VPN up");
+        assert!(config.commands[0].description.is_none());
+        assert!(!config.commands[0].terminal);
+        assert!(!config.commands[0].show_output);
+        assert_eq!(config.commands[1].icon.as_deref(), Some("🌐"));
+        assert!(config.commands[1].show_output);
+    }
+
+    #[test]
+    fn test_hex_colors() {
+        let white = Color::parse("#fff").unwrap();
+        assert_eq!((white.l, white.a), (1.0, 1.0));
+
+        let red = Color::parse("#ff000080").unwrap();
+        assert_eq!((red.h, red.s, red.l), (0.0, 1.0, 0.5));
+        assert!((red.a - 128.0 / 255.0).abs() < 1e-6);
+
+        assert!(Color::parse("#ggg").is_err());
+        assert!(Color::parse("#12345").is_err());
+        assert!(Color::parse("red").is_err());
+    }
+
+    #[test]
+    fn test_window_position() {
+        let output = (1920.0, 1080.0);
+        let panel = (600.0, 400.0);
+        assert_eq!(WindowPosition::Center.origin(output, panel), (660.0, 340.0));
+        assert_eq!(WindowPosition::Top.origin(output, panel), (660.0, 160.0));
+
+        // Panels are kept on the output
+        let offset = WindowPosition::Offset {
+            x: Size(1800.0),
+            y: Size(10.0),
+        };
+        assert_eq!(offset.origin(output, panel), (1320.0, 10.0));
+        assert_eq!(
+            WindowPosition::Center.origin((500.0, 300.0), panel),
+            (0.0, 0.0)
+        );
+
+        let config = parse("[window]\noutput = \"primary\"\nposition = \"top\"\n").unwrap();
+        assert_eq!(config.window.output, OutputChoice::Primary);
+        assert_eq!(config.window.position, WindowPosition::Top);
+        assert!(parse("[window]\nposition = \"bottom\"\n").is_err());
+    }
+
+    #[test]
+    fn test_unknown_key_reports_location() {
+        let err = parse("[behavior]\nshow_emojis = true\nshow_emoji = false\n").unwrap_err();
+        match &err {
+            ConfigError::Parse {
+                line,
+                column,
+                message,
+                ..
+            } => {
+                assert_eq!(*line, Some(3));
+                assert_eq!(*column, Some(1));
+                assert!(message.contains("show_emoji"), "{}", message);
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+        assert!(err.to_string().starts_with("config.toml:3:1: "));
+    }
+
+    #[test]
+    fn test_invalid_values_report_location() {
+        let err = parse("[theme]\nwindow_border = \"#zzzzzz\"\n").unwrap_err();
+        assert!(err.to_string().starts_with("config.toml:2:17: "), "{}", err);
+
+        let err = parse("[theme]\nemoji_columns = 0\n").unwrap_err();
+        assert!(err.to_string().contains("at least 1"), "{}", err);
+
+        let err = parse("[keybindings]\nconfirm = \"hyper-enter\"\n").unwrap_err();
+        assert!(
+            err.to_string().contains("unknown modifier `hyper`"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_command_requires_name_and_command() {
+        let err = parse("[[commands]]\nname = \"VPN up\"\n").unwrap_err();
+        assert!(
+            err.to_string().contains("missing field `command`"),
+            "{}",
+            err
+        );
+
+        let err = parse(
+            "[[commands]]\nname = \"top\"\ncommand = \"top\"\nterminal = true\nshow_output = true\n",
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("cannot set both terminal and show_output"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_syntax_error_reports_location() {
+        let err = parse("[theme\n").unwrap_err();
+        assert!(
+            matches!(err, ConfigError::Parse { line: Some(1), .. }),
+            "{}",
+            err
+        );
+    }
+}
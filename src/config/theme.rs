@@ -0,0 +1,110 @@
+//! Theme overrides from the `[theme]` table.
+//!
+//! Every field mirrors a field of `LauncherTheme`. Fields left unset keep
+//! the built-in default.
+
+use super::color::Color;
+use serde::{Deserialize, Deserializer};
+
+/// A size in logical pixels, written as a number (`12`) or with a unit (`"12px"`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Size(pub f32);
+
+impl<'de> Deserialize<'de> for Size {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Int(i64),
+            Float(f64),
+            Text(String),
+        }
+
+        let value = match Raw::deserialize(deserializer)? {
+            Raw::Int(v) => v as f32,
+            Raw::Float(v) => v as f32,
+            Raw::Text(s) => s
+                .trim()
+                .strip_suffix("px")
+                .unwrap_or(s.trim())
+                .trim()
+                .parse::<f32>()
+                .map_err(|_| serde::de::Error::custom(format!("invalid size `{}`", s)))?,
+        };
+
+        if value < 0.0 || !value.is_finite() {
+            return Err(serde::de::Error::custom(format!(
+                "size must be a non-negative number of pixels, got {}",
+                value
+            )));
+        }
+
+        Ok(Size(value))
+    }
+}
+
+/// Optional overrides for every `LauncherTheme` field.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeConfig {
+    // Window
+    pub window_width: Option<Size>,
+    pub window_height: Option<Size>,
+    pub window_background: Option<Color>,
+    pub window_border: Option<Color>,
+    pub window_border_radius: Option<Size>,
+
+    // List items
+    pub item_margin_x: Option<Size>,
+    pub item_margin_y: Option<Size>,
+    pub item_padding_x: Option<Size>,
+    pub item_padding_y: Option<Size>,
+    pub item_border_radius: Option<Size>,
+    pub item_background: Option<Color>,
+    pub item_background_selected: Option<Color>,
+
+    // Item content
+    pub item_title_color: Option<Color>,
+    pub item_description_color: Option<Color>,
+    pub item_title_line_height: Option<Size>,
+    pub item_content_height: Option<Size>,
+
+    // Icons
+    pub icon_size: Option<Size>,
+    pub icon_placeholder_background: Option<Color>,
+    pub icon_placeholder_color: Option<Color>,
+
+    // Action indicator
+    pub action_indicator_width: Option<Size>,
+    pub action_label_color: Option<Color>,
+    pub action_key_background: Option<Color>,
+    pub action_key_border: Option<Color>,
+    pub action_key_color: Option<Color>,
+
+    // Empty state
+    pub empty_state_height: Option<Size>,
+    pub empty_state_color: Option<Color>,
+
+    // Section headers
+    pub section_header_color: Option<Color>,
+    pub section_header_margin_top: Option<Size>,
+    pub section_header_margin_bottom: Option<Size>,
+
+    // Emoji grid
+    #[serde(deserialize_with = "deserialize_columns")]
+    pub emoji_columns: Option<usize>,
+    pub emoji_cell_size: Option<Size>,
+    pub emoji_font_size: Option<Size>,
+    pub emoji_cell_selected_bg: Option<Color>,
+}
+
+/// Emoji columns must be at least one, otherwise the grid has no rows.
+fn deserialize_columns<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<usize>, D::Error> {
+    let columns = usize::deserialize(deserializer)?;
+    if columns == 0 {
+        return Err(serde::de::Error::custom("emoji_columns must be at least 1"));
+    }
+    Ok(Some(columns))
+}
@@ -0,0 +1,100 @@
+//! Launcher placement from the `[window]` table.
+
+use super::theme::Size;
+use serde::{Deserialize, Deserializer};
+
+/// Placement settings from the `[window]` table.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WindowConfig {
+    /// Output the launcher opens on.
+    pub output: OutputChoice,
+    /// Position of the launcher panel on the output.
+    pub position: WindowPosition,
+}
+
+/// Output the launcher opens on, written as `"focused"`, `"primary"` or an
+/// output name such as `"DP-1"`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum OutputChoice {
+    /// The output with the focused window, which follows the cursor on most
+    /// compositors.
+    #[default]
+    Focused,
+    /// The primary display.
+    Primary,
+    /// The output with this name, or the focused one when it is not connected.
+    Named(String),
+}
+
+impl<'de> Deserialize<'de> for OutputChoice {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        match name.trim() {
+            "" => Err(serde::de::Error::custom("output name must not be empty")),
+            "focused" => Ok(Self::Focused),
+            "primary" => Ok(Self::Primary),
+            name => Ok(Self::Named(name.to_string())),
+        }
+    }
+}
+
+/// Position of the launcher panel, written as `"center"`, `"top"` or a
+/// `{ x = 100, y = 40 }` offset from the top left corner of the output.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum WindowPosition {
+    /// Centered on the output.
+    #[default]
+    Center,
+    /// Horizontally centered, with its middle a third of the way down.
+    Top,
+    /// Top left corner at this offset from the output's top left corner.
+    Offset { x: Size, y: Size },
+}
+
+impl WindowPosition {
+    /// Get the top left corner of a `panel` sized panel on an `output` sized
+    /// output, in logical pixels. The panel is kept on the output.
+    pub fn origin(&self, output: (f32, f32), panel: (f32, f32)) -> (f32, f32) {
+        let (x, y) = match self {
+            Self::Center => ((output.0 - panel.0) / 2.0, (output.1 - panel.1) / 2.0),
+            Self::Top => ((output.0 - panel.0) / 2.0, output.1 / 3.0 - panel.1 / 2.0),
+            Self::Offset { x, y } => (x.0, y.0),
+        };
+
+        (
+            x.min(output.0 - panel.0).max(0.0),
+            y.min(output.1 - panel.1).max(0.0),
+        )
+    }
+}
+
+impl<'de> Deserialize<'de> for WindowPosition {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Offset {
+            x: Size,
+            y: Size,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Name(String),
+            Offset(Offset),
+        }
+
+        match Raw::deserialize(deserializer)? {
+            Raw::Name(name) => match name.as_str() {
+                "center" => Ok(Self::Center),
+                "top" => Ok(Self::Top),
+                other => Err(serde::de::Error::custom(format!(
+                    "invalid position `{}`, expected \"center\", \"top\" or {{ x, y }}",
+                    other
+                ))),
+            },
+            Raw::Offset(Offset { x, y }) => Ok(Self::Offset { x, y }),
+        }
+    }
+}
@@ -2,15 +2,29 @@ use anyhow::Result;
 use gpui::{Application, QuitMode, hsla};
 use gpui_component::theme::{Theme, ThemeMode};
 use std::sync::Arc;
-use tracing::{debug, error, info};
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
 
-use crate::app::{DaemonEvent, WindowEvent, create_daemon_channel, window};
+use crate::app::reload::{self, ReloadedState};
+use crate::app::{DaemonEvent, DaemonEventSender, WindowEvent, create_daemon_channel, window};
+use crate::clipboard;
 use crate::compositor::{Compositor, detect_compositor};
-use crate::desktop::cache::load_applications;
+use crate::config::{config, set_config};
 use crate::desktop::capture_session_environment;
-use crate::ipc::{Command, IpcServer, client};
+use crate::history::reset_history;
+use crate::ipc::server::handle_connection;
+use crate::ipc::{
+    ApplicationSummary, Command, DaemonStatus, IpcRequest, IpcServer, Response, ShowOptions, client,
+};
 use crate::items::ApplicationItem;
-use crate::ui::init_launcher;
+use crate::system::system_actions;
+use crate::ui::{init_launcher, launcher, reload_theme};
+
+/// How long a connection waits for the event loop to handle its command.
+const IPC_REPLY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Delay before quitting so the reply to `Quit` reaches the client.
+const QUIT_DELAY: Duration = Duration::from_millis(100);
 
 /// Initialize the tracing subscriber for logging.
 pub fn init_logging() {
@@ -38,35 +52,61 @@ pub fn run() -> Result<()> {
         Err(e) => {
             if client::is_daemon_running() {
                 debug!("Daemon already running, sending toggle command");
-                client::send_command(Command::Toggle)?;
+                client::send_command(Command::Toggle(ShowOptions::default()))?;
                 return Ok(());
             }
             return Err(e);
         }
     };
 
+    // Load user config and applications before anything reads the theme or key bindings.
+    // An invalid config is logged and the defaults are used.
+    let ReloadedState {
+        config: loaded_config,
+        applications,
+    } = reload::load_state();
+    if let Some(loaded_config) = loaded_config {
+        set_config(loaded_config);
+    }
+
     // Detect compositor for window switching support
     let compositor: Arc<dyn Compositor> = Arc::from(detect_compositor());
 
-    // Load applications and convert to ApplicationItems
-    let entries = load_applications();
-    let applications: Vec<ApplicationItem> = entries.into_iter().map(Into::into).collect();
-    info!(count = applications.len(), "Loaded applications");
-
     // Create unified event channel
     let (event_tx, event_rx) = create_daemon_channel();
 
-    // Spawn background thread for blocking IPC accept
+    // Watch config and application directories for live reload.
+    // The watcher stops when dropped, so keep it alive for the daemon lifetime.
+    let _reload_watcher = match reload::spawn_watcher(event_tx.clone()) {
+        Ok(watcher) => Some(watcher),
+        Err(e) => {
+            warn!(%e, "Failed to watch for changes, live reload disabled");
+            None
+        }
+    };
+
+    // Record the clipboard for the Clipboard submenu
+    clipboard::start_watcher();
+
+    // Spawn background thread for blocking IPC accept.
+    // Each connection gets its own thread so a slow client cannot block others.
     let ipc_listener = ipc_server.listener();
     let ipc_event_tx = event_tx.clone();
     std::thread::spawn(move || {
         loop {
-            if let Some(cmd) = IpcServer::accept_blocking(&ipc_listener)
-                && ipc_event_tx.send(DaemonEvent::Ipc(cmd)).is_err()
-            {
+            let Some(stream) = IpcServer::accept_blocking(&ipc_listener) else {
+                continue;
+            };
+            if ipc_event_tx.is_disconnected() {
                 // Channel closed, exit thread
                 break;
             }
+            let event_tx = ipc_event_tx.clone();
+            std::thread::spawn(move || {
+                if let Err(e) = handle_connection(stream, |cmd| dispatch_ipc(cmd, &event_tx)) {
+                    debug!(%e, "IPC connection failed");
+                }
+            });
         }
     });
 
@@ -81,10 +121,14 @@ pub fn run() -> Result<()> {
             // Customize theme for transparent background and no borders
             configure_theme(cx);
 
-            let applications_clone = applications.clone();
+            let mut applications = applications.clone();
             let compositor_clone = compositor.clone();
             let mut window_handle = None;
             let mut visible = false;
+            // Reloaded state waiting to be applied when the window next opens
+            let mut pending_reload: Option<Box<ReloadedState>> = None;
+            // `zlaunch dmenu` request waiting for the user to pick
+            let mut pending_dmenu: Option<IpcRequest> = None;
 
             // Main event loop - async wait on channel, no polling needed
             cx.spawn(async move |cx: &mut gpui::AsyncApp| {
@@ -98,35 +142,144 @@ pub fn run() -> Result<()> {
                             });
                             window_handle = None;
                             visible = false;
+                            if let Some(request) = pending_dmenu.take() {
+                                request.respond(Response::Cancelled);
+                            }
                         }
-                        DaemonEvent::Ipc(cmd) => {
-                            let _ = cx.update(|cx| match cmd {
-                                Command::Show | Command::Toggle if !visible => {
+                        DaemonEvent::Window(WindowEvent::DmenuSelected(selection)) => {
+                            if let Some(request) = pending_dmenu.take() {
+                                request.respond(Response::Selected(selection));
+                            }
+                        }
+                        DaemonEvent::Reloaded(state) => {
+                            pending_reload = Some(state);
+                        }
+                        DaemonEvent::Ipc(request) => {
+                            // A dmenu request is answered once the user picks or cancels
+                            if let Command::Dmenu(dmenu) = &request.command {
+                                if visible {
+                                    request.respond(Response::Error(
+                                        "The launcher is already open".to_string(),
+                                    ));
+                                    continue;
+                                }
+                                let opened = cx.update(|cx| {
+                                    if let Some(state) = pending_reload.take() {
+                                        apply_reload(*state, &mut applications, cx);
+                                    }
+                                    window::create_dmenu_window(
+                                        dmenu,
+                                        compositor_clone.clone(),
+                                        event_tx.clone(),
+                                        cx,
+                                    )
+                                });
+                                match opened.and_then(|result| result) {
+                                    Ok(handle) => {
+                                        window_handle = Some(handle);
+                                        visible = true;
+                                        pending_dmenu = Some(request);
+                                    }
+                                    Err(e) => {
+                                        error!(%e, "Failed to create window");
+                                        request.respond(Response::Error(format!(
+                                            "Failed to create window: {}",
+                                            e
+                                        )));
+                                    }
+                                }
+                                continue;
+                            }
+
+                            // Queries waiting on the compositor run off the event loop,
+                            // so they never freeze an open launcher
+                            if matches!(request.command, Command::Status | Command::ListWindows) {
+                                let compositor = compositor_clone.clone();
+                                let applications = applications.len();
+                                cx.background_executor()
+                                    .spawn(async move {
+                                        let response = match &request.command {
+                                            Command::Status => Response::Status(daemon_status(
+                                                visible,
+                                                applications,
+                                                compositor.as_ref(),
+                                            )),
+                                            _ => match compositor.list_windows() {
+                                                Ok(windows) => Response::Windows(windows),
+                                                Err(e) => Response::Error(format!(
+                                                    "Failed to list windows: {}",
+                                                    e
+                                                )),
+                                            },
+                                        };
+                                        request.respond(response);
+                                    })
+                                    .detach();
+                                continue;
+                            }
+
+                            let response = cx.update(|cx| match &request.command {
+                                Command::Show(options) | Command::Toggle(options) if !visible => {
+                                    if let Some(state) = pending_reload.take() {
+                                        apply_reload(*state, &mut applications, cx);
+                                    }
                                     match window::create_and_show_window(
-                                        applications_clone.clone(),
+                                        applications.clone(),
                                         compositor_clone.clone(),
                                         event_tx.clone(),
+                                        options,
                                         cx,
                                     ) {
                                         Ok(handle) => {
                                             window_handle = Some(handle);
                                             visible = true;
+                                            Response::Ok
+                                        }
+                                        Err(e) => {
+                                            error!(%e, "Failed to create window");
+                                            Response::Error(format!(
+                                                "Failed to create window: {}",
+                                                e
+                                            ))
                                         }
-                                        Err(e) => error!(%e, "Failed to create window"),
                                     }
                                 }
-                                Command::Hide | Command::Toggle if visible => {
+                                Command::Hide | Command::Toggle(_) if visible => {
                                     if let Some(ref handle) = window_handle {
                                         window::close_window(handle, cx);
                                         window_handle = None;
                                         visible = false;
                                     }
+                                    if let Some(request) = pending_dmenu.take() {
+                                        request.respond(Response::Cancelled);
+                                    }
+                                    Response::Ok
+                                }
+                                Command::Reload => {
+                                    reload::reload_in_background(event_tx.clone());
+                                    Response::Ok
                                 }
-                                Command::Quit => {
-                                    cx.quit();
+                                Command::ResetHistory => {
+                                    reset_history();
+                                    info!("Launch history reset");
+                                    Response::Ok
                                 }
-                                _ => {}
+                                Command::ListApplications => Response::Applications(
+                                    applications.iter().map(ApplicationSummary::from).collect(),
+                                ),
+                                _ => Response::Ok,
                             });
+
+                            let quit = matches!(request.command, Command::Quit);
+                            request.respond(
+                                response.unwrap_or_else(|e| Response::Error(e.to_string())),
+                            );
+
+                            if quit {
+                                // Give the connection thread a moment to deliver the reply
+                                cx.background_executor().timer(QUIT_DELAY).await;
+                                let _ = cx.update(|cx| cx.quit());
+                            }
                         }
                         _ => {}
                     }
@@ -138,6 +291,56 @@ pub fn run() -> Result<()> {
     Ok(())
 }
 
+/// Forward an IPC command to the event loop and wait for its response.
+/// Runs on the connection thread.
+fn dispatch_ipc(cmd: Command, event_tx: &DaemonEventSender) -> Response {
+    // A dmenu waits for the user, however long that takes
+    let waits_for_user = matches!(cmd, Command::Dmenu(_));
+
+    let (request, reply) = IpcRequest::new(cmd);
+    if event_tx.send(DaemonEvent::Ipc(request)).is_err() {
+        return Response::Error("Daemon is shutting down".to_string());
+    }
+
+    let response = if waits_for_user {
+        reply.recv().ok()
+    } else {
+        reply.recv_timeout(IPC_REPLY_TIMEOUT).ok()
+    };
+    response.unwrap_or_else(|| Response::Error("Daemon did not respond".to_string()))
+}
+
+/// Collect the state reported by `zlaunch status`.
+///
+/// Asks the compositor for its windows, so it should not be called on the
+/// event loop.
+fn daemon_status(visible: bool, applications: usize, compositor: &dyn Compositor) -> DaemonStatus {
+    let config = config();
+    DaemonStatus {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        visible,
+        compositor: compositor.name().to_string(),
+        applications,
+        windows: compositor.list_windows().ok().map(|windows| windows.len()),
+        actions: system_actions(&config.actions).len() + config.commands.len(),
+    }
+}
+
+/// Apply reloaded config and applications.
+/// Must only be called while the launcher window is closed.
+fn apply_reload(state: ReloadedState, applications: &mut Vec<ApplicationItem>, cx: &mut gpui::App) {
+    if let Some(new_config) = state.config {
+        let previous_keys = config().keybindings.clone();
+        set_config(new_config);
+        reload_theme();
+        launcher::rebind(&previous_keys, cx);
+        clipboard::start_watcher();
+    }
+
+    *applications = state.applications;
+    info!("Applied reloaded config and applications");
+}
+
 /// Configure the global theme for transparent launcher appearance.
 fn configure_theme(cx: &mut gpui::App) {
     let theme = Theme::global_mut(cx);
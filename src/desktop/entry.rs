@@ -1,5 +1,16 @@
 use std::path::PathBuf;
 
+/// An additional action declared in a `[Desktop Action <id>]` group,
+/// such as "New Private Window".
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DesktopAction {
+    /// Action identifier from the group name.
+    pub id: String,
+    pub name: String,
+    pub exec: String,
+    pub icon: Option<String>,
+}
+
 #[derive(Clone, Debug)]
 pub struct DesktopEntry {
     pub id: String,
@@ -12,6 +23,8 @@ pub struct DesktopEntry {
     pub categories: Vec<String>,
     pub terminal: bool,
     pub path: PathBuf,
+    /// Actions listed in the `Actions` key, in declaration order.
+    pub actions: Vec<DesktopAction>,
 }
 
 impl DesktopEntry {
@@ -37,6 +50,13 @@ impl DesktopEntry {
             categories,
             terminal,
             path,
+            actions: Vec::new(),
         }
     }
+
+    /// Builder method to set the desktop actions.
+    pub fn with_actions(mut self, actions: Vec<DesktopAction>) -> Self {
+        self.actions = actions;
+        self
+    }
 }
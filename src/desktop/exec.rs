@@ -1,21 +1,42 @@
 use crate::desktop::entry::DesktopEntry;
 use crate::desktop::env::get_session_environment;
+#[cfg(unix)]
+use crate::desktop::exec_line::{ExecContext, expand_exec};
 use std::process::Command;
 
 pub fn launch_application(entry: &DesktopEntry) -> anyhow::Result<()> {
+    launch_application_with(entry, &[])
+}
+
+/// Launch an application, passing it files or URLs to open.
+///
+/// Targets are substituted for the `%f`, `%F`, `%u` and `%U` field codes of
+/// the Exec line. Applications that take a single file are started once per
+/// target.
+pub fn launch_application_with(entry: &DesktopEntry, targets: &[String]) -> anyhow::Result<()> {
     #[cfg(unix)]
     {
-        let exec = clean_exec_string(&entry.exec);
-
-        if entry.terminal {
-            launch_in_terminal_unix(&exec)?;
-        } else {
-            launch_detached_unix(&exec)?;
+        let context = ExecContext {
+            name: &entry.name,
+            icon: entry.icon.as_deref(),
+            desktop_file: Some(&entry.path),
+            targets,
+        };
+        let commands = expand_exec(&entry.exec, &context)
+            .map_err(|e| anyhow::anyhow!("{}: {}", entry.path.display(), e))?;
+
+        for argv in &commands {
+            if entry.terminal {
+                launch_in_terminal_unix(argv)?;
+            } else {
+                launch_detached_unix(argv)?;
+            }
         }
     }
 
     #[cfg(windows)]
     {
+        let _ = targets;
         launch_windows(entry)?;
     }
 
@@ -23,27 +44,10 @@ pub fn launch_application(entry: &DesktopEntry) -> anyhow::Result<()> {
 }
 
 #[cfg(unix)]
-fn clean_exec_string(exec: &str) -> String {
-    let mut result = exec.to_string();
-
-    for placeholder in [
-        "%f", "%F", "%u", "%U", "%d", "%D", "%n", "%N", "%i", "%c", "%k",
-    ] {
-        result = result.replace(placeholder, "");
-    }
-
-    result.trim().to_string()
-}
-
-#[cfg(unix)]
-fn launch_detached_unix(exec: &str) -> anyhow::Result<()> {
-    let parts: Vec<&str> = exec.split_whitespace().collect();
-    if parts.is_empty() {
+fn launch_detached_unix(argv: &[String]) -> anyhow::Result<()> {
+    let Some((program, args)) = argv.split_first() else {
         anyhow::bail!("Empty exec command");
-    }
-
-    let program = parts[0];
-    let args = &parts[1..];
+    };
 
     Command::new(program)
         .args(args)
@@ -58,12 +62,12 @@ fn launch_detached_unix(exec: &str) -> anyhow::Result<()> {
 }
 
 #[cfg(unix)]
-fn launch_in_terminal_unix(exec: &str) -> anyhow::Result<()> {
+pub(crate) fn launch_in_terminal_unix(argv: &[String]) -> anyhow::Result<()> {
     let terminal = get_terminal_unix()?;
 
     Command::new(&terminal)
         .arg("-e")
-        .arg(exec)
+        .args(argv)
         .env_clear()
         .envs(get_session_environment().iter())
         .stdin(std::process::Stdio::null())
@@ -0,0 +1,505 @@
+//! Desktop Entry `Exec` line parsing and field code expansion.
+//!
+//! Implements the rules from the freedesktop Desktop Entry Specification,
+//! section "The Exec key":
+//! - The general string escapes (`\s`, `\n`, `\t`, `\r`, `\\`) are applied
+//!   first, then the line is split into arguments on unquoted whitespace.
+//! - Arguments may be quoted with `"`. Inside quotes, `"`, `` ` ``, `$` and
+//!   `\` are escaped with a backslash.
+//! - Field codes (`%f`, `%F`, `%u`, `%U`, `%i`, `%c`, `%k`, `%%`) are expanded
+//!   after splitting, so expanded values never need quoting.
+//!
+//! Outside quotes a backslash escapes the next character, so lines such as
+//! `Exec=my\ app` keep working even though the spec asks for quotes.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// An error in an `Exec` line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExecError {
+    /// A `"` was opened but never closed.
+    UnterminatedQuote,
+    /// The line ends with a lone backslash.
+    TrailingBackslash,
+    /// The line contains no program to run.
+    Empty,
+}
+
+impl fmt::Display for ExecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnterminatedQuote => write!(f, "unterminated quote in Exec line"),
+            Self::TrailingBackslash => write!(f, "trailing backslash in Exec line"),
+            Self::Empty => write!(f, "empty Exec line"),
+        }
+    }
+}
+
+impl std::error::Error for ExecError {}
+
+/// A piece of an argument: literal text or a field code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Piece {
+    Text(String),
+    Field(char),
+}
+
+/// A single argument of a parsed `Exec` line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Argument {
+    pieces: Vec<Piece>,
+    /// Whether any part of the argument was quoted.
+    quoted: bool,
+}
+
+impl Argument {
+    fn new() -> Self {
+        Self {
+            pieces: Vec::new(),
+            quoted: false,
+        }
+    }
+
+    fn push_char(&mut self, c: char) {
+        match self.pieces.last_mut() {
+            Some(Piece::Text(text)) => text.push(c),
+            _ => self.pieces.push(Piece::Text(c.to_string())),
+        }
+    }
+
+    /// The field code if this argument is exactly one unquoted field code.
+    fn standalone_field(&self) -> Option<char> {
+        match self.pieces.as_slice() {
+            [Piece::Field(code)] if !self.quoted => Some(*code),
+            _ => None,
+        }
+    }
+}
+
+/// Values substituted for field codes.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExecContext<'a> {
+    /// Translated application name, for `%c`.
+    pub name: &'a str,
+    /// Icon key of the entry, for `%i`.
+    pub icon: Option<&'a str>,
+    /// Location of the desktop file, for `%k`.
+    pub desktop_file: Option<&'a Path>,
+    /// Files or URLs to open, for `%f`, `%F`, `%u` and `%U`.
+    pub targets: &'a [String],
+}
+
+/// A parsed `Exec` line, ready for field code expansion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecLine {
+    args: Vec<Argument>,
+}
+
+impl ExecLine {
+    /// Parse an `Exec` value as it appears in the desktop file.
+    pub fn parse(exec: &str) -> Result<Self, ExecError> {
+        let unescaped = unescape_string(exec);
+        let args = split_arguments(&unescaped)?;
+
+        if args.is_empty() {
+            return Err(ExecError::Empty);
+        }
+
+        Ok(Self { args })
+    }
+
+    /// Whether the line accepts files or URLs to open.
+    pub fn accepts_targets(&self) -> bool {
+        self.field_codes()
+            .any(|c| matches!(c, 'f' | 'F' | 'u' | 'U'))
+    }
+
+    /// Expand field codes into one or more command lines.
+    ///
+    /// Usually this yields a single command. If the line takes a single file
+    /// or URL (`%f` or `%u`) but several targets are given, the spec asks
+    /// for one instance per target, so one command per target is returned.
+    pub fn expand(&self, context: &ExecContext) -> Result<Vec<Vec<String>>, ExecError> {
+        let single_target = self.field_codes().any(|c| matches!(c, 'f' | 'u'));
+
+        let commands = if single_target && context.targets.len() > 1 {
+            context
+                .targets
+                .iter()
+                .map(|target| self.expand_once(context, Some(target)))
+                .collect()
+        } else {
+            vec![self.expand_once(context, context.targets.first())]
+        };
+
+        // An Exec line made only of field codes that expanded to nothing
+        if commands.iter().any(|argv| argv.is_empty()) {
+            return Err(ExecError::Empty);
+        }
+
+        Ok(commands)
+    }
+
+    /// Expand field codes for one instance, with `%f`/`%u` bound to `target`.
+    fn expand_once(&self, context: &ExecContext, target: Option<&String>) -> Vec<String> {
+        let mut argv = Vec::new();
+
+        for arg in &self.args {
+            // Field codes standing alone may expand to zero or several arguments
+            if let Some(code) = arg.standalone_field() {
+                match code {
+                    'f' => argv.extend(target.map(|t| to_file_path(t))),
+                    'u' => argv.extend(target.cloned()),
+                    'F' => argv.extend(context.targets.iter().map(|t| to_file_path(t))),
+                    'U' => argv.extend(context.targets.iter().cloned()),
+                    'i' => {
+                        if let Some(icon) = context.icon.filter(|i| !i.is_empty()) {
+                            argv.push("--icon".to_string());
+                            argv.push(icon.to_string());
+                        }
+                    }
+                    'c' => argv.push(context.name.to_string()),
+                    'k' => argv.extend(context.desktop_file.map(|p| p.display().to_string())),
+                    // Deprecated (%d, %D, %n, %N, %v, %m) and unknown codes are removed
+                    _ => {}
+                }
+                continue;
+            }
+
+            // Field codes embedded in a larger argument expand in place
+            let mut value = String::new();
+            for piece in &arg.pieces {
+                match piece {
+                    Piece::Text(text) => value.push_str(text),
+                    Piece::Field(code) => match code {
+                        'f' => value.push_str(&target.map(|t| to_file_path(t)).unwrap_or_default()),
+                        'u' => value.push_str(target.map(String::as_str).unwrap_or_default()),
+                        'F' => value.push_str(
+                            &context
+                                .targets
+                                .iter()
+                                .map(|t| to_file_path(t))
+                                .collect::<Vec<_>>()
+                                .join(" "),
+                        ),
+                        'U' => value.push_str(&context.targets.join(" ")),
+                        'i' => value.push_str(context.icon.unwrap_or_default()),
+                        'c' => value.push_str(context.name),
+                        'k' => {
+                            if let Some(path) = context.desktop_file {
+                                value.push_str(&path.display().to_string());
+                            }
+                        }
+                        _ => {}
+                    },
+                }
+            }
+            argv.push(value);
+        }
+
+        argv
+    }
+
+    fn field_codes(&self) -> impl Iterator<Item = char> + '_ {
+        self.args
+            .iter()
+            .flat_map(|arg| arg.pieces.iter())
+            .filter_map(|piece| match piece {
+                Piece::Field(code) => Some(*code),
+                Piece::Text(_) => None,
+            })
+    }
+}
+
+/// Parse and expand an `Exec` line in one step.
+pub fn expand_exec(exec: &str, context: &ExecContext) -> Result<Vec<Vec<String>>, ExecError> {
+    ExecLine::parse(exec)?.expand(context)
+}
+
+/// Apply the general escape rules for desktop entry string values.
+fn unescape_string(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('s') => result.push(' '),
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('r') => result.push('\r'),
+            Some('\\') => result.push('\\'),
+            // Not a string escape, keep it for the quoting rules
+            Some(other) => {
+                result.push('\\');
+                result.push(other);
+            }
+            None => result.push('\\'),
+        }
+    }
+
+    result
+}
+
+/// Split an unescaped `Exec` value into arguments, applying the quoting rules.
+fn split_arguments(line: &str) -> Result<Vec<Argument>, ExecError> {
+    let mut args = Vec::new();
+    let mut current: Option<Argument> = None;
+    let mut in_quotes = false;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                current.get_or_insert_with(Argument::new).quoted = true;
+            }
+            '\\' => {
+                let next = chars.next().ok_or(ExecError::TrailingBackslash)?;
+                let arg = current.get_or_insert_with(Argument::new);
+                // Inside quotes only these four characters can be escaped
+                if in_quotes && !matches!(next, '"' | '`' | '$' | '\\') {
+                    arg.push_char('\\');
+                }
+                arg.push_char(next);
+            }
+            '%' => {
+                let arg = current.get_or_insert_with(Argument::new);
+                match chars.next() {
+                    Some('%') => arg.push_char('%'),
+                    Some(code) => arg.pieces.push(Piece::Field(code)),
+                    None => arg.push_char('%'),
+                }
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if let Some(arg) = current.take() {
+                    args.push(arg);
+                }
+            }
+            c => current.get_or_insert_with(Argument::new).push_char(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(ExecError::UnterminatedQuote);
+    }
+
+    args.extend(current);
+    Ok(args)
+}
+
+/// Convert a `file://` URL to a local path for `%f`/`%F`.
+/// Anything else (plain paths and remote URLs) is passed through unchanged.
+fn to_file_path(target: &str) -> String {
+    let Some(rest) = target.strip_prefix("file://") else {
+        return target.to_string();
+    };
+
+    // Drop an optional host ("file://localhost/path")
+    let path = match rest.find('/') {
+        Some(idx) => &rest[idx..],
+        None => rest,
+    };
+
+    PathBuf::from(percent_decode(path)).display().to_string()
+}
+
+/// Decode `%XX` escapes in a URL path.
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && i + 2 < bytes.len()
+            && let Some(byte) = std::str::from_utf8(&bytes[i + 1..i + 3])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            decoded.push(byte);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(exec: &str) -> Vec<String> {
+        expand_with(exec, &[])
+    }
+
+    fn expand_with(exec: &str, targets: &[&str]) -> Vec<String> {
+        let targets: Vec<String> = targets.iter().map(|t| t.to_string()).collect();
+        let context = ExecContext {
+            name: "My App",
+            icon: Some("my-app"),
+            desktop_file: Some(Path::new("/usr/share/applications/my-app.desktop")),
+            targets: &targets,
+        };
+        let mut commands = expand_exec(exec, &context).unwrap();
+        assert_eq!(commands.len(), 1, "expected a single command");
+        commands.remove(0)
+    }
+
+    #[test]
+    fn test_plain_arguments() {
+        assert_eq!(expand("firefox"), ["firefox"]);
+        assert_eq!(expand("  gimp   -n  "), ["gimp", "-n"]);
+    }
+
+    #[test]
+    fn test_quoted_arguments() {
+        assert_eq!(expand(r#"sh -c "foo bar""#), ["sh", "-c", "foo bar"]);
+        assert_eq!(
+            expand(r#""/opt/My App/bin/app" --flag"#),
+            ["/opt/My App/bin/app", "--flag"]
+        );
+        // Quotes may start in the middle of a word
+        assert_eq!(expand(r#"app --title="a b""#), ["app", "--title=a b"]);
+        assert_eq!(expand(r#"app """#), ["app", ""]);
+    }
+
+    #[test]
+    fn test_escapes_inside_quotes() {
+        // Spec example: `"\$HOME"` in the parsed value is a literal $HOME,
+        // written `"\\$HOME"` in the file because of the string escapes
+        assert_eq!(
+            expand(r#"sh -c "echo \\$HOME""#),
+            ["sh", "-c", "echo $HOME"]
+        );
+        assert_eq!(expand(r#"app "say \\"hi\\"""#), ["app", r#"say "hi""#]);
+        assert_eq!(expand(r#"app "\\`cmd\\`""#), ["app", "`cmd`"]);
+        // A literal backslash needs four backslashes in the file
+        assert_eq!(expand(r#"app "a\\\\b""#), ["app", r"a\b"]);
+    }
+
+    #[test]
+    fn test_string_escapes() {
+        // String escapes are decoded before splitting, so `\s` separates
+        // arguments unless it is quoted
+        assert_eq!(expand(r"app a\sb"), ["app", "a", "b"]);
+        assert_eq!(expand(r#"app "a\sb""#), ["app", "a b"]);
+        assert_eq!(expand(r"app\tx"), ["app", "x"]);
+    }
+
+    #[test]
+    fn test_escaped_space_outside_quotes() {
+        assert_eq!(expand(r"/opt/my\ app --x"), ["/opt/my app", "--x"]);
+    }
+
+    #[test]
+    fn test_percent_literal() {
+        assert_eq!(expand("printf 100%%"), ["printf", "100%"]);
+        assert_eq!(expand(r#"date "+%%H:%%M""#), ["date", "+%H:%M"]);
+    }
+
+    #[test]
+    fn test_icon_name_and_location() {
+        assert_eq!(expand("app %i"), ["app", "--icon", "my-app"]);
+        assert_eq!(expand("app --name %c"), ["app", "--name", "My App"]);
+        assert_eq!(
+            expand("app %k"),
+            ["app", "/usr/share/applications/my-app.desktop"]
+        );
+
+        let context = ExecContext {
+            name: "App",
+            ..Default::default()
+        };
+        assert_eq!(expand_exec("app %i %k", &context).unwrap(), [["app"]]);
+    }
+
+    #[test]
+    fn test_embedded_field_codes() {
+        assert_eq!(expand("app --class=%c"), ["app", "--class=My App"]);
+        assert_eq!(
+            expand_with("app --open=%u", &["https://example.com"]),
+            ["app", "--open=https://example.com"]
+        );
+        // Quoted field codes are still expanded, as a single argument
+        assert_eq!(expand(r#"app "%c""#), ["app", "My App"]);
+    }
+
+    #[test]
+    fn test_targets_removed_when_absent() {
+        assert_eq!(expand("app %f"), ["app"]);
+        assert_eq!(expand("app %U --new-window"), ["app", "--new-window"]);
+    }
+
+    #[test]
+    fn test_deprecated_codes_removed() {
+        assert_eq!(expand("app %d %D %n %N %v %m -x"), ["app", "-x"]);
+    }
+
+    #[test]
+    fn test_file_lists() {
+        assert_eq!(
+            expand_with("app %F", &["/tmp/a b.txt", "file:///tmp/c%20d.txt"]),
+            ["app", "/tmp/a b.txt", "/tmp/c d.txt"]
+        );
+        assert_eq!(
+            expand_with("app %U", &["file:///tmp/a.txt", "https://example.com"]),
+            ["app", "file:///tmp/a.txt", "https://example.com"]
+        );
+    }
+
+    #[test]
+    fn test_single_file_converts_urls() {
+        assert_eq!(
+            expand_with("app %f", &["file://localhost/home/u/My%20Doc.pdf"]),
+            ["app", "/home/u/My Doc.pdf"]
+        );
+        assert_eq!(
+            expand_with("app %u", &["file:///home/u/a.pdf"]),
+            ["app", "file:///home/u/a.pdf"]
+        );
+    }
+
+    #[test]
+    fn test_one_instance_per_target() {
+        let targets = vec!["/a".to_string(), "/b".to_string()];
+        let context = ExecContext {
+            name: "App",
+            targets: &targets,
+            ..Default::default()
+        };
+        assert_eq!(
+            expand_exec("app --open %f", &context).unwrap(),
+            [["app", "--open", "/a"], ["app", "--open", "/b"]]
+        );
+    }
+
+    #[test]
+    fn test_accepts_targets() {
+        assert!(ExecLine::parse("app %U").unwrap().accepts_targets());
+        assert!(!ExecLine::parse("app %i %c").unwrap().accepts_targets());
+    }
+
+    #[test]
+    fn test_errors() {
+        let context = ExecContext::default();
+        assert_eq!(
+            expand_exec(r#"sh -c "foo"#, &context),
+            Err(ExecError::UnterminatedQuote)
+        );
+        assert_eq!(
+            expand_exec(r"app \", &context),
+            Err(ExecError::TrailingBackslash)
+        );
+        assert_eq!(expand_exec("   ", &context), Err(ExecError::Empty));
+        assert_eq!(expand_exec("%f", &context), Err(ExecError::Empty));
+    }
+}
@@ -3,10 +3,12 @@ pub mod entry;
 pub mod env;
 pub mod exec;
 #[cfg(unix)]
+pub mod exec_line;
+#[cfg(unix)]
 pub mod parser;
 pub mod scanner;
 
-pub use entry::DesktopEntry;
+pub use entry::{DesktopAction, DesktopEntry};
 pub use env::{capture_session_environment, get_session_environment};
-pub use exec::launch_application;
-pub use scanner::scan_applications;
+pub use exec::{launch_application, launch_application_with};
+pub use scanner::{get_application_dirs, scan_applications};
@@ -1,7 +1,7 @@
 //! Desktop file parser for Unix/Linux systems.
 //! This module is only compiled on Unix platforms.
 
-use crate::desktop::entry::DesktopEntry;
+use crate::desktop::entry::{DesktopAction, DesktopEntry};
 use freedesktop_desktop_entry::DesktopEntry as FdEntry;
 use std::path::Path;
 
@@ -32,17 +32,40 @@ pub fn parse_desktop_file(path: &Path) -> Option<DesktopEntry> {
         .unwrap_or_default();
 
     let terminal = fd_entry.terminal();
+    let actions = parse_actions(&fd_entry, locales);
 
     // icon_path is resolved later in cache.rs after all entries are loaded
-    Some(DesktopEntry::new(
-        id,
-        name,
-        exec,
-        icon,
-        None,
-        comment,
-        categories,
-        terminal,
-        path.to_path_buf(),
-    ))
+    Some(
+        DesktopEntry::new(
+            id,
+            name,
+            exec,
+            icon,
+            None,
+            comment,
+            categories,
+            terminal,
+            path.to_path_buf(),
+        )
+        .with_actions(actions),
+    )
+}
+
+/// Collect the `[Desktop Action <id>]` groups listed in the `Actions` key.
+/// Actions without a name or Exec line cannot be launched and are skipped.
+fn parse_actions(fd_entry: &FdEntry, locales: &[&str]) -> Vec<DesktopAction> {
+    fd_entry
+        .actions()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|id| !id.is_empty())
+        .filter_map(|id| {
+            Some(DesktopAction {
+                id: id.to_string(),
+                name: fd_entry.action_name(id, locales)?.to_string(),
+                exec: fd_entry.action_exec(id)?.to_string(),
+                icon: fd_entry.action_entry(id, "Icon").map(|s| s.to_string()),
+            })
+        })
+        .collect()
 }
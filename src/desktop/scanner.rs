@@ -26,8 +26,21 @@ pub fn scan_applications() -> Vec<DesktopEntry> {
     result
 }
 
+/// Get all directories that are scanned for applications.
+pub fn get_application_dirs() -> Vec<PathBuf> {
+    #[cfg(unix)]
+    {
+        get_xdg_application_dirs()
+    }
+
+    #[cfg(windows)]
+    {
+        get_start_menu_dirs()
+    }
+}
+
 #[cfg(unix)]
-fn get_xdg_application_dirs() -> Vec<PathBuf> {
+pub fn get_xdg_application_dirs() -> Vec<PathBuf> {
     let mut dirs = Vec::new();
 
     if let Some(data_home) = dirs::data_local_dir() {
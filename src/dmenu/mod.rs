@@ -0,0 +1,156 @@
+//! dmenu-compatible selection.
+//!
+//! `zlaunch dmenu` reads lines from stdin and sends them to the daemon, which
+//! shows them in the launcher list. The chosen entries are sent back and
+//! printed to stdout, as rofi and wofi do with `-dmenu`.
+//!
+//! Lines may carry rofi-style metadata after a NUL byte, either as
+//! alternating keys and values separated by `\x1f` (`text\0icon\x1ffirefox`)
+//! or as `key=value` fields (`text\0icon=firefox`). Only `icon` is used.
+
+use serde::{Deserialize, Serialize};
+
+/// Separator between metadata fields.
+const FIELD_SEPARATOR: char = '\x1f';
+
+/// A line offered for selection.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DmenuEntry {
+    /// Text shown in the list and printed when chosen
+    pub text: String,
+    /// Icon name or path from the line metadata
+    pub icon: Option<String>,
+}
+
+impl DmenuEntry {
+    /// Parse a line, splitting off its metadata.
+    pub fn parse(line: &str) -> Self {
+        let (text, metadata) = match line.split_once('\0') {
+            Some((text, metadata)) => (text, metadata),
+            None => (line, ""),
+        };
+
+        let icon = metadata_fields(metadata)
+            .into_iter()
+            .find(|(key, value)| *key == "icon" && !value.is_empty())
+            .map(|(_, value)| value.to_string());
+
+        Self {
+            text: text.to_string(),
+            icon,
+        }
+    }
+}
+
+/// Split metadata into key/value pairs.
+fn metadata_fields(metadata: &str) -> Vec<(&str, &str)> {
+    if metadata.is_empty() {
+        return Vec::new();
+    }
+
+    let fields: Vec<&str> = metadata.split(FIELD_SEPARATOR).collect();
+    if fields[0].contains('=') {
+        fields
+            .iter()
+            .filter_map(|field| field.split_once('='))
+            .collect()
+    } else {
+        fields
+            .chunks_exact(2)
+            .map(|pair| (pair[0], pair[1]))
+            .collect()
+    }
+}
+
+/// Parse the lines read from stdin into entries.
+pub fn parse_entries(input: &str) -> Vec<DmenuEntry> {
+    input
+        .lines()
+        .map(|line| DmenuEntry::parse(line.strip_suffix('\r').unwrap_or(line)))
+        .collect()
+}
+
+/// A dmenu selection requested by `zlaunch dmenu`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DmenuRequest {
+    pub entries: Vec<DmenuEntry>,
+    /// Shown in the empty search input
+    pub prompt: Option<String>,
+    /// Allow marking several entries before confirming
+    pub multi_select: bool,
+    /// Return the typed text when no entry matches
+    pub allow_custom: bool,
+}
+
+/// An entry chosen by the user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DmenuSelection {
+    /// Index of the entry in the input, `None` for custom text
+    pub index: Option<usize>,
+    pub text: String,
+}
+
+impl DmenuSelection {
+    /// A selection of typed text that matches no entry.
+    pub fn custom(text: impl Into<String>) -> Self {
+        Self {
+            index: None,
+            text: text.into(),
+        }
+    }
+
+    /// The line printed for this selection. With `print_index`, custom
+    /// text is printed as `-1` like rofi does.
+    pub fn format(&self, print_index: bool) -> String {
+        match (print_index, self.index) {
+            (false, _) => self.text.clone(),
+            (true, Some(index)) => index.to_string(),
+            (true, None) => "-1".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_plain_lines() {
+        let entries = parse_entries("one\r\ntwo\n\nthree");
+        let texts: Vec<&str> = entries.iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(texts, ["one", "two", "", "three"]);
+        assert!(entries.iter().all(|e| e.icon.is_none()));
+    }
+
+    #[test]
+    fn test_parse_metadata() {
+        let rofi = DmenuEntry::parse("Firefox\0icon\x1ffirefox\x1finfo\x1fweb");
+        assert_eq!(rofi.text, "Firefox");
+        assert_eq!(rofi.icon.as_deref(), Some("firefox"));
+
+        let key_value = DmenuEntry::parse("Files\0icon=folder");
+        assert_eq!(key_value.text, "Files");
+        assert_eq!(key_value.icon.as_deref(), Some("folder"));
+
+        let unknown = DmenuEntry::parse("Text\0meta\x1fsearch words");
+        assert_eq!(unknown.text, "Text");
+        assert_eq!(unknown.icon, None);
+
+        let dangling = DmenuEntry::parse("Text\0icon");
+        assert_eq!(dangling.icon, None);
+    }
+
+    #[test]
+    fn test_format_selection() {
+        let entry = DmenuSelection {
+            index: Some(3),
+            text: "three".to_string(),
+        };
+        assert_eq!(entry.format(false), "three");
+        assert_eq!(entry.format(true), "3");
+
+        let custom = DmenuSelection::custom("typed");
+        assert_eq!(custom.format(false), "typed");
+        assert_eq!(custom.format(true), "-1");
+    }
+}
@@ -0,0 +1,364 @@
+//! Persistent launch history used to rank results by frecency.
+//!
+//! Every confirmed item is recorded under a stable key. Each key keeps an
+//! exponentially decaying score: a use adds 1, and the score halves every
+//! [`HALF_LIFE_SECS`]. Frequently and recently used items therefore rank
+//! above items that were used often a long time ago.
+//!
+//! The history is stored as JSON in `$XDG_STATE_HOME/zlaunch/history.json`.
+
+use crate::config::config;
+use crate::items::ListItem;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::mpsc::{self, Sender};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+/// Time after which a use counts half as much (one week).
+pub const HALF_LIFE_SECS: f64 = 7.0 * 24.0 * 60.0 * 60.0;
+
+/// Entries whose decayed score drops below this are forgotten on save.
+const MIN_SCORE: f64 = 0.01;
+
+/// Maximum number of entries kept on disk.
+const MAX_ENTRIES: usize = 1000;
+
+/// Weight of frecency relative to the fuzzy match score.
+const FRECENCY_WEIGHT: f64 = 25.0;
+
+/// Usage record for a single item.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct HistoryEntry {
+    /// Decayed score as of `last_used`.
+    score: f64,
+    /// Unix timestamp (seconds) of the last use.
+    last_used: u64,
+    /// Total number of uses, for display and debugging.
+    count: u32,
+}
+
+impl HistoryEntry {
+    /// Score decayed to the given time.
+    fn score_at(&self, now: u64) -> f64 {
+        let age = now.saturating_sub(self.last_used) as f64;
+        self.score * 0.5_f64.powf(age / HALF_LIFE_SECS)
+    }
+}
+
+/// Launch history keyed by [`ListItem::history_key`].
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    entries: HashMap<String, HistoryEntry>,
+    /// Where the history is persisted. None keeps it in memory only.
+    path: Option<PathBuf>,
+}
+
+impl History {
+    /// Create an empty in-memory history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load the history from a file.
+    ///
+    /// A missing or unreadable file yields an empty history that will be
+    /// written to `path` on the next save.
+    pub fn load(path: &Path) -> Self {
+        let entries = match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                warn!(%e, path = %path.display(), "Ignoring corrupt history file");
+                HashMap::new()
+            }),
+            Err(_) => HashMap::new(),
+        };
+
+        Self {
+            entries,
+            path: Some(path.to_path_buf()),
+        }
+    }
+
+    /// Record a use of `key` at time `now`.
+    pub fn record(&mut self, key: &str, now: u64) {
+        let entry = self.entries.entry(key.to_string()).or_insert(HistoryEntry {
+            score: 0.0,
+            last_used: now,
+            count: 0,
+        });
+
+        entry.score = entry.score_at(now) + 1.0;
+        entry.last_used = now;
+        entry.count = entry.count.saturating_add(1);
+    }
+
+    /// Get the frecency of `key` at time `now`. Unknown keys score 0.
+    pub fn frecency(&self, key: &str, now: u64) -> f64 {
+        self.entries.get(key).map_or(0.0, |e| e.score_at(now))
+    }
+
+    /// Get the number of recorded uses of `key`.
+    pub fn count(&self, key: &str) -> u32 {
+        self.entries.get(key).map_or(0, |e| e.count)
+    }
+
+    /// Forget all recorded uses.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Drop stale entries and persist the history, if it has a path.
+    pub fn save(&mut self, now: u64) -> anyhow::Result<()> {
+        self.prune(now);
+
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        write_json_atomic(path, &self.entries, None)
+    }
+
+    /// Remove entries that have decayed away and cap the number of entries.
+    fn prune(&mut self, now: u64) {
+        self.entries.retain(|_, e| e.score_at(now) >= MIN_SCORE);
+
+        if self.entries.len() > MAX_ENTRIES {
+            let mut scores: Vec<(String, f64)> = self
+                .entries
+                .iter()
+                .map(|(k, e)| (k.clone(), e.score_at(now)))
+                .collect();
+            scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+            for (key, _) in scores.into_iter().skip(MAX_ENTRIES) {
+                self.entries.remove(&key);
+            }
+        }
+    }
+}
+
+/// Convert a frecency into a bonus added to fuzzy match scores.
+///
+/// Logarithmic so a handful of uses matters, but a heavily used item
+/// cannot outrank a much better textual match.
+pub fn frecency_boost(frecency: f64) -> i64 {
+    (frecency.ln_1p() * FRECENCY_WEIGHT) as i64
+}
+
+/// Get the path of the history file.
+///
+/// Uses `$XDG_STATE_HOME/zlaunch/history.json`, falling back to the local
+/// data directory on platforms without a state directory.
+pub fn history_path() -> PathBuf {
+    dirs::state_dir()
+        .or_else(dirs::data_local_dir)
+        .unwrap_or_else(std::env::temp_dir)
+        .join("zlaunch")
+        .join("history.json")
+}
+
+/// Current Unix time in seconds.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Write `value` as JSON to `path`, creating its directory.
+///
+/// The JSON is written to a temporary file that is renamed over `path`, so
+/// a crash never leaves a truncated file behind. `mode` is passed on to
+/// [`write_file`].
+pub fn write_json_atomic(
+    path: &Path,
+    value: &impl Serialize,
+    mode: Option<u32>,
+) -> anyhow::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let tmp = path.with_extension("json.tmp");
+    write_file(&tmp, &serde_json::to_vec(value)?, mode)?;
+    std::fs::rename(&tmp, path)?;
+
+    Ok(())
+}
+
+/// Write a file, with the permissions `mode` on Unix if given.
+///
+/// With a mode, an existing file is replaced rather than truncated, so it
+/// never keeps broader permissions it was created with.
+pub fn write_file(path: &Path, data: &[u8], mode: Option<u32>) -> std::io::Result<()> {
+    let Some(mode) = mode else {
+        return std::fs::write(path, data);
+    };
+
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+        _ => {}
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, mode);
+    #[cfg(not(unix))]
+    let _ = mode;
+    std::io::Write::write_all(&mut options.open(path)?, data)
+}
+
+lazy_static::lazy_static! {
+    /// Global launch history, loaded on first use.
+    static ref HISTORY: Mutex<History> = Mutex::new(History::load(&history_path()));
+
+    /// Wakes the thread persisting the history after a change.
+    static ref SAVER: Sender<()> = spawn_saver();
+}
+
+/// Start the thread persisting the global history.
+///
+/// Changes arriving while a save runs are written together by the next one.
+fn spawn_saver() -> Sender<()> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        while rx.recv().is_ok() {
+            while rx.try_recv().is_ok() {}
+
+            // Write a snapshot so searches are not blocked on the disk
+            let now = now();
+            let mut snapshot = {
+                let mut history = HISTORY.lock().unwrap();
+                history.prune(now);
+                history.clone()
+            };
+            if let Err(e) = snapshot.save(now) {
+                warn!(%e, "Failed to save launch history");
+            }
+        }
+    });
+    tx
+}
+
+/// Record that an item was confirmed.
+///
+/// The history is persisted on a background thread. Does nothing if
+/// history is disabled in the config.
+pub fn record_use(item: &ListItem) {
+    if !config().history.enabled {
+        return;
+    }
+
+    HISTORY.lock().unwrap().record(&item.history_key(), now());
+    let _ = SAVER.send(());
+}
+
+/// Compute the frecency of every item, in the same order as `items`.
+///
+/// Returns all zeros if history is disabled in the config.
+pub fn frecency_scores(items: &[ListItem]) -> Vec<f64> {
+    if !config().history.enabled {
+        return vec![0.0; items.len()];
+    }
+
+    let now = now();
+    let history = HISTORY.lock().unwrap();
+    items
+        .iter()
+        .map(|item| history.frecency(&item.history_key(), now))
+        .collect()
+}
+
+/// Forget all recorded uses and delete them from disk.
+pub fn reset_history() {
+    HISTORY.lock().unwrap().clear();
+    let _ = SAVER.send(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: u64 = 24 * 60 * 60;
+
+    #[test]
+    fn test_unknown_key_scores_zero() {
+        let history = History::new();
+        assert_eq!(history.frecency("app:firefox", 0), 0.0);
+        assert_eq!(history.count("app:firefox"), 0);
+    }
+
+    #[test]
+    fn test_score_decays_by_half_life() {
+        let mut history = History::new();
+        history.record("app:firefox", 0);
+        assert_eq!(history.frecency("app:firefox", 0), 1.0);
+        assert!((history.frecency("app:firefox", 7 * DAY) - 0.5).abs() < 1e-9);
+        assert!((history.frecency("app:firefox", 14 * DAY) - 0.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_frequency_and_recency() {
+        let mut history = History::new();
+
+        // Used ten times a month ago
+        for i in 0..10 {
+            history.record("app:gimp", i);
+        }
+        // Used three times this week
+        for day in 0..3 {
+            history.record("app:firefox", 28 * DAY + day * DAY);
+        }
+        // Used once, a month ago
+        history.record("app:xterm", 0);
+
+        let now = 31 * DAY;
+        let gimp = history.frecency("app:gimp", now);
+        let firefox = history.frecency("app:firefox", now);
+        let xterm = history.frecency("app:xterm", now);
+
+        assert!(firefox > gimp, "{} > {}", firefox, gimp);
+        assert!(gimp > xterm, "{} > {}", gimp, xterm);
+        assert_eq!(history.count("app:gimp"), 10);
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("zlaunch-history-test-{}", std::process::id()));
+        let path = dir.join("history.json");
+
+        let mut history = History::load(&path);
+        history.record("app:firefox", 100);
+        history.record("app:firefox", 200);
+        history.save(200).unwrap();
+
+        let loaded = History::load(&path);
+        assert_eq!(loaded.count("app:firefox"), 2);
+        assert!(
+            (loaded.frecency("app:firefox", 300) - history.frecency("app:firefox", 300)).abs()
+                < 1e-9
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_prune_forgets_stale_entries() {
+        let mut history = History::new();
+        history.record("app:old", 0);
+        history.record("app:new", 365 * DAY);
+        history.save(365 * DAY).unwrap();
+
+        assert_eq!(history.count("app:old"), 0);
+        assert_eq!(history.count("app:new"), 1);
+    }
+
+    #[test]
+    fn test_boost_is_monotonic_and_bounded() {
+        assert_eq!(frecency_boost(0.0), 0);
+        assert!(frecency_boost(1.0) < frecency_boost(5.0));
+        assert!(frecency_boost(5.0) < frecency_boost(50.0));
+        assert!(frecency_boost(1000.0) < 200);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::ipc::commands::{Command, Response};
-use std::io::{Read, Write};
+use crate::ipc::protocol::{Hello, PROTOCOL_VERSION, read_message, write_message};
+use std::io::{BufReader, Read, Write};
 
 #[cfg(unix)]
 use crate::ipc::server::get_socket_path;
@@ -13,31 +14,33 @@ use std::net::TcpStream;
 
 #[cfg(unix)]
 pub fn send_command(cmd: Command) -> anyhow::Result<Response> {
-    let socket_path = get_socket_path();
-    let mut stream = UnixStream::connect(&socket_path)?;
-
-    let msg = serde_json::to_vec(&cmd)?;
-    stream.write_all(&msg)?;
-
-    let mut buf = [0u8; 1024];
-    let n = stream.read(&mut buf)?;
-    let response: Response = serde_json::from_slice(&buf[..n])?;
-
-    Ok(response)
+    let stream = UnixStream::connect(get_socket_path())?;
+    exchange(stream, &cmd)
 }
 
 #[cfg(windows)]
 pub fn send_command(cmd: Command) -> anyhow::Result<Response> {
-    let mut stream = TcpStream::connect(get_socket_addr())?;
-
-    let msg = serde_json::to_vec(&cmd)?;
-    stream.write_all(&msg)?;
-
-    let mut buf = [0u8; 1024];
-    let n = stream.read(&mut buf)?;
-    let response: Response = serde_json::from_slice(&buf[..n])?;
+    let stream = TcpStream::connect(get_socket_addr())?;
+    exchange(stream, &cmd)
+}
 
-    Ok(response)
+/// Perform the version handshake, send a command and wait for its response.
+pub fn exchange<S: Read + Write>(stream: S, cmd: &Command) -> anyhow::Result<Response> {
+    let mut stream = BufReader::new(stream);
+
+    write_message(stream.get_mut(), &Hello::current())?;
+    let hello: Hello = read_message(&mut stream)?;
+    if hello.version != PROTOCOL_VERSION {
+        anyhow::bail!(
+            "Daemon uses IPC protocol version {}, but this client uses version {}. \
+             Restart the daemon with: zlaunch quit && zlaunch",
+            hello.version,
+            PROTOCOL_VERSION
+        );
+    }
+
+    write_message(stream.get_mut(), cmd)?;
+    Ok(read_message(&mut stream)?)
 }
 
 #[cfg(unix)]
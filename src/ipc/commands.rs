@@ -1,15 +1,168 @@
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::compositor::WindowInfo;
+use crate::dmenu::{DmenuRequest, DmenuSelection};
+use crate::items::ApplicationItem;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Command {
-    Show,
+    Show(ShowOptions),
     Hide,
-    Toggle,
+    Toggle(ShowOptions),
+    Reload,
+    ResetHistory,
     Quit,
+    /// Report the daemon state
+    Status,
+    /// List the applications the launcher currently knows about
+    ListApplications,
+    /// List the open windows reported by the compositor
+    ListWindows,
+    /// Let the user pick from the given entries
+    Dmenu(DmenuRequest),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Response {
     Ok,
     Error(String),
+    Status(DaemonStatus),
+    Applications(Vec<ApplicationSummary>),
+    Windows(Vec<WindowInfo>),
+    /// Entries picked for a [`Command::Dmenu`]
+    Selected(Vec<DmenuSelection>),
+    /// The user closed the launcher without picking anything
+    Cancelled,
+}
+
+/// How the launcher should open, given with `show` or `toggle`.
+/// Only applies when the launcher window is opened.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShowOptions {
+    /// View to open directly, the full launcher if unset
+    pub mode: Option<LaunchMode>,
+    /// Text to prefill the search input with
+    pub query: Option<String>,
+}
+
+/// View the launcher opens into.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LaunchMode {
+    /// Only applications
+    Apps,
+    /// Only open windows, e.g. as a window switcher
+    Windows,
+    /// The emoji picker
+    Emojis,
+    /// Only calculator results
+    Calc,
+    /// A submenu identified by its id
+    Submenu(String),
+}
+
+impl FromStr for LaunchMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "" => Err("mode must not be empty".to_string()),
+            "apps" => Ok(Self::Apps),
+            "windows" => Ok(Self::Windows),
+            "emojis" => Ok(Self::Emojis),
+            "calc" => Ok(Self::Calc),
+            id => Ok(Self::Submenu(id.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for LaunchMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Apps => write!(f, "apps"),
+            Self::Windows => write!(f, "windows"),
+            Self::Emojis => write!(f, "emojis"),
+            Self::Calc => write!(f, "calc"),
+            Self::Submenu(id) => write!(f, "{}", id),
+        }
+    }
+}
+
+/// Snapshot of the daemon state returned by [`Command::Status`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaemonStatus {
+    /// Version of the running daemon
+    pub version: String,
+    /// Whether the launcher window is open
+    pub visible: bool,
+    /// Name of the detected compositor
+    pub compositor: String,
+    /// Number of applications available
+    pub applications: usize,
+    /// Number of open windows, if the compositor could be queried
+    pub windows: Option<usize>,
+    /// Number of system actions and user-defined commands
+    pub actions: usize,
+}
+
+/// An application entry returned by [`Command::ListApplications`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApplicationSummary {
+    pub id: String,
+    pub name: String,
+    pub exec: String,
+    pub description: Option<String>,
+    pub desktop_path: PathBuf,
+}
+
+impl From<&ApplicationItem> for ApplicationSummary {
+    fn from(app: &ApplicationItem) -> Self {
+        Self {
+            id: app.id.clone(),
+            name: app.name.clone(),
+            exec: app.exec.clone(),
+            description: app.description.clone(),
+            desktop_path: app.desktop_path.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_launch_mode() {
+        for mode in ["apps", "windows", "emojis", "calc"] {
+            assert_eq!(mode.parse::<LaunchMode>().unwrap().to_string(), mode);
+        }
+        assert_eq!(
+            "submenu-emojis".parse::<LaunchMode>(),
+            Ok(LaunchMode::Submenu("submenu-emojis".to_string()))
+        );
+        assert!(" ".parse::<LaunchMode>().is_err());
+    }
+
+    #[test]
+    fn test_show_options_roundtrip() {
+        let cmd = Command::Show(ShowOptions {
+            mode: Some(LaunchMode::Windows),
+            query: Some("fire".to_string()),
+        });
+        let json = serde_json::to_string(&cmd).unwrap();
+        match serde_json::from_str(&json).unwrap() {
+            Command::Show(options) => {
+                assert_eq!(options.mode, Some(LaunchMode::Windows));
+                assert_eq!(options.query.as_deref(), Some("fire"));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+
+        // Missing fields fall back to the defaults
+        let options: ShowOptions = serde_json::from_str("{}").unwrap();
+        assert_eq!(options, ShowOptions::default());
+    }
 }
@@ -1,7 +1,8 @@
 pub mod client;
 pub mod commands;
+pub mod protocol;
 pub mod server;
 
 pub use client::send_command;
-pub use commands::Command;
-pub use server::IpcServer;
+pub use commands::{ApplicationSummary, Command, DaemonStatus, LaunchMode, Response, ShowOptions};
+pub use server::{IpcRequest, IpcServer};
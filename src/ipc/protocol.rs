@@ -0,0 +1,114 @@
+//! Wire format shared by the IPC client and server.
+//!
+//! Every message is a single JSON document terminated by a newline. A
+//! connection starts with both sides exchanging a [`Hello`] carrying their
+//! protocol version. The client then sends one [`Command`] and the server
+//! answers with one [`Response`] once the daemon has handled it.
+//!
+//! [`Command`]: crate::ipc::Command
+//! [`Response`]: crate::ipc::Response
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, Read, Write};
+
+/// Version of the IPC protocol. Bump on incompatible changes to the
+/// message types so old clients get a clear error instead of garbage.
+pub const PROTOCOL_VERSION: u32 = 2;
+
+/// Largest message accepted, to bound memory use on malformed input.
+const MAX_MESSAGE_LEN: u64 = 16 * 1024 * 1024;
+
+/// First message sent by each side of a connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Hello {
+    pub version: u32,
+}
+
+impl Hello {
+    /// A greeting for this build's protocol version.
+    pub fn current() -> Self {
+        Self {
+            version: PROTOCOL_VERSION,
+        }
+    }
+}
+
+/// Write a message followed by a newline.
+pub fn write_message<W: Write, T: Serialize>(writer: &mut W, message: &T) -> io::Result<()> {
+    let mut buf = serde_json::to_vec(message)?;
+    buf.push(b'\n');
+    writer.write_all(&buf)?;
+    writer.flush()
+}
+
+/// Read one newline-terminated message.
+pub fn read_message<R: BufRead, T: DeserializeOwned>(reader: &mut R) -> io::Result<T> {
+    let mut line = Vec::new();
+    reader
+        .by_ref()
+        .take(MAX_MESSAGE_LEN + 1)
+        .read_until(b'\n', &mut line)?;
+
+    if line.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "connection closed",
+        ));
+    }
+    if line.last() != Some(&b'\n') {
+        return Err(if line.len() as u64 > MAX_MESSAGE_LEN {
+            io::Error::new(io::ErrorKind::InvalidData, "message too large")
+        } else {
+            io::Error::new(io::ErrorKind::UnexpectedEof, "truncated message")
+        });
+    }
+
+    Ok(serde_json::from_slice(&line)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_roundtrip_multiple_messages() {
+        let mut buf = Vec::new();
+        write_message(&mut buf, &Hello::current()).unwrap();
+        write_message(&mut buf, &"line one\nline two".to_string()).unwrap();
+
+        // Embedded newlines are escaped, so each message is one line
+        assert_eq!(buf.iter().filter(|&&b| b == b'\n').count(), 2);
+
+        let mut reader = Cursor::new(buf);
+        let hello: Hello = read_message(&mut reader).unwrap();
+        let text: String = read_message(&mut reader).unwrap();
+        assert_eq!(hello.version, PROTOCOL_VERSION);
+        assert_eq!(text, "line one\nline two");
+
+        let err = read_message::<_, Hello>(&mut reader).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_large_message() {
+        let names: Vec<String> = (0..10_000).map(|i| format!("Application {}", i)).collect();
+        let mut buf = Vec::new();
+        write_message(&mut buf, &names).unwrap();
+        assert!(buf.len() > 100_000);
+
+        let decoded: Vec<String> = read_message(&mut Cursor::new(buf)).unwrap();
+        assert_eq!(decoded, names);
+    }
+
+    #[test]
+    fn test_invalid_messages() {
+        let err =
+            read_message::<_, Hello>(&mut Cursor::new(b"{\"version\":1}".to_vec())).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+        let err = read_message::<_, Hello>(&mut Cursor::new(b"not json\n".to_vec())).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -1,6 +1,8 @@
 use crate::ipc::commands::{Command, Response};
-use std::io::{Read, Write};
+use crate::ipc::protocol::{Hello, PROTOCOL_VERSION, read_message, write_message};
+use std::io::{BufReader, Read, Write};
 use std::sync::Arc;
+use std::time::Duration;
 
 // Platform-specific imports and types
 #[cfg(unix)]
@@ -11,12 +13,21 @@ use std::path::PathBuf;
 #[cfg(windows)]
 use std::net::{TcpListener, TcpStream};
 
+/// How long to wait for a client to send its next message.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Platform-specific listener type alias
 #[cfg(unix)]
 pub type PlatformListener = UnixListener;
 #[cfg(windows)]
 pub type PlatformListener = TcpListener;
 
+/// Platform-specific stream type alias
+#[cfg(unix)]
+pub type PlatformStream = UnixStream;
+#[cfg(windows)]
+pub type PlatformStream = TcpStream;
+
 /// IPC server that listens for commands from external clients.
 pub struct IpcServer {
     listener: Arc<PlatformListener>,
@@ -70,45 +81,63 @@ impl IpcServer {
         Arc::clone(&self.listener)
     }
 
-    /// Blocking accept - waits for a connection and returns the command.
+    /// Blocking accept - waits for a connection and returns the stream.
     /// This should be called from a background thread.
-    #[cfg(unix)]
-    pub fn accept_blocking(listener: &UnixListener) -> Option<Command> {
-        match listener.accept() {
-            Ok((mut stream, _)) => {
-                let mut buf = [0u8; 1024];
-                let n = stream.read(&mut buf).ok()?;
-                let cmd: Command = serde_json::from_slice(&buf[..n]).ok()?;
-
-                let response = Response::Ok;
-                let response_bytes = serde_json::to_vec(&response).ok()?;
-                let _ = stream.write_all(&response_bytes);
-
-                Some(cmd)
-            }
-            Err(_) => None,
-        }
+    pub fn accept_blocking(listener: &PlatformListener) -> Option<PlatformStream> {
+        let (stream, _) = listener.accept().ok()?;
+        // Don't let a client that never sends anything hold its thread forever
+        stream.set_read_timeout(Some(READ_TIMEOUT)).ok()?;
+        Some(stream)
     }
+}
 
-    #[cfg(windows)]
-    pub fn accept_blocking(listener: &TcpListener) -> Option<Command> {
-        match listener.accept() {
-            Ok((mut stream, _)) => {
-                let mut buf = [0u8; 1024];
-                let n = stream.read(&mut buf).ok()?;
-                let cmd: Command = serde_json::from_slice(&buf[..n]).ok()?;
-
-                let response = Response::Ok;
-                let response_bytes = serde_json::to_vec(&response).ok()?;
-                let _ = stream.write_all(&response_bytes);
-
-                Some(cmd)
-            }
-            Err(_) => None,
-        }
+/// A command received from a client, paired with the channel its reply is sent on.
+#[derive(Debug, Clone)]
+pub struct IpcRequest {
+    pub command: Command,
+    reply: flume::Sender<Response>,
+}
+
+impl IpcRequest {
+    /// Create a request and the receiver its response will arrive on.
+    pub fn new(command: Command) -> (Self, flume::Receiver<Response>) {
+        let (reply, rx) = flume::bounded(1);
+        (Self { command, reply }, rx)
+    }
+
+    /// Send the response back to the waiting connection.
+    pub fn respond(self, response: Response) {
+        // The client may have gone away, nothing left to do then
+        let _ = self.reply.send(response);
     }
 }
 
+/// Serve a single client connection.
+///
+/// Performs the version handshake, reads one command and writes back the
+/// response produced by `dispatch`.
+pub fn handle_connection<S: Read + Write>(
+    stream: S,
+    dispatch: impl FnOnce(Command) -> Response,
+) -> anyhow::Result<()> {
+    let mut stream = BufReader::new(stream);
+
+    let hello: Hello = read_message(&mut stream)?;
+    write_message(stream.get_mut(), &Hello::current())?;
+    if hello.version != PROTOCOL_VERSION {
+        anyhow::bail!(
+            "Client uses protocol version {}, expected {}",
+            hello.version,
+            PROTOCOL_VERSION
+        );
+    }
+
+    let cmd: Command = read_message(&mut stream)?;
+    let response = dispatch(cmd);
+    write_message(stream.get_mut(), &response)?;
+    Ok(())
+}
+
 #[cfg(unix)]
 impl Drop for IpcServer {
     fn drop(&mut self) {
@@ -129,3 +158,50 @@ pub fn get_socket_addr() -> &'static str {
     // Use a fixed localhost port for IPC on Windows
     "127.0.0.1:47392"
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::ipc::client::exchange;
+    use std::io::BufReader;
+
+    #[test]
+    fn test_command_roundtrip() {
+        let (client, server) = UnixStream::pair().unwrap();
+        let handle = std::thread::spawn(move || {
+            handle_connection(server, |cmd| match cmd {
+                Command::ListApplications => Response::Error("x".repeat(4096)),
+                _ => Response::Ok,
+            })
+        });
+
+        let response = exchange(client, &Command::ListApplications).unwrap();
+        handle.join().unwrap().unwrap();
+        match response {
+            Response::Error(msg) => assert_eq!(msg.len(), 4096),
+            other => panic!("unexpected response: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_version_mismatch_rejected() {
+        let (client, server) = UnixStream::pair().unwrap();
+        let handle = std::thread::spawn(move || {
+            handle_connection(server, |_| panic!("command must not be dispatched"))
+        });
+
+        let mut client = BufReader::new(client);
+        write_message(client.get_mut(), &Hello { version: 999 }).unwrap();
+        let hello: Hello = read_message(&mut client).unwrap();
+        assert_eq!(hello.version, PROTOCOL_VERSION);
+
+        assert!(handle.join().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_closed_connection() {
+        let (client, server) = UnixStream::pair().unwrap();
+        drop(client);
+        assert!(handle_connection(server, |_| Response::Ok).is_err());
+    }
+}
@@ -1,6 +1,5 @@
 /// The kind of action to perform.
-/// This is a placeholder for future implementation.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ActionKind {
     /// Shutdown the system
     Shutdown,
@@ -12,12 +11,25 @@ pub enum ActionKind {
     Lock,
     /// Log out of the session
     Logout,
-    /// Custom command execution
-    Command(String),
+    /// Custom shell command from the config
+    Command {
+        command: String,
+        /// Run in a terminal emulator
+        terminal: bool,
+        /// Capture stdout and show it instead of running in the background
+        show_output: bool,
+    },
+}
+
+impl ActionKind {
+    /// Check if the action ends the session or powers off the machine,
+    /// losing unsaved work. These require confirmation.
+    pub fn is_destructive(&self) -> bool {
+        matches!(self, Self::Shutdown | Self::Reboot | Self::Logout)
+    }
 }
 
 /// An action item representing a functional command (shutdown, reboot, etc.).
-/// This is a placeholder for future implementation.
 #[derive(Clone, Debug)]
 pub struct ActionItem {
     pub id: String,
@@ -1,4 +1,4 @@
-use crate::desktop::DesktopEntry;
+use crate::desktop::{DesktopAction, DesktopEntry};
 use std::path::PathBuf;
 
 /// An application item representing a desktop application.
@@ -7,17 +7,23 @@ pub struct ApplicationItem {
     pub id: String,
     pub name: String,
     pub exec: String,
+    /// Icon name from the desktop file, used for the `%i` field code
+    pub icon: Option<String>,
     pub icon_path: Option<PathBuf>,
     pub description: Option<String>,
     pub terminal: bool,
     pub desktop_path: PathBuf,
+    /// Secondary actions declared by the desktop file.
+    pub actions: Vec<DesktopAction>,
 }
 
 impl ApplicationItem {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id: String,
         name: String,
         exec: String,
+        icon: Option<String>,
         icon_path: Option<PathBuf>,
         description: Option<String>,
         terminal: bool,
@@ -27,12 +33,62 @@ impl ApplicationItem {
             id,
             name,
             exec,
+            icon,
             icon_path,
             description,
             terminal,
             desktop_path,
+            actions: Vec::new(),
         }
     }
+
+    /// Builder method to set the desktop actions.
+    pub fn with_actions(mut self, actions: Vec<DesktopAction>) -> Self {
+        self.actions = actions;
+        self
+    }
+
+    /// Check if this application declares any desktop actions.
+    pub fn has_actions(&self) -> bool {
+        !self.actions.is_empty()
+    }
+
+    /// Build one launchable item per desktop action.
+    ///
+    /// Each item runs the action's own Exec line but keeps the application's
+    /// icon, terminal flag and desktop file, and is shown with the
+    /// application name as its description.
+    pub fn action_items(&self) -> Vec<ApplicationItem> {
+        self.actions
+            .iter()
+            .map(|action| ApplicationItem {
+                id: format!("{}:{}", self.id, action.id),
+                name: action.name.clone(),
+                exec: action.exec.clone(),
+                icon: action.icon.clone().or_else(|| self.icon.clone()),
+                icon_path: self.icon_path.clone(),
+                description: Some(self.name.clone()),
+                terminal: self.terminal,
+                desktop_path: self.desktop_path.clone(),
+                actions: Vec::new(),
+            })
+            .collect()
+    }
+
+    /// Convert back to a desktop entry for launching.
+    pub fn to_desktop_entry(&self) -> DesktopEntry {
+        DesktopEntry::new(
+            self.id.clone(),
+            self.name.clone(),
+            self.exec.clone(),
+            self.icon.clone(),
+            self.icon_path.clone(),
+            self.description.clone(),
+            vec![],
+            self.terminal,
+            self.desktop_path.clone(),
+        )
+    }
 }
 
 impl From<DesktopEntry> for ApplicationItem {
@@ -41,10 +97,12 @@ impl From<DesktopEntry> for ApplicationItem {
             id: entry.id,
             name: entry.name,
             exec: entry.exec,
+            icon: entry.icon,
             icon_path: entry.icon_path,
             description: entry.comment,
             terminal: entry.terminal,
             desktop_path: entry.path,
+            actions: entry.actions,
         }
     }
 }
@@ -55,10 +113,12 @@ impl From<&DesktopEntry> for ApplicationItem {
             id: entry.id.clone(),
             name: entry.name.clone(),
             exec: entry.exec.clone(),
+            icon: entry.icon.clone(),
             icon_path: entry.icon_path.clone(),
             description: entry.comment.clone(),
             terminal: entry.terminal,
             desktop_path: entry.path.clone(),
+            actions: entry.actions.clone(),
         }
     }
 }
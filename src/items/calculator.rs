@@ -16,12 +16,14 @@ pub struct CalculatorItem {
     pub clipboard_result: Option<String>,
     /// Whether this is an error result.
     pub is_error: bool,
+    /// The result this item shows, remembered as `ans` when copied.
+    pub result: CalcResult,
 }
 
 impl CalculatorItem {
     /// Create a new calculator item from a CalcResult.
     pub fn from_calc_result(result: CalcResult) -> Self {
-        match result {
+        match result.clone() {
             CalcResult::Success {
                 expression,
                 display_result,
@@ -33,6 +35,7 @@ impl CalculatorItem {
                 display_result,
                 clipboard_result: Some(clipboard_result),
                 is_error: false,
+                result,
             },
             CalcResult::Error {
                 expression,
@@ -43,10 +46,17 @@ impl CalculatorItem {
                 display_result: message,
                 clipboard_result: None,
                 is_error: true,
+                result,
             },
         }
     }
 
+    /// Set the identifier, for lists of several results.
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+
     /// Get the text to copy to clipboard.
     /// Returns the clipboard result for successful calculations,
     /// or the display result for errors (so user can still copy the error message).
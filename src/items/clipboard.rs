@@ -0,0 +1,139 @@
+use crate::clipboard::{ClipboardContent, ClipboardEntry};
+use std::path::PathBuf;
+
+/// Longest title shown and searched for a text entry, in characters.
+const MAX_TITLE_CHARS: usize = 200;
+
+/// Longest text shown in the preview, in characters.
+const MAX_PREVIEW_CHARS: usize = 2000;
+
+/// A clipboard history entry, restored to the clipboard when confirmed.
+#[derive(Clone, Debug)]
+pub struct ClipboardItem {
+    /// Internal ID for the list
+    pub id: String,
+    /// Id of the entry in the clipboard history
+    pub entry_id: u64,
+    /// Text on a single line, or "Image" for images
+    pub title: String,
+    /// Pre-computed description (e.g., "Pinned - 3 lines - 5 minutes ago")
+    pub description: String,
+    /// Start of the text, shown in the preview
+    pub preview: String,
+    /// File holding the image, for image entries
+    pub image_path: Option<PathBuf>,
+    pub pinned: bool,
+}
+
+impl ClipboardItem {
+    /// Create a ClipboardItem from a history entry at unix time `now`.
+    pub fn from_entry(entry: &ClipboardEntry, image_path: Option<PathBuf>, now: u64) -> Self {
+        let (title, preview, kind) = match &entry.content {
+            ClipboardContent::Text { text } => {
+                let title: String = text
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+                    .chars()
+                    .take(MAX_TITLE_CHARS)
+                    .collect();
+                let preview = text.chars().take(MAX_PREVIEW_CHARS).collect();
+                let kind = match text.trim_end().lines().count() {
+                    0 | 1 => format!("{} characters", text.chars().count()),
+                    n => format!("{} lines", n),
+                };
+                (title, preview, kind)
+            }
+            ClipboardContent::Image {
+                mime_type, size, ..
+            } => {
+                let format = mime_type.trim_start_matches("image/").to_uppercase();
+                let kind = format!("{} image, {}", format, format_size(*size));
+                ("Image".to_string(), String::new(), kind)
+            }
+        };
+
+        let age = format_age(now.saturating_sub(entry.copied_at));
+        let description = if entry.pinned {
+            format!("Pinned - {} - {}", kind, age)
+        } else {
+            format!("{} - {}", kind, age)
+        };
+
+        Self {
+            id: format!("clipboard-{}", entry.id),
+            entry_id: entry.id,
+            title,
+            description,
+            preview,
+            image_path,
+            pinned: entry.pinned,
+        }
+    }
+}
+
+/// Format a byte count like `24 KB`.
+fn format_size(bytes: usize) -> String {
+    match bytes {
+        0..1024 => format!("{} bytes", bytes),
+        1024..1_048_576 => format!("{} KB", bytes / 1024),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+/// Format how long ago something happened, like `5 minutes ago`.
+fn format_age(secs: u64) -> String {
+    let (count, unit) = match secs {
+        0..60 => return "Just now".to_string(),
+        60..3600 => (secs / 60, "minute"),
+        3600..86400 => (secs / 3600, "hour"),
+        _ => (secs / 86400, "day"),
+    };
+    let plural = if count == 1 { "" } else { "s" };
+    format!("{} {}{} ago", count, unit, plural)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(content: ClipboardContent, pinned: bool) -> ClipboardEntry {
+        ClipboardEntry {
+            id: 7,
+            content,
+            copied_at: 1000,
+            pinned,
+        }
+    }
+
+    #[test]
+    fn test_text_entry() {
+        let text = ClipboardContent::Text {
+            text: "fn main() {\n    println!();\n}\n".to_string(),
+        };
+        let item = ClipboardItem::from_entry(&entry(text, true), None, 1000 + 7200);
+        assert_eq!(item.id, "clipboard-7");
+        assert_eq!(item.title, "fn main() { println!(); }");
+        assert_eq!(item.description, "Pinned - 3 lines - 2 hours ago");
+        assert!(item.preview.starts_with("fn main() {\n"));
+
+        let text = ClipboardContent::Text {
+            text: "hello".to_string(),
+        };
+        let item = ClipboardItem::from_entry(&entry(text, false), None, 1030);
+        assert_eq!(item.description, "5 characters - Just now");
+    }
+
+    #[test]
+    fn test_image_entry() {
+        let image = ClipboardContent::Image {
+            mime_type: "image/png".to_string(),
+            file: "7.png".to_string(),
+            size: 24 * 1024,
+            hash: 0,
+        };
+        let item = ClipboardItem::from_entry(&entry(image, false), None, 1060);
+        assert_eq!(item.title, "Image");
+        assert_eq!(item.description, "PNG image, 24 KB - 1 minute ago");
+    }
+}
@@ -0,0 +1,34 @@
+use crate::dmenu::DmenuSelection;
+use std::path::PathBuf;
+
+/// A line given to `zlaunch dmenu`.
+#[derive(Clone, Debug)]
+pub struct DmenuItem {
+    pub id: String,
+    /// Position of the line in the input
+    pub index: usize,
+    pub text: String,
+    pub icon_path: Option<PathBuf>,
+    /// Marked for a multi-select confirm
+    pub marked: bool,
+}
+
+impl DmenuItem {
+    pub fn new(index: usize, text: String, icon_path: Option<PathBuf>) -> Self {
+        Self {
+            id: format!("dmenu:{}", index),
+            index,
+            text,
+            icon_path,
+            marked: false,
+        }
+    }
+
+    /// The selection reported back when this entry is chosen.
+    pub fn selection(&self) -> DmenuSelection {
+        DmenuSelection {
+            index: Some(self.index),
+            text: self.text.clone(),
+        }
+    }
+}
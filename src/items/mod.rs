@@ -1,14 +1,20 @@
 mod action;
 mod application;
 mod calculator;
+mod clipboard;
+mod dmenu;
 mod submenu;
 mod window;
+mod workspace;
 
 pub use action::{ActionItem, ActionKind};
 pub use application::ApplicationItem;
 pub use calculator::CalculatorItem;
+pub use clipboard::ClipboardItem;
+pub use dmenu::DmenuItem;
 pub use submenu::{SubmenuItem, SubmenuLayout};
-pub use window::WindowItem;
+pub use window::{WindowItem, window_workspaces, workspace_targets};
+pub use workspace::{WorkspaceItem, workspace_items};
 
 use std::path::PathBuf;
 
@@ -20,12 +26,18 @@ pub enum ListItem {
     Application(ApplicationItem),
     /// An open window (for window switching)
     Window(WindowItem),
+    /// A workspace (for workspace switching)
+    Workspace(WorkspaceItem),
     /// A functional action (shutdown, reboot, etc.)
     Action(ActionItem),
     /// A submenu that opens a nested view
     Submenu(SubmenuItem),
     /// A calculator result
     Calculator(CalculatorItem),
+    /// A line given to `zlaunch dmenu`
+    Dmenu(DmenuItem),
+    /// A clipboard history entry
+    Clipboard(ClipboardItem),
 }
 
 impl ListItem {
@@ -34,9 +46,12 @@ impl ListItem {
         match self {
             Self::Application(app) => &app.id,
             Self::Window(win) => &win.id,
+            Self::Workspace(ws) => &ws.id,
             Self::Action(act) => &act.id,
             Self::Submenu(sub) => &sub.id,
             Self::Calculator(calc) => &calc.id,
+            Self::Dmenu(entry) => &entry.id,
+            Self::Clipboard(clip) => &clip.id,
         }
     }
 
@@ -45,9 +60,12 @@ impl ListItem {
         match self {
             Self::Application(app) => &app.name,
             Self::Window(win) => &win.title,
+            Self::Workspace(ws) => &ws.name,
             Self::Action(act) => &act.name,
             Self::Submenu(sub) => &sub.name,
             Self::Calculator(calc) => &calc.expression,
+            Self::Dmenu(entry) => &entry.text,
+            Self::Clipboard(clip) => &clip.title,
         }
     }
 
@@ -56,9 +74,12 @@ impl ListItem {
         match self {
             Self::Application(app) => app.description.as_deref(),
             Self::Window(win) => Some(&win.description),
+            Self::Workspace(ws) => Some(&ws.description),
             Self::Action(act) => act.description.as_deref(),
             Self::Submenu(sub) => sub.description.as_deref(),
             Self::Calculator(calc) => Some(&calc.display_result),
+            Self::Dmenu(_) => None,
+            Self::Clipboard(clip) => Some(&clip.description),
         }
     }
 
@@ -67,9 +88,29 @@ impl ListItem {
         match self {
             Self::Application(app) => app.icon_path.as_ref(),
             Self::Window(win) => win.icon_path.as_ref(),
+            Self::Workspace(_) => None,  // Workspaces use a built-in icon
             Self::Action(_) => None,     // Actions use icon names, not paths
             Self::Submenu(_) => None,    // Submenus use icon names, not paths
             Self::Calculator(_) => None, // Calculator uses custom icon
+            Self::Dmenu(entry) => entry.icon_path.as_ref(),
+            Self::Clipboard(clip) => clip.image_path.as_ref(),
+        }
+    }
+
+    /// Get the key under which uses of this item are recorded in the launch history.
+    ///
+    /// Windows are keyed by application rather than by address, since
+    /// addresses change every time the window is reopened.
+    pub fn history_key(&self) -> String {
+        match self {
+            Self::Application(app) => format!("app:{}", app.id),
+            Self::Window(win) => format!("window:{}", win.app_id),
+            Self::Workspace(ws) => format!("workspace:{}", ws.workspace),
+            Self::Action(act) => format!("action:{}", act.id),
+            Self::Submenu(sub) => format!("submenu:{}", sub.id),
+            Self::Calculator(calc) => format!("calc:{}", calc.expression),
+            Self::Dmenu(entry) => format!("dmenu:{}", entry.text),
+            Self::Clipboard(clip) => format!("clipboard:{}", clip.entry_id),
         }
     }
 
@@ -88,6 +129,11 @@ impl ListItem {
         matches!(self, Self::Window(_))
     }
 
+    /// Check if this item is a workspace.
+    pub fn is_workspace(&self) -> bool {
+        matches!(self, Self::Workspace(_))
+    }
+
     /// Check if this item is an action.
     pub fn is_action(&self) -> bool {
         matches!(self, Self::Action(_))
@@ -98,27 +144,44 @@ impl ListItem {
         matches!(self, Self::Calculator(_))
     }
 
+    /// Check if this item is a dmenu entry.
+    pub fn is_dmenu(&self) -> bool {
+        matches!(self, Self::Dmenu(_))
+    }
+
+    /// Check if this item is a clipboard history entry.
+    pub fn is_clipboard(&self) -> bool {
+        matches!(self, Self::Clipboard(_))
+    }
+
     /// Get the action label to display (e.g., "Open", "Switch", "Run").
     pub fn action_label(&self) -> &'static str {
         match self {
             Self::Application(_) => "Open",
-            Self::Window(_) => "Switch",
+            Self::Window(win) if win.action.is_some() => "Run",
+            Self::Window(_) | Self::Workspace(_) => "Switch",
             Self::Action(_) => "Run",
             Self::Submenu(_) => "Open",
             Self::Calculator(_) => "Copy",
+            Self::Dmenu(_) => "Select",
+            Self::Clipboard(_) => "Copy",
         }
     }
 
     /// Get the sort priority for this item type.
     /// Lower values appear first in the list.
-    /// Calculator (0) < Windows (1) < Commands (2) < Applications (3) < Actions (4)
+    /// Calculator (0) < Windows (1) < Workspaces (2) < Commands (3) < Applications (4)
+    /// < Actions (5) < dmenu entries (6) < clipboard entries (7)
     pub fn sort_priority(&self) -> u8 {
         match self {
             Self::Calculator(_) => 0,
             Self::Window(_) => 1,
-            Self::Submenu(_) => 2,
-            Self::Application(_) => 3,
-            Self::Action(_) => 4,
+            Self::Workspace(_) => 2,
+            Self::Submenu(_) => 3,
+            Self::Application(_) => 4,
+            Self::Action(_) => 5,
+            Self::Dmenu(_) => 6,
+            Self::Clipboard(_) => 7,
         }
     }
 
@@ -127,9 +190,12 @@ impl ListItem {
         match self {
             Self::Calculator(_) => "Calculator",
             Self::Window(_) => "Windows",
+            Self::Workspace(_) => "Workspaces",
             Self::Submenu(_) => "Commands",
             Self::Application(_) => "Applications",
             Self::Action(_) => "Actions",
+            Self::Dmenu(_) => "Entries",
+            Self::Clipboard(_) => "Clipboard",
         }
     }
 }
@@ -148,6 +214,12 @@ impl From<WindowItem> for ListItem {
     }
 }
 
+impl From<WorkspaceItem> for ListItem {
+    fn from(item: WorkspaceItem) -> Self {
+        Self::Workspace(item)
+    }
+}
+
 impl From<ActionItem> for ListItem {
     fn from(item: ActionItem) -> Self {
         Self::Action(item)
@@ -165,3 +237,15 @@ impl From<CalculatorItem> for ListItem {
         Self::Calculator(item)
     }
 }
+
+impl From<DmenuItem> for ListItem {
+    fn from(item: DmenuItem) -> Self {
+        Self::Dmenu(item)
+    }
+}
+
+impl From<ClipboardItem> for ListItem {
+    fn from(item: ClipboardItem) -> Self {
+        Self::Clipboard(item)
+    }
+}
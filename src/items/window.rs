@@ -1,4 +1,5 @@
-use crate::compositor::WindowInfo;
+use crate::compositor::{WindowAction, WindowCapabilities, WindowInfo, WorkspaceInfo};
+use crate::items::WorkspaceItem;
 use std::path::PathBuf;
 
 /// A window item representing an open window for window switching.
@@ -22,6 +23,8 @@ pub struct WindowItem {
     pub workspace: i32,
     /// Whether this window is currently focused
     pub focused: bool,
+    /// Operation run instead of focusing, for the items of the window actions view
+    pub action: Option<WindowAction>,
 }
 
 impl WindowItem {
@@ -35,7 +38,7 @@ impl WindowItem {
         workspace: i32,
         focused: bool,
     ) -> Self {
-        let description = format!("{} - Workspace {}", app_name, workspace);
+        let description = describe_window(&app_name, workspace, false);
         Self {
             id,
             address,
@@ -46,6 +49,7 @@ impl WindowItem {
             icon_path,
             workspace,
             focused,
+            action: None,
         }
     }
 
@@ -55,7 +59,7 @@ impl WindowItem {
     /// Icon resolution should be done externally and passed in.
     pub fn from_window_info(info: WindowInfo, icon_path: Option<PathBuf>) -> Self {
         let app_name = titlecase_app_name(&info.class);
-        let description = format!("{} - Workspace {}", app_name, info.workspace);
+        let description = describe_window(&app_name, info.workspace, info.scratchpad);
         Self {
             id: format!("window-{}", info.address),
             address: info.address,
@@ -66,8 +70,84 @@ impl WindowItem {
             icon_path,
             workspace: info.workspace,
             focused: info.focused,
+            action: None,
         }
     }
+
+    /// Build one item per operation the compositor supports on this window.
+    ///
+    /// Each item is shown with the window title as its description.
+    /// `workspaces` are offered as move targets, except the window's own.
+    pub fn action_items(
+        &self,
+        capabilities: WindowCapabilities,
+        workspaces: &[WorkspaceItem],
+    ) -> Vec<WindowItem> {
+        let moves = workspaces
+            .iter()
+            .filter(|workspace| workspace.workspace != self.workspace)
+            .map(|workspace| {
+                let title = format!("Move to {}", workspace.name);
+                (WindowAction::MoveToWorkspace(workspace.workspace), title)
+            });
+
+        [
+            WindowAction::ToggleFullscreen,
+            WindowAction::ToggleFloating,
+            WindowAction::Pin,
+        ]
+        .into_iter()
+        .map(|action| (action, action.to_string()))
+        .chain(moves)
+        .chain([(WindowAction::Close, WindowAction::Close.to_string())])
+        .filter(|(action, _)| capabilities.supports(*action))
+        .enumerate()
+        .map(|(index, (action, title))| WindowItem {
+            id: format!("{}:action-{}", self.id, index),
+            title,
+            description: self.title.clone(),
+            action: Some(action),
+            ..self.clone()
+        })
+        .collect()
+    }
+}
+
+/// Workspaces offered as move targets: every workspace up to the highest
+/// one in use, plus the next empty one.
+pub fn workspace_targets(workspaces: impl IntoIterator<Item = i32>) -> Vec<i32> {
+    let highest = workspaces.into_iter().max().unwrap_or(0).max(0);
+    (1..=highest + 1).collect()
+}
+
+/// Move targets for compositors that cannot list their workspaces: every
+/// workspace up to the highest one with a window on it, which are the
+/// only ones known to exist.
+pub fn window_workspaces(workspaces: impl IntoIterator<Item = i32>) -> Vec<WorkspaceItem> {
+    let highest = workspaces.into_iter().max().unwrap_or(0);
+    (1..=highest)
+        .map(|id| {
+            WorkspaceItem::from_workspace_info(&WorkspaceInfo {
+                id,
+                name: id.to_string(),
+                windows: 0,
+                focused: false,
+            })
+        })
+        .collect()
+}
+
+/// Describe a window by its application and workspace.
+/// Workspace 0 means the window has no single workspace, or the compositor
+/// does not report one.
+fn describe_window(app_name: &str, workspace: i32, scratchpad: bool) -> String {
+    if scratchpad {
+        format!("{} - Scratchpad", app_name)
+    } else if workspace == 0 {
+        app_name.to_string()
+    } else {
+        format!("{} - Workspace {}", app_name, workspace)
+    }
 }
 
 /// Convert an app class to a human-readable name.
@@ -83,3 +163,88 @@ fn titlecase_app_name(class: &str) -> String {
         Some(first) => first.to_uppercase().chain(chars).collect(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(workspace: i32) -> WindowItem {
+        let info = WindowInfo {
+            address: "0x1".to_string(),
+            title: "~/src — fish".to_string(),
+            class: "foot".to_string(),
+            workspace,
+            focused: false,
+            scratchpad: false,
+        };
+        WindowItem::from_window_info(info, None)
+    }
+
+    #[test]
+    fn test_describe_window() {
+        assert_eq!(describe_window("Foot", 2, false), "Foot - Workspace 2");
+        // Named Hyprland workspaces have negative ids
+        assert_eq!(
+            describe_window("Foot", -1337, false),
+            "Foot - Workspace -1337"
+        );
+        assert_eq!(describe_window("Foot", 0, true), "Foot - Scratchpad");
+        assert_eq!(describe_window("Foot", 0, false), "Foot");
+    }
+
+    #[test]
+    fn test_workspace_targets() {
+        assert_eq!(workspace_targets([2, -99, 4]), [1, 2, 3, 4, 5]);
+        assert_eq!(workspace_targets([-1]), [1]);
+        assert_eq!(workspace_targets([]), [1]);
+    }
+
+    #[test]
+    fn test_window_workspaces() {
+        let ids = |items: Vec<WorkspaceItem>| items.iter().map(|w| w.workspace).collect::<Vec<_>>();
+        assert_eq!(ids(window_workspaces([2, 0, 4])), [1, 2, 3, 4]);
+        assert!(window_workspaces([0]).is_empty());
+    }
+
+    #[test]
+    fn test_action_items_follow_capabilities() {
+        let capabilities = WindowCapabilities {
+            close: true,
+            move_to_workspace: true,
+            fullscreen: true,
+            ..Default::default()
+        };
+        let workspaces: Vec<WorkspaceItem> = [(1, "1"), (2, "2"), (-1337, "web")]
+            .into_iter()
+            .map(|(id, name)| {
+                WorkspaceItem::from_workspace_info(&WorkspaceInfo {
+                    id,
+                    name: name.to_string(),
+                    windows: 1,
+                    focused: false,
+                })
+            })
+            .collect();
+        let items = window(2).action_items(capabilities, &workspaces);
+
+        let titles: Vec<&str> = items.iter().map(|item| item.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            [
+                "Toggle Fullscreen",
+                "Move to Workspace 1",
+                // Named workspaces are moved to by name
+                "Move to Workspace web",
+                "Close Window",
+            ]
+        );
+        assert!(items.iter().all(|item| item.description == "~/src — fish"));
+        assert_eq!(items[1].action, Some(WindowAction::MoveToWorkspace(1)));
+
+        assert!(
+            window(1)
+                .action_items(WindowCapabilities::default(), &workspaces)
+                .is_empty()
+        );
+    }
+}
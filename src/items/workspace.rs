@@ -0,0 +1,127 @@
+use crate::compositor::WorkspaceInfo;
+use crate::items::workspace_targets;
+
+/// A workspace item for switching to a workspace.
+#[derive(Clone, Debug)]
+pub struct WorkspaceItem {
+    /// Internal ID for the list
+    pub id: String,
+    /// Compositor workspace number (used for switching)
+    pub workspace: i32,
+    /// Display name (e.g., "Workspace 2" or "Workspace web")
+    pub name: String,
+    /// Pre-computed description (e.g., "Current - 2 windows")
+    pub description: String,
+    /// Whether this is the active workspace
+    pub focused: bool,
+}
+
+impl WorkspaceItem {
+    /// Create a WorkspaceItem from compositor WorkspaceInfo.
+    pub fn from_workspace_info(info: &WorkspaceInfo) -> Self {
+        let number = info.id.to_string();
+        let name = if info.id <= 0 {
+            format!("Workspace {}", info.name)
+        } else if info.name.is_empty() || info.name == number {
+            format!("Workspace {}", info.id)
+        } else {
+            format!("Workspace {} ({})", info.id, info.name)
+        };
+
+        let windows = match info.windows {
+            0 => "Empty".to_string(),
+            1 => "1 window".to_string(),
+            n => format!("{} windows", n),
+        };
+        let description = if info.focused {
+            format!("Current - {}", windows)
+        } else {
+            windows
+        };
+
+        Self {
+            id: format!("workspace-{}", info.id),
+            workspace: info.id,
+            name,
+            description,
+            focused: info.focused,
+        }
+    }
+}
+
+/// Build the items listing the compositor's workspaces.
+///
+/// Compositors may not report empty workspaces, so numbered workspaces are
+/// filled in up to the highest one in use plus the next empty one, letting
+/// users jump to an empty workspace. Named workspaces follow the numbered ones.
+pub fn workspace_items(workspaces: &[WorkspaceInfo]) -> Vec<WorkspaceItem> {
+    let numbered = workspace_targets(workspaces.iter().map(|w| w.id))
+        .into_iter()
+        .map(|id| match workspaces.iter().find(|w| w.id == id) {
+            Some(info) => WorkspaceItem::from_workspace_info(info),
+            None => WorkspaceItem::from_workspace_info(&WorkspaceInfo {
+                id,
+                name: id.to_string(),
+                windows: 0,
+                focused: false,
+            }),
+        });
+    let named = workspaces
+        .iter()
+        .filter(|w| w.id <= 0)
+        .map(WorkspaceItem::from_workspace_info);
+
+    numbered.chain(named).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace(id: i32, name: &str, windows: usize, focused: bool) -> WorkspaceInfo {
+        WorkspaceInfo {
+            id,
+            name: name.to_string(),
+            windows,
+            focused,
+        }
+    }
+
+    #[test]
+    fn test_workspace_items() {
+        let items = workspace_items(&[
+            workspace(1, "1", 3, false),
+            workspace(3, "code", 1, true),
+            workspace(-1337, "web", 2, false),
+        ]);
+
+        let summary: Vec<(i32, &str, &str)> = items
+            .iter()
+            .map(|item| {
+                (
+                    item.workspace,
+                    item.name.as_str(),
+                    item.description.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (1, "Workspace 1", "3 windows"),
+                // Gaps and the next workspace are offered empty
+                (2, "Workspace 2", "Empty"),
+                (3, "Workspace 3 (code)", "Current - 1 window"),
+                (4, "Workspace 4", "Empty"),
+                (-1337, "Workspace web", "2 windows"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_workspaces() {
+        let items = workspace_items(&[]);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].name, "Workspace 1");
+    }
+}
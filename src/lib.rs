@@ -1,10 +1,15 @@
 pub mod app;
 pub mod calculator;
 pub mod cli;
+pub mod clipboard;
 pub mod compositor;
+pub mod config;
 pub mod daemon;
 pub mod desktop;
+pub mod dmenu;
 pub mod emoji;
+pub mod history;
 pub mod ipc;
 pub mod items;
+pub mod system;
 pub mod ui;
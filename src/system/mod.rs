@@ -0,0 +1,438 @@
+//! Built-in system actions (shutdown, reboot, suspend, lock and logout)
+//! and user-defined commands.
+//!
+//! Each system action runs a shell command from the `[actions]` config
+//! table, `systemctl`/`loginctl` by default. User-defined commands come from
+//! `[[commands]]` entries. Commands are executed through an
+//! [`ActionBackend`] so the dispatch logic can be tested without powering
+//! off the machine.
+//!
+//! Destructive actions must be confirmed by pressing Enter a second time,
+//! tracked by a [`ConfirmationGate`].
+
+use crate::config::{ActionsConfig, CommandConfig, config};
+use crate::desktop::get_session_environment;
+use crate::items::{ActionItem, ActionKind};
+use std::process::{Command, Stdio};
+
+/// Executes the commands behind system actions.
+pub trait ActionBackend: Send + Sync {
+    /// Run a shell command without waiting for it to finish.
+    fn spawn(&self, command: &str) -> anyhow::Result<()>;
+
+    /// Run a shell command in a terminal emulator.
+    fn spawn_in_terminal(&self, command: &str) -> anyhow::Result<()>;
+
+    /// Run a shell command to completion and return its standard output.
+    fn output(&self, command: &str) -> anyhow::Result<String>;
+}
+
+/// Runs commands through the platform shell, detached from the launcher.
+pub struct ShellBackend;
+
+impl ShellBackend {
+    /// Build a shell invocation of `command` in the session environment.
+    fn shell(command: &str) -> Command {
+        #[cfg(unix)]
+        let mut cmd = {
+            let mut cmd = Command::new("sh");
+            cmd.arg("-c").arg(command);
+            cmd
+        };
+
+        #[cfg(windows)]
+        let mut cmd = {
+            let mut cmd = Command::new("cmd");
+            cmd.arg("/C").arg(command);
+            cmd
+        };
+
+        cmd.env_clear()
+            .envs(get_session_environment().iter())
+            .stdin(Stdio::null());
+        cmd
+    }
+}
+
+impl ActionBackend for ShellBackend {
+    fn spawn(&self, command: &str) -> anyhow::Result<()> {
+        Self::shell(command)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    fn spawn_in_terminal(&self, command: &str) -> anyhow::Result<()> {
+        let argv = ["sh".to_string(), "-c".to_string(), command.to_string()];
+        crate::desktop::exec::launch_in_terminal_unix(&argv)
+    }
+
+    #[cfg(windows)]
+    fn spawn_in_terminal(&self, command: &str) -> anyhow::Result<()> {
+        self.spawn(&format!("start \"\" cmd /K {}", command))
+    }
+
+    fn output(&self, command: &str) -> anyhow::Result<String> {
+        let output = Self::shell(command).output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            match stderr.trim() {
+                "" => anyhow::bail!("Command failed with {}", output.status),
+                message => anyhow::bail!("{}", message),
+            }
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+/// Get the configured command for an action.
+pub fn command_for<'a>(kind: &'a ActionKind, config: &'a ActionsConfig) -> &'a str {
+    match kind {
+        ActionKind::Shutdown => &config.shutdown,
+        ActionKind::Reboot => &config.reboot,
+        ActionKind::Suspend => &config.suspend,
+        ActionKind::Lock => &config.lock,
+        ActionKind::Logout => &config.logout,
+        ActionKind::Command { command, .. } => command,
+    }
+}
+
+/// Run an action with the given config and backend.
+pub fn run_action_with(
+    kind: &ActionKind,
+    config: &ActionsConfig,
+    backend: &dyn ActionBackend,
+) -> anyhow::Result<()> {
+    let command = command_for(kind, config).trim();
+    if command.is_empty() {
+        anyhow::bail!("No command configured for {:?}", kind);
+    }
+
+    tracing::info!(?kind, command, "Running action");
+    match kind {
+        ActionKind::Command { terminal: true, .. } => backend.spawn_in_terminal(command),
+        _ => backend.spawn(command),
+    }
+}
+
+/// Run an action using the global config and the shell.
+pub fn run_action(kind: &ActionKind) -> anyhow::Result<()> {
+    run_action_with(kind, &config().actions, &ShellBackend)
+}
+
+/// Run a command through the shell and capture its standard output.
+///
+/// Blocks until the command exits and should not be called on the UI thread.
+pub fn capture_output(command: &str) -> anyhow::Result<String> {
+    tracing::info!(command, "Capturing command output");
+    ShellBackend.output(command)
+}
+
+/// Build action items for the user-defined commands.
+///
+/// Items are keyed by name, so renaming a command resets its history.
+/// Entries with an empty command are skipped.
+pub fn custom_commands(commands: &[CommandConfig]) -> Vec<ActionItem> {
+    commands
+        .iter()
+        .filter(|c| !c.command.trim().is_empty())
+        .map(|c| {
+            ActionItem::new(
+                format!("command:{}", c.name),
+                c.name.clone(),
+                c.description.clone(),
+                c.icon.clone(),
+                ActionKind::Command {
+                    command: c.command.clone(),
+                    terminal: c.terminal,
+                    show_output: c.show_output,
+                },
+            )
+        })
+        .collect()
+}
+
+/// Build the built-in action items.
+///
+/// Actions whose command is empty are left out, as are all of them if
+/// system actions are disabled.
+pub fn system_actions(config: &ActionsConfig) -> Vec<ActionItem> {
+    if !config.enabled {
+        return Vec::new();
+    }
+
+    [
+        (
+            ActionKind::Lock,
+            "lock",
+            "Lock Screen",
+            "Lock the session",
+            "lock",
+        ),
+        (
+            ActionKind::Suspend,
+            "suspend",
+            "Suspend",
+            "Suspend to memory",
+            "moon",
+        ),
+        (
+            ActionKind::Logout,
+            "logout",
+            "Log Out",
+            "End the session",
+            "logout",
+        ),
+        (
+            ActionKind::Reboot,
+            "reboot",
+            "Reboot",
+            "Restart the computer",
+            "restart",
+        ),
+        (
+            ActionKind::Shutdown,
+            "shutdown",
+            "Shut Down",
+            "Power off the computer",
+            "power",
+        ),
+    ]
+    .into_iter()
+    .filter(|(kind, ..)| !command_for(kind, config).trim().is_empty())
+    .map(|(kind, id, name, description, icon)| {
+        ActionItem::new(
+            format!("system-{}", id),
+            name.to_string(),
+            Some(description.to_string()),
+            Some(icon.to_string()),
+            kind,
+        )
+    })
+    .collect()
+}
+
+/// Tracks which destructive action is waiting for a second confirmation.
+#[derive(Debug, Default)]
+pub struct ConfirmationGate {
+    pending: Option<String>,
+}
+
+impl ConfirmationGate {
+    /// Handle a confirm on `action`, returning whether it should run now.
+    ///
+    /// Destructive actions only run when confirmed twice in a row; the first
+    /// confirm marks them as pending. Other actions, and all actions when
+    /// `required` is false, run immediately.
+    pub fn confirm(&mut self, action: &ActionItem, required: bool) -> bool {
+        if !required || !action.kind.is_destructive() {
+            self.pending = None;
+            return true;
+        }
+
+        if self.is_pending(&action.id) {
+            self.pending = None;
+            true
+        } else {
+            self.pending = Some(action.id.clone());
+            false
+        }
+    }
+
+    /// Check if the action with this id is waiting for confirmation.
+    pub fn is_pending(&self, id: &str) -> bool {
+        self.pending.as_deref() == Some(id)
+    }
+
+    /// Forget any pending confirmation, e.g. when the selection changes.
+    pub fn reset(&mut self) {
+        self.pending = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Records commands instead of running them.
+    #[derive(Default)]
+    struct MockBackend {
+        commands: Mutex<Vec<String>>,
+    }
+
+    impl ActionBackend for MockBackend {
+        fn spawn(&self, command: &str) -> anyhow::Result<()> {
+            self.commands.lock().unwrap().push(command.to_string());
+            Ok(())
+        }
+
+        fn spawn_in_terminal(&self, command: &str) -> anyhow::Result<()> {
+            self.spawn(&format!("terminal: {}", command))
+        }
+
+        fn output(&self, command: &str) -> anyhow::Result<String> {
+            self.spawn(command)?;
+            Ok(format!("output of {}", command))
+        }
+    }
+
+    impl MockBackend {
+        fn commands(&self) -> Vec<String> {
+            self.commands.lock().unwrap().clone()
+        }
+    }
+
+    fn action(kind: ActionKind) -> ActionItem {
+        system_actions(&ActionsConfig::default())
+            .into_iter()
+            .find(|a| a.kind == kind)
+            .unwrap()
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_default_commands() {
+        let backend = MockBackend::default();
+        let config = ActionsConfig::default();
+
+        run_action_with(&ActionKind::Shutdown, &config, &backend).unwrap();
+        run_action_with(&ActionKind::Reboot, &config, &backend).unwrap();
+        run_action_with(&ActionKind::Lock, &config, &backend).unwrap();
+
+        assert_eq!(
+            backend.commands(),
+            [
+                "systemctl poweroff",
+                "systemctl reboot",
+                "loginctl lock-session"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_configured_commands() {
+        let backend = MockBackend::default();
+        let config = ActionsConfig {
+            lock: "swaylock -f".to_string(),
+            suspend: String::new(),
+            ..Default::default()
+        };
+
+        run_action_with(&ActionKind::Lock, &config, &backend).unwrap();
+        let command = ActionKind::Command {
+            command: "notify-send hi".to_string(),
+            terminal: false,
+            show_output: false,
+        };
+        run_action_with(&command, &config, &backend).unwrap();
+        assert!(run_action_with(&ActionKind::Suspend, &config, &backend).is_err());
+
+        assert_eq!(backend.commands(), ["swaylock -f", "notify-send hi"]);
+    }
+
+    #[test]
+    fn test_custom_commands() {
+        let command = |name: &str, command: &str, terminal: bool| CommandConfig {
+            name: name.to_string(),
+            description: None,
+            icon: None,
+            command: command.to_string(),
+            terminal,
+            show_output: false,
+        };
+        let commands = [
+            command("VPN up", "nmcli connection up work", false),
+            command("htop", "htop", true),
+            command("Broken", " ", false),
+        ];
+
+        let items = custom_commands(&commands);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].id, "command:VPN up");
+        assert_eq!(items[1].name, "htop");
+
+        let backend = MockBackend::default();
+        let config = ActionsConfig::default();
+        for item in &items {
+            run_action_with(&item.kind, &config, &backend).unwrap();
+        }
+        assert_eq!(
+            backend.commands(),
+            ["nmcli connection up work", "terminal: htop"]
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_shell_backend_output() {
+        assert_eq!(ShellBackend.output("echo hello").unwrap(), "hello\n");
+
+        let err = ShellBackend.output("echo oops >&2; exit 3").unwrap_err();
+        assert_eq!(err.to_string(), "oops");
+    }
+
+    #[test]
+    fn test_system_actions_follow_config() {
+        let all = system_actions(&ActionsConfig::default());
+        assert_eq!(all.len(), 5);
+        assert!(all.iter().all(|a| a.id.starts_with("system-")));
+
+        let config = ActionsConfig {
+            suspend: "  ".to_string(),
+            ..Default::default()
+        };
+        let actions = system_actions(&config);
+        assert_eq!(actions.len(), 4);
+        assert!(actions.iter().all(|a| a.kind != ActionKind::Suspend));
+
+        let disabled = ActionsConfig {
+            enabled: false,
+            ..Default::default()
+        };
+        assert!(system_actions(&disabled).is_empty());
+    }
+
+    #[test]
+    fn test_destructive_actions_need_second_confirm() {
+        let mut gate = ConfirmationGate::default();
+        let shutdown = action(ActionKind::Shutdown);
+
+        assert!(!gate.confirm(&shutdown, true));
+        assert!(gate.is_pending(&shutdown.id));
+        assert!(gate.confirm(&shutdown, true));
+        assert!(!gate.is_pending(&shutdown.id));
+
+        // Starts over after running
+        assert!(!gate.confirm(&shutdown, true));
+    }
+
+    #[test]
+    fn test_confirmation_is_per_action() {
+        let mut gate = ConfirmationGate::default();
+        let shutdown = action(ActionKind::Shutdown);
+        let reboot = action(ActionKind::Reboot);
+
+        assert!(!gate.confirm(&shutdown, true));
+        assert!(!gate.confirm(&reboot, true));
+        assert!(!gate.is_pending(&shutdown.id));
+        assert!(gate.confirm(&reboot, true));
+
+        assert!(!gate.confirm(&shutdown, true));
+        gate.reset();
+        assert!(!gate.confirm(&shutdown, true));
+    }
+
+    #[test]
+    fn test_harmless_or_unconfirmed_actions_run_immediately() {
+        let mut gate = ConfirmationGate::default();
+        assert!(gate.confirm(&action(ActionKind::Lock), true));
+        assert!(gate.confirm(&action(ActionKind::Suspend), true));
+        assert!(gate.confirm(&action(ActionKind::Shutdown), false));
+    }
+}
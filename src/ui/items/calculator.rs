@@ -14,6 +14,7 @@ use super::base::render_action_indicator;
 /// - A custom "=" icon
 /// - The expression as a muted title
 /// - The result (or error) with "= " prefix in larger text
+/// - For integer results, the result in the other number bases
 pub fn render_calculator(calc: &CalculatorItem, selected: bool, row: usize) -> Stateful<Div> {
     let t = theme();
 
@@ -105,8 +106,12 @@ fn render_calculator_content(calc: &CalculatorItem, selected: bool) -> Div {
     };
 
     let result_text = format!("= {}", calc.display_result);
-    let result_element = div()
+    let mut result_element = div()
         .w_full()
+        .flex()
+        .flex_row()
+        .items_center()
+        .gap_2()
         .text_base() // Slightly larger than normal text_sm
         .font_weight(gpui::FontWeight::MEDIUM)
         .text_color(result_color)
@@ -115,6 +120,20 @@ fn render_calculator_content(calc: &CalculatorItem, selected: bool) -> Div {
         .text_ellipsis()
         .child(SharedString::from(result_text));
 
+    // Integer results also show the other bases, copied with their own keys
+    if let Some(forms) = calc.result.integer() {
+        let others = forms.others().collect::<Vec<_>>().join("  ");
+        result_element = result_element.child(
+            div()
+                .text_xs()
+                .font_weight(gpui::FontWeight::NORMAL)
+                .text_color(t.item_description_color)
+                .overflow_hidden()
+                .text_ellipsis()
+                .child(SharedString::from(others)),
+        );
+    }
+
     let max_width = t.max_text_width(selected);
 
     // Content height is 1.5x normal to accommodate larger result text
@@ -0,0 +1,84 @@
+//! Clipboard entry rendering.
+//!
+//! Entries render as regular items, with image entries using the image as
+//! their icon. The selected entry is previewed below the list.
+
+use crate::config::{Keys, config};
+use crate::items::ClipboardItem;
+use crate::ui::theme::theme;
+use gpui::{Div, SharedString, Stateful, div, img, prelude::*, px};
+
+use super::base::{item_container, render_action_indicator, render_icon, render_text_content};
+use super::render_submenu_icon;
+
+/// Height of the preview below the list.
+const PREVIEW_HEIGHT: f32 = 160.0;
+
+/// Render a clipboard entry.
+pub fn render_clipboard(clip: &ClipboardItem, selected: bool, row: usize) -> Stateful<Div> {
+    let icon = match clip.image_path {
+        Some(ref path) => render_icon(Some(path)),
+        None if clip.pinned => render_submenu_icon(Some("pin")),
+        None => render_submenu_icon(Some("clipboard")),
+    };
+
+    let mut item = item_container(row, selected)
+        .child(icon)
+        .child(render_text_content(
+            &clip.title,
+            Some(&clip.description),
+            selected,
+        ));
+
+    if selected {
+        item = item.child(render_action_indicator("Copy"));
+    }
+
+    item
+}
+
+/// Render the preview of the selected clipboard entry, with the keys to
+/// pin and delete it.
+pub fn render_clipboard_preview(clip: &ClipboardItem) -> Div {
+    let t = theme();
+    let keys = &config().keybindings;
+    let key = |keys: &Keys| keys.iter().next().unwrap_or("unbound").to_string();
+    let hint = format!(
+        "{} to {}, {} to delete",
+        key(&keys.toggle_pin),
+        if clip.pinned { "unpin" } else { "pin" },
+        key(&keys.delete_entry),
+    );
+
+    let content = match clip.image_path {
+        Some(ref path) => div()
+            .flex_1()
+            .overflow_hidden()
+            .child(img(path.clone()).size_full()),
+        None => div()
+            .flex_1()
+            .overflow_hidden()
+            .font_family("monospace")
+            .text_xs()
+            .text_color(t.item_title_color)
+            .child(SharedString::from(clip.preview.clone())),
+    };
+
+    div()
+        .h(px(PREVIEW_HEIGHT))
+        .flex_shrink_0()
+        .flex()
+        .flex_col()
+        .gap_1()
+        .border_t_1()
+        .border_color(t.window_border)
+        .px(t.item_margin_x + t.item_padding_x)
+        .py_2()
+        .child(content)
+        .child(
+            div()
+                .text_xs()
+                .text_color(t.section_header_color)
+                .child(SharedString::from(hint)),
+        )
+}
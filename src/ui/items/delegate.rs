@@ -1,10 +1,13 @@
-use crate::calculator::{evaluate_expression, looks_like_expression};
+use crate::calculator::evaluate;
+use crate::config::config;
+use crate::history::{frecency_boost, frecency_scores};
 use crate::items::{CalculatorItem, ListItem, SubmenuItem};
-use crate::ui::items::render_item;
+use crate::system::{ConfirmationGate, custom_commands, system_actions};
+use crate::ui::items::{render_item, render_pending_action};
 use crate::ui::theme::theme;
 use fuzzy_matcher::FuzzyMatcher;
 use fuzzy_matcher::skim::SkimMatcherV2;
-use gpui::{App, Context, SharedString, Task, Window, div, prelude::*};
+use gpui::{Action, App, Context, SharedString, Task, Window, div, prelude::*};
 use gpui_component::IndexPath;
 use gpui_component::list::{ListDelegate, ListItem as GpuiListItem, ListState};
 use std::sync::Arc;
@@ -12,26 +15,62 @@ use std::sync::Arc;
 /// Section information for the list.
 #[derive(Clone, Debug, Default)]
 pub struct SectionInfo {
+    /// Number of past calculations in filtered results
+    pub calculation_count: usize,
     /// Number of windows in filtered results
     pub window_count: usize,
+    /// Number of workspaces in filtered results
+    pub workspace_count: usize,
     /// Number of commands (submenus) in filtered results
     pub command_count: usize,
     /// Number of applications in filtered results
     pub app_count: usize,
+    /// Number of system actions in filtered results
+    pub action_count: usize,
+    /// Number of dmenu entries in filtered results
+    pub entry_count: usize,
+    /// Number of clipboard entries in filtered results
+    pub clipboard_count: usize,
+}
+
+impl SectionInfo {
+    /// Non-empty sections in display order, with their item counts.
+    fn sections(&self, has_calc: bool) -> impl Iterator<Item = (SectionType, usize)> {
+        [
+            (SectionType::Calculator, has_calc as usize),
+            (SectionType::Calculations, self.calculation_count),
+            (SectionType::Windows, self.window_count),
+            (SectionType::Workspaces, self.workspace_count),
+            (SectionType::Commands, self.command_count),
+            (SectionType::Applications, self.app_count),
+            (SectionType::Actions, self.action_count),
+            (SectionType::Entries, self.entry_count),
+            (SectionType::Clipboard, self.clipboard_count),
+        ]
+        .into_iter()
+        .filter(|&(_, count)| count > 0)
+    }
 }
 
 /// Types of sections in the list.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum SectionType {
     Calculator,
+    Calculations,
     Windows,
+    Workspaces,
     Commands,
     Applications,
+    Actions,
+    Entries,
+    Clipboard,
 }
 
 /// A generic delegate for displaying and filtering list items.
 pub struct ItemListDelegate {
     items: Arc<Vec<ListItem>>,
+    /// Frecency of each item from the launch history, parallel to `items`.
+    frecency: Arc<Vec<f64>>,
     filtered_indices: Vec<usize>,
     section_info: SectionInfo,
     selected_index: Option<usize>,
@@ -40,23 +79,58 @@ pub struct ItemListDelegate {
     calculator_item: Option<CalculatorItem>,
     on_confirm: Option<Arc<dyn Fn(&ListItem) + Send + Sync>>,
     on_cancel: Option<Arc<dyn Fn() + Send + Sync>>,
+    /// Action dispatched when an item is clicked, instead of confirming it here.
+    click_action: Option<Box<dyn Action>>,
+    /// Destructive action waiting for a second confirm.
+    confirmation: ConfirmationGate,
+    /// Whether math expressions in the query show a calculator result.
+    calculator: bool,
 }
 
 impl ItemListDelegate {
     pub fn new(mut items: Vec<ListItem>) -> Self {
         // Add built-in submenu items
-        items.push(ListItem::Submenu(
-            SubmenuItem::grid("submenu-emojis", "Emojis", 8)
-                .with_description("Search and copy emojis")
-                .with_icon("smile"),
-        ));
+        if config().behavior.show_emojis {
+            items.push(ListItem::Submenu(
+                SubmenuItem::grid("submenu-emojis", "Emojis", theme().emoji_columns)
+                    .with_description("Search and copy emojis")
+                    .with_icon("smile"),
+            ));
+        }
+        if config().history.enabled {
+            items.push(ListItem::Submenu(
+                SubmenuItem::list("submenu-calculator-history", "Calculator history")
+                    .with_description("Copy a past calculation")
+                    .with_icon("calculator"),
+            ));
+        }
+        if config().clipboard.enabled {
+            items.push(ListItem::Submenu(
+                SubmenuItem::list("submenu-clipboard", "Clipboard")
+                    .with_description("Copy a past clipboard entry")
+                    .with_icon("clipboard"),
+            ));
+        }
+        items.extend(
+            custom_commands(&config().commands)
+                .into_iter()
+                .chain(system_actions(&config().actions))
+                .map(ListItem::Action),
+        );
+
+        Self::from_items(items)
+    }
 
+    /// Create a delegate showing exactly `items`, without the built-in submenus.
+    pub fn from_items(items: Vec<ListItem>) -> Self {
+        let frecency = frecency_scores(&items);
         let len = items.len();
-        let filtered_indices: Vec<usize> = (0..len).collect();
+        let filtered_indices = Self::filter_items_sync(&items, &frecency, "");
         let section_info = Self::compute_section_info(&items, &filtered_indices);
 
         Self {
             items: Arc::new(items),
+            frecency: Arc::new(frecency),
             filtered_indices,
             section_info,
             selected_index: if len > 0 { Some(0) } else { None },
@@ -64,21 +138,40 @@ impl ItemListDelegate {
             calculator_item: None,
             on_confirm: None,
             on_cancel: None,
+            click_action: None,
+            confirmation: ConfirmationGate::default(),
+            calculator: true,
         }
     }
 
+    /// Don't show calculator results for math expressions in the query.
+    pub fn without_calculator(mut self) -> Self {
+        self.calculator = false;
+        self
+    }
+
     /// Compute section counts from filtered indices.
     fn compute_section_info(items: &[ListItem], filtered_indices: &[usize]) -> SectionInfo {
         let mut info = SectionInfo::default();
 
         for &item_idx in filtered_indices {
             if let Some(item) = items.get(item_idx) {
-                if item.is_window() {
+                if item.is_calculator() {
+                    info.calculation_count += 1;
+                } else if item.is_window() {
                     info.window_count += 1;
+                } else if item.is_workspace() {
+                    info.workspace_count += 1;
                 } else if item.is_submenu() {
                     info.command_count += 1;
                 } else if item.is_application() {
                     info.app_count += 1;
+                } else if item.is_action() {
+                    info.action_count += 1;
+                } else if item.is_dmenu() {
+                    info.entry_count += 1;
+                } else if item.is_clipboard() {
+                    info.clipboard_count += 1;
                 }
             }
         }
@@ -96,18 +189,47 @@ impl ItemListDelegate {
         self.on_cancel = Some(Arc::new(callback));
     }
 
+    /// Dispatch `action` to the window when an item is clicked, so clicks
+    /// take the same path as the keyboard.
+    pub fn set_click_action(&mut self, action: Box<dyn Action>) {
+        self.click_action = Some(action);
+    }
+
     /// Returns the items Arc for use in background filtering.
     pub fn items(&self) -> Arc<Vec<ListItem>> {
         Arc::clone(&self.items)
     }
 
+    /// Returns the frecency Arc for use in background filtering.
+    pub fn frecency(&self) -> Arc<Vec<f64>> {
+        Arc::clone(&self.frecency)
+    }
+
     /// Filter items on a background thread - returns filtered indices.
-    /// Results are sorted by type (windows first) then by score.
-    pub fn filter_items_sync(items: &[ListItem], query: &str) -> Vec<usize> {
+    /// Results are sorted by type (windows first), then by match score
+    /// boosted by frecency. With an empty query, items within each type are
+    /// ordered by frecency, keeping the original order for unused items.
+    /// Windows keep the compositor's most recently focused order instead,
+    /// and past calculations and clipboard entries stay most recent first.
+    pub fn filter_items_sync(items: &[ListItem], frecency: &[f64], query: &str) -> Vec<usize> {
         if query.is_empty() {
-            // Sort by type priority (windows first, then applications)
+            // Sort by type priority (windows first, then applications), then most used first
             let mut indices: Vec<usize> = (0..items.len()).collect();
-            indices.sort_by_key(|&idx| items[idx].sort_priority());
+            indices.sort_by(|&a, &b| {
+                items[a]
+                    .sort_priority()
+                    .cmp(&items[b].sort_priority())
+                    .then_with(|| {
+                        if items[a].is_window()
+                            || items[a].is_calculator()
+                            || items[a].is_clipboard()
+                        {
+                            std::cmp::Ordering::Equal
+                        } else {
+                            frecency[b].total_cmp(&frecency[a])
+                        }
+                    })
+            });
             indices
         } else {
             let matcher = SkimMatcherV2::default();
@@ -117,11 +239,11 @@ impl ItemListDelegate {
                 .filter_map(|(idx, item)| {
                     matcher
                         .fuzzy_match(item.name(), query)
-                        .map(|score| (idx, score))
+                        .map(|score| (idx, score + frecency_boost(frecency[idx])))
                 })
                 .collect();
 
-            // Sort by type priority first, then by score within each type
+            // Sort by type priority first, then by boosted score within each type
             scored.sort_by(|a, b| {
                 let priority_a = items[a.0].sort_priority();
                 let priority_b = items[b.0].sort_priority();
@@ -143,6 +265,7 @@ impl ItemListDelegate {
 
             let has_items = self.calculator_item.is_some() || !self.filtered_indices.is_empty();
             self.selected_index = if has_items { Some(0) } else { None };
+            self.confirmation.reset();
         }
     }
 
@@ -150,21 +273,22 @@ impl ItemListDelegate {
         // Try to evaluate as calculator expression
         self.calculator_item = self.try_evaluate_calculator(&self.query.clone());
 
-        self.filtered_indices = Self::filter_items_sync(&self.items, &self.query);
+        self.filtered_indices = Self::filter_items_sync(&self.items, &self.frecency, &self.query);
         self.section_info = Self::compute_section_info(&self.items, &self.filtered_indices);
 
         // Set selection: calculator at 0 if present, otherwise first filtered item
         let has_items = self.calculator_item.is_some() || !self.filtered_indices.is_empty();
         self.selected_index = if has_items { Some(0) } else { None };
+        self.confirmation.reset();
     }
 
     /// Try to evaluate the query as a calculator expression.
     fn try_evaluate_calculator(&self, query: &str) -> Option<CalculatorItem> {
-        if !looks_like_expression(query) {
+        if !self.calculator {
             return None;
         }
 
-        evaluate_expression(query).map(CalculatorItem::from_calc_result)
+        evaluate(query).map(CalculatorItem::from_calc_result)
     }
 
     /// Check if a calculator item is currently shown.
@@ -174,22 +298,23 @@ impl ItemListDelegate {
 
     /// Get the item at a global row index, accounting for calculator at position 0.
     fn get_item_at(&self, row: usize) -> Option<ListItem> {
-        if self.calculator_item.is_some() {
-            if row == 0 {
-                // Return calculator as ListItem
-                return self.calculator_item.clone().map(ListItem::Calculator);
-            }
-            // Offset by 1 for non-calculator items
-            self.filtered_indices
-                .get(row - 1)
-                .and_then(|&idx| self.items.get(idx))
-                .cloned()
-        } else {
-            self.filtered_indices
-                .get(row)
-                .and_then(|&idx| self.items.get(idx))
-                .cloned()
+        if self.calculator_item.is_some() && row == 0 {
+            // Return calculator as ListItem
+            return self.calculator_item.clone().map(ListItem::Calculator);
         }
+        self.item_index_at(row)
+            .and_then(|idx| self.items.get(idx))
+            .cloned()
+    }
+
+    /// Get the index into `items` of a global row, `None` for the calculator row.
+    fn item_index_at(&self, row: usize) -> Option<usize> {
+        // Offset by 1 for non-calculator items
+        let row = match self.calculator_item {
+            Some(_) => row.checked_sub(1)?,
+            None => row,
+        };
+        self.filtered_indices.get(row).copied()
     }
 
     /// Convert section + row to global selected index.
@@ -200,40 +325,23 @@ impl ItemListDelegate {
 
     /// Convert global index to section + row.
     pub fn global_to_section_row(&self, global: usize) -> (usize, usize) {
-        let has_calc = self.calculator_item.is_some();
-        let has_windows = self.section_info.window_count > 0;
-        let has_commands = self.section_info.command_count > 0;
-
-        let calc_offset = if has_calc { 1 } else { 0 };
-        let window_end = calc_offset + self.section_info.window_count;
-        let command_end = window_end + self.section_info.command_count;
-
-        // Determine which section and compute the row within it
-        let mut section_idx = 0;
+        let mut start = 0;
+        let mut last = (0, 0);
 
-        if has_calc {
-            if global == 0 {
-                return (0, 0);
-            }
-            section_idx += 1;
-        }
-
-        if has_windows {
-            if global < window_end {
-                return (section_idx, global - calc_offset);
-            }
-            section_idx += 1;
-        }
-
-        if has_commands {
-            if global < command_end {
-                return (section_idx, global - window_end);
+        for (section_idx, (_, count)) in self
+            .section_info
+            .sections(self.calculator_item.is_some())
+            .enumerate()
+        {
+            if global < start + count {
+                return (section_idx, global - start);
             }
-            section_idx += 1;
+            last = (section_idx, global - start);
+            start += count;
         }
 
-        // Must be in Applications section
-        (section_idx, global - command_end)
+        // Past the end, keep the row in the last section
+        last
     }
 
     pub fn clear_query(&mut self) {
@@ -267,14 +375,26 @@ impl ItemListDelegate {
     }
 
     pub fn set_selected(&mut self, index: usize) {
+        if self.selected_index != Some(index) {
+            self.confirmation.reset();
+        }
         self.selected_index = Some(index);
     }
 
-    pub fn do_confirm(&self) {
+    /// Confirm the selected item.
+    ///
+    /// Destructive actions are only confirmed on the second call; the first
+    /// marks them as pending so they render a confirmation prompt.
+    pub fn do_confirm(&mut self) {
         if let Some(idx) = self.selected_index
             && let Some(item) = self.get_item_at(idx)
             && let Some(ref on_confirm) = self.on_confirm
         {
+            if let ListItem::Action(ref action) = item
+                && !self.confirmation.confirm(action, config().actions.confirm)
+            {
+                return;
+            }
             on_confirm(&item);
         }
     }
@@ -292,49 +412,30 @@ impl ItemListDelegate {
 
     /// Determine what type of section is at the given section index.
     fn section_type_at(&self, section: usize) -> SectionType {
-        let has_calc = self.calculator_item.is_some();
-        let has_windows = self.section_info.window_count > 0;
-        let has_commands = self.section_info.command_count > 0;
-
-        let mut current_section = 0;
-
-        if has_calc {
-            if section == current_section {
-                return SectionType::Calculator;
-            }
-            current_section += 1;
-        }
-
-        if has_windows {
-            if section == current_section {
-                return SectionType::Windows;
-            }
-            current_section += 1;
-        }
-
-        if has_commands {
-            if section == current_section {
-                return SectionType::Commands;
-            }
-            // current_section += 1; // Not needed, Applications is the default
-        }
-
-        // Default to Applications
-        SectionType::Applications
+        self.section_info
+            .sections(self.calculator_item.is_some())
+            .nth(section)
+            .map(|(section_type, _)| section_type)
+            // Actions always come last
+            .unwrap_or(SectionType::Actions)
     }
 
     /// Get the starting filtered index for a given section type.
     fn section_start_index(&self, section_type: SectionType) -> usize {
-        let has_calc = self.calculator_item.is_some();
-        let calc_offset = if has_calc { 1 } else { 0 };
-
-        match section_type {
-            SectionType::Calculator => 0,
-            SectionType::Windows => calc_offset,
-            SectionType::Commands => calc_offset + self.section_info.window_count,
-            SectionType::Applications => {
-                calc_offset + self.section_info.window_count + self.section_info.command_count
-            }
+        self.section_info
+            .sections(self.calculator_item.is_some())
+            .take_while(|&(t, _)| t != section_type)
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// Toggle the multi-select mark of the selected dmenu entry.
+    pub fn toggle_selected_mark(&mut self) {
+        let Some(idx) = self.selected_index.and_then(|row| self.item_index_at(row)) else {
+            return;
+        };
+        if let Some(ListItem::Dmenu(entry)) = Arc::make_mut(&mut self.items).get_mut(idx) {
+            entry.marked = !entry.marked;
         }
     }
 }
@@ -343,35 +444,16 @@ impl ListDelegate for ItemListDelegate {
     type Item = GpuiListItem;
 
     fn sections_count(&self, _cx: &App) -> usize {
-        let has_calc = self.calculator_item.is_some();
-        let has_windows = self.section_info.window_count > 0;
-        let has_commands = self.section_info.command_count > 0;
-        let has_apps = self.section_info.app_count > 0;
-
-        let mut count = 0;
-        if has_calc {
-            count += 1;
-        }
-        if has_windows {
-            count += 1;
-        }
-        if has_commands {
-            count += 1;
-        }
-        if has_apps {
-            count += 1;
-        }
-        count
+        self.section_info
+            .sections(self.calculator_item.is_some())
+            .count()
     }
 
     fn items_count(&self, section: usize, _cx: &App) -> usize {
-        let section_type = self.section_type_at(section);
-        match section_type {
-            SectionType::Calculator => 1,
-            SectionType::Windows => self.section_info.window_count,
-            SectionType::Commands => self.section_info.command_count,
-            SectionType::Applications => self.section_info.app_count,
-        }
+        self.section_info
+            .sections(self.calculator_item.is_some())
+            .nth(section)
+            .map_or(0, |(_, count)| count)
     }
 
     fn render_section_header(
@@ -388,11 +470,7 @@ impl ListDelegate for ItemListDelegate {
         }
 
         // Count how many non-calculator sections we have
-        let has_windows = self.section_info.window_count > 0;
-        let has_commands = self.section_info.command_count > 0;
-        let has_apps = self.section_info.app_count > 0;
-        let non_calc_section_count =
-            has_windows as usize + has_commands as usize + has_apps as usize;
+        let non_calc_section_count = self.section_info.sections(false).count();
 
         // Only show headers if we have multiple non-calculator sections
         if non_calc_section_count <= 1 {
@@ -402,9 +480,14 @@ impl ListDelegate for ItemListDelegate {
         let t = theme();
         let title = match section_type {
             SectionType::Calculator => return None,
+            SectionType::Calculations => "Calculations",
             SectionType::Windows => "Windows",
+            SectionType::Workspaces => "Workspaces",
             SectionType::Commands => "Commands",
             SectionType::Applications => "Applications",
+            SectionType::Actions => "Actions",
+            SectionType::Entries => "Entries",
+            SectionType::Clipboard => "Clipboard",
         };
 
         Some(
@@ -440,7 +523,12 @@ impl ListDelegate for ItemListDelegate {
             self.items.get(item_idx)?.clone()
         };
 
-        let item_content = render_item(&item, selected, global_idx);
+        let item_content = match &item {
+            ListItem::Action(action) if self.confirmation.is_pending(&action.id) => {
+                render_pending_action(action, selected, global_idx)
+            }
+            _ => render_item(&item, selected, global_idx),
+        };
 
         // Reset ListItem default padding - we handle all styling ourselves
         Some(
@@ -457,7 +545,11 @@ impl ListDelegate for ItemListDelegate {
         _window: &mut Window,
         _cx: &mut Context<ListState<Self>>,
     ) {
-        self.selected_index = ix.map(|i| self.section_row_to_global(i.section, i.row));
+        let index = ix.map(|i| self.section_row_to_global(i.section, i.row));
+        if index != self.selected_index {
+            self.confirmation.reset();
+        }
+        self.selected_index = index;
     }
 
     fn perform_search(
@@ -474,10 +566,14 @@ impl ListDelegate for ItemListDelegate {
     fn confirm(
         &mut self,
         _secondary: bool,
-        _window: &mut Window,
-        _cx: &mut Context<ListState<Self>>,
+        window: &mut Window,
+        cx: &mut Context<ListState<Self>>,
     ) {
-        self.do_confirm();
+        match &self.click_action {
+            Some(action) => window.dispatch_action(action.boxed_clone(), cx),
+            None => self.do_confirm(),
+        }
+        cx.notify();
     }
 
     fn cancel(&mut self, _window: &mut Window, _cx: &mut Context<ListState<Self>>) {
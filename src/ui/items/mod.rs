@@ -1,11 +1,13 @@
 mod application;
 mod base;
 mod calculator;
+mod clipboard;
 mod delegate;
 
 pub use application::render_application;
 pub use base::{item_container, render_action_indicator, render_icon, render_text_content};
 pub use calculator::render_calculator;
+pub use clipboard::{render_clipboard, render_clipboard_preview};
 pub use delegate::ItemListDelegate;
 
 use crate::items::ListItem;
@@ -17,9 +19,12 @@ pub fn render_item(item: &ListItem, selected: bool, row: usize) -> Stateful<Div>
     match item {
         ListItem::Application(app) => render_application(app, selected, row),
         ListItem::Window(win) => render_window(win, selected, row),
-        ListItem::Action(act) => render_action(act, selected, row),
+        ListItem::Workspace(ws) => render_workspace(ws, selected, row),
+        ListItem::Action(act) => render_action(act, selected, row, false),
         ListItem::Submenu(sub) => render_submenu(sub, selected, row),
         ListItem::Calculator(calc) => render_calculator(calc, selected, row),
+        ListItem::Dmenu(entry) => render_dmenu(entry, selected, row),
+        ListItem::Clipboard(clip) => render_clipboard(clip, selected, row),
     }
 }
 
@@ -35,24 +40,78 @@ fn render_window(win: &crate::items::WindowItem, selected: bool, row: usize) ->
         ));
 
     if selected {
-        item = item.child(render_action_indicator("Switch"));
+        let label = if win.action.is_some() {
+            "Run"
+        } else {
+            "Switch"
+        };
+        item = item.child(render_action_indicator(label));
     }
 
     item
 }
 
-fn render_action(act: &crate::items::ActionItem, selected: bool, row: usize) -> Stateful<Div> {
-    // Actions don't have file-based icons yet, use placeholder
+fn render_workspace(ws: &crate::items::WorkspaceItem, selected: bool, row: usize) -> Stateful<Div> {
     let mut item = item_container(row, selected)
-        .child(render_icon(None))
+        .child(render_submenu_icon(Some("workspaces")))
         .child(render_text_content(
-            &act.name,
-            act.description.as_deref(),
+            &ws.name,
+            Some(&ws.description),
             selected,
         ));
 
     if selected {
-        item = item.child(render_action_indicator("Run"));
+        item = item.child(render_action_indicator("Switch"));
+    }
+
+    item
+}
+
+fn render_dmenu(entry: &crate::items::DmenuItem, selected: bool, row: usize) -> Stateful<Div> {
+    let mut item = item_container(row, selected);
+
+    // Plain dmenu lines have no icon, don't show a placeholder for them
+    if entry.icon_path.is_some() {
+        item = item.child(render_icon(entry.icon_path.as_ref()));
+    }
+    let description = entry.marked.then_some("✓ Marked");
+    item = item.child(render_text_content(&entry.text, description, selected));
+
+    if selected {
+        item = item.child(render_action_indicator("Select"));
+    }
+
+    item
+}
+
+/// Render an action waiting for a second confirm.
+pub fn render_pending_action(
+    act: &crate::items::ActionItem,
+    selected: bool,
+    row: usize,
+) -> Stateful<Div> {
+    render_action(act, selected, row, true)
+}
+
+fn render_action(
+    act: &crate::items::ActionItem,
+    selected: bool,
+    row: usize,
+    pending: bool,
+) -> Stateful<Div> {
+    let description = if pending {
+        Some("Press Enter again to confirm")
+    } else {
+        act.description.as_deref()
+    };
+
+    let mut item = item_container(row, selected)
+        .child(render_submenu_icon(act.icon_name.as_deref()))
+        .child(render_text_content(&act.name, description, selected));
+
+    if selected {
+        let label = if pending { "Confirm" } else { "Run" };
+        item = item.child(render_action_indicator(label));
     }
 
     item
@@ -75,7 +134,7 @@ fn render_submenu(sub: &crate::items::SubmenuItem, selected: bool, row: usize) -
     item
 }
 
-/// Render a submenu icon, using emoji or fallback placeholder.
+/// Render a submenu or action icon, using emoji or fallback placeholder.
 fn render_submenu_icon(icon_name: Option<&str>) -> Div {
     use crate::ui::theme::theme;
     use gpui::{SharedString, div, prelude::*};
@@ -96,8 +155,18 @@ fn render_submenu_icon(icon_name: Option<&str>) -> Div {
     // Use emoji based on icon name
     let emoji = match icon_name {
         Some("smile") => "😀",
+        Some("workspaces") => "🗂️",
+        Some("calculator") => "🧮",
+        Some("clipboard") => "📋",
+        Some("pin") => "📌",
         Some("settings") => "⚙️",
         Some("power") => "⏻",
+        Some("restart") => "🔄",
+        Some("moon") => "🌙",
+        Some("lock") => "🔒",
+        Some("logout") => "🚪",
+        // Custom commands may use an emoji directly
+        Some(icon) if !icon.is_ascii() => icon,
         _ => "?",
     };
 
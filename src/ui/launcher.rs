@@ -1,13 +1,23 @@
-use crate::calculator::copy_to_clipboard;
+use crate::calculator::{NumberBase, copy_to_clipboard, recent_calculations, remember};
+use crate::clipboard::{self, clipboard_entries};
 use crate::compositor::Compositor;
+use crate::config::{KeybindingsConfig, Keys, config};
 use crate::desktop::launch_application;
-use crate::items::ListItem;
+use crate::dmenu::{DmenuRequest, DmenuSelection};
+use crate::history::{now, record_use};
+use crate::ipc::{LaunchMode, ShowOptions};
+use crate::items::{
+    ActionKind, CalculatorItem, ClipboardItem, ListItem, WorkspaceItem, window_workspaces,
+    workspace_items,
+};
+use crate::system::{capture_output, run_action};
 use crate::ui::emoji::EmojiGridDelegate;
-use crate::ui::items::ItemListDelegate;
+use crate::ui::items::{ItemListDelegate, render_clipboard_preview};
 use crate::ui::theme::theme;
 use gpui::{
-    AnyElement, App, AsyncApp, Context, Entity, FocusHandle, Focusable, KeyBinding, ScrollStrategy,
-    Task, WeakEntity, Window, actions, div, image_cache, prelude::*, retain_all,
+    Action, AnyElement, App, AsyncApp, Context, Entity, FocusHandle, Focusable, KeyBinding,
+    NoAction, ScrollStrategy, SharedString, Task, WeakEntity, Window, actions, div, image_cache,
+    prelude::*, px, retain_all,
 };
 use gpui_component::IndexPath;
 use gpui_component::input::{Input, InputState};
@@ -24,7 +34,13 @@ actions!(
         SelectTabPrev,
         Confirm,
         Cancel,
-        GoBack
+        GoBack,
+        ShowActions,
+        CopyDecimal,
+        CopyHex,
+        CopyBinary,
+        TogglePin,
+        DeleteEntry
     ]
 );
 
@@ -36,18 +52,89 @@ pub enum ViewMode {
     Main,
     /// Emoji picker grid view.
     EmojiPicker,
+    /// Secondary list: desktop actions of the selected application, operations
+    /// on the selected window, or the workspaces.
+    Actions,
+    /// Captured output of a custom command.
+    Output,
+}
+
+/// State of a custom command whose output is shown in the launcher.
+enum CommandOutput {
+    Running,
+    Finished(String),
+    Failed(String),
+}
+
+/// The output view of a custom command.
+struct OutputView {
+    title: String,
+    output: CommandOutput,
+}
+
+/// An active `zlaunch dmenu` selection.
+struct DmenuSession {
+    multi_select: bool,
+    allow_custom: bool,
+    on_select: Arc<dyn Fn(Vec<DmenuSelection>) + Send + Sync>,
 }
 
 pub fn init(cx: &mut App) {
-    cx.bind_keys([
-        KeyBinding::new("up", SelectPrev, Some("LauncherView")),
-        KeyBinding::new("down", SelectNext, Some("LauncherView")),
-        KeyBinding::new("tab", SelectTab, Some("LauncherView")),
-        KeyBinding::new("shift-tab", SelectTabPrev, Some("LauncherView")),
-        KeyBinding::new("enter", Confirm, Some("LauncherView")),
-        KeyBinding::new("escape", Cancel, Some("LauncherView")),
-        KeyBinding::new("backspace", GoBack, Some("LauncherView")),
-    ]);
+    cx.bind_keys(key_bindings(&config().keybindings));
+}
+
+/// Re-apply the launcher key bindings after the config changed.
+///
+/// GPUI has no way to remove individual bindings, so keystrokes that are no
+/// longer configured are bound to `NoAction`, which shadows the old binding.
+pub fn rebind(previous: &KeybindingsConfig, cx: &mut App) {
+    let current = config();
+    let unbound: Vec<KeyBinding> = previous
+        .all_keys()
+        .filter(|key| !current.keybindings.all_keys().any(|k| k == *key))
+        .map(|key| KeyBinding::new(key, NoAction, Some("LauncherView")))
+        .collect();
+
+    cx.bind_keys(unbound);
+    cx.bind_keys(key_bindings(&current.keybindings));
+}
+
+/// Build the launcher key bindings from the configured keystrokes.
+fn key_bindings(keys: &KeybindingsConfig) -> Vec<KeyBinding> {
+    fn bind<A: Action + Clone>(keys: &Keys, action: A) -> impl Iterator<Item = KeyBinding> {
+        keys.iter()
+            .map(move |k| KeyBinding::new(k, action.clone(), Some("LauncherView")))
+    }
+
+    bind(&keys.select_prev, SelectPrev)
+        .chain(bind(&keys.select_next, SelectNext))
+        .chain(bind(&keys.select_tab, SelectTab))
+        .chain(bind(&keys.select_tab_prev, SelectTabPrev))
+        .chain(bind(&keys.confirm, Confirm))
+        .chain(bind(&keys.cancel, Cancel))
+        .chain(bind(&keys.go_back, GoBack))
+        .chain(bind(&keys.show_actions, ShowActions))
+        .chain(bind(&keys.copy_decimal, CopyDecimal))
+        .chain(bind(&keys.copy_hex, CopyHex))
+        .chain(bind(&keys.copy_binary, CopyBinary))
+        .chain(bind(&keys.toggle_pin, TogglePin))
+        .chain(bind(&keys.delete_entry, DeleteEntry))
+        .collect()
+}
+
+/// List the clipboard history, pinned entries first.
+fn clipboard_items() -> Vec<ListItem> {
+    let now = now();
+    clipboard_entries()
+        .iter()
+        .map(|entry| {
+            ListItem::Clipboard(ClipboardItem::from_entry(
+                entry,
+                clipboard::image_path(entry),
+                now,
+            ))
+        })
+        .collect()
 }
 
 pub struct LauncherView {
@@ -57,11 +144,24 @@ pub struct LauncherView {
     list_state: Entity<ListState<ItemListDelegate>>,
     /// Emoji grid state (created on demand).
     emoji_list_state: Option<Entity<ListState<EmojiGridDelegate>>>,
+    /// Secondary list state (created on demand).
+    actions_list_state: Option<Entity<ListState<ItemListDelegate>>>,
+    /// Shared handler for confirmed items, used by the main and actions lists.
+    on_confirm: Arc<dyn Fn(&ListItem) + Send + Sync>,
+    /// Output view state (created on demand).
+    output_view: Option<OutputView>,
+    /// Placeholder of the main view, depending on the mode it was opened in.
+    placeholder: String,
+    /// Set when the launcher was opened by `zlaunch dmenu`.
+    dmenu: Option<DmenuSession>,
+    /// Decides which window actions are offered, and lists workspaces.
+    compositor: Arc<dyn Compositor>,
     input_state: Entity<InputState>,
     focus_handle: FocusHandle,
     #[allow(dead_code)] // Kept alive for blur handler
     on_hide: std::sync::Arc<dyn Fn() + Send + Sync>,
     _search_task: Task<()>,
+    _output_task: Task<()>,
 }
 
 impl LauncherView {
@@ -79,27 +179,49 @@ impl LauncherView {
         let on_hide_for_confirm = on_hide.clone();
         let on_hide_for_cancel = on_hide.clone();
 
-        delegate.set_on_confirm(move |item| {
+        let compositor_for_confirm = compositor.clone();
+        let on_confirm: Arc<dyn Fn(&ListItem) + Send + Sync> = Arc::new(move |item| {
+            // Window actions are not switches to the window, clipboard entries
+            // are ordered by when they were copied and calculations have their
+            // own history, keep them out of the launch history
+            let recorded = match item {
+                ListItem::Window(win) => win.action.is_none(),
+                ListItem::Clipboard(_) | ListItem::Calculator(_) => false,
+                _ => true,
+            };
+            if recorded {
+                record_use(item);
+            }
             match item {
                 ListItem::Application(app) => {
                     // Convert back to DesktopEntry for launching
-                    let entry = crate::desktop::DesktopEntry::new(
-                        app.id.clone(),
-                        app.name.clone(),
-                        app.exec.clone(),
-                        None,
-                        app.icon_path.clone(),
-                        app.description.clone(),
-                        vec![],
-                        app.terminal,
-                        app.desktop_path.clone(),
-                    );
-                    let _ = launch_application(&entry);
+                    if let Err(e) = launch_application(&app.to_desktop_entry()) {
+                        tracing::warn!(%e, "Failed to launch application");
+                    }
                 }
-                ListItem::Window(win) => {
+                ListItem::Window(win) => match win.action {
+                    Some(action) => {
+                        if let Err(e) =
+                            compositor_for_confirm.run_window_action(&win.address, action)
+                        {
+                            tracing::warn!(%e, %action, "Failed to run window action");
+                        }
+                    }
                     // Focus the window via compositor
-                    if let Err(e) = compositor.focus_window(&win.address) {
-                        tracing::warn!(%e, "Failed to focus window");
+                    None => {
+                        if let Err(e) = compositor_for_confirm.focus_window(&win.address) {
+                            tracing::warn!(%e, "Failed to focus window");
+                        }
+                    }
+                },
+                ListItem::Workspace(ws) => {
+                    if let Err(e) = compositor_for_confirm.focus_workspace(ws.workspace) {
+                        tracing::warn!(%e, "Failed to switch workspace");
+                    }
+                }
+                ListItem::Action(action) => {
+                    if let Err(e) = run_action(&action.kind) {
+                        tracing::warn!(%e, "Failed to run action");
                     }
                 }
                 ListItem::Calculator(calc) => {
@@ -107,23 +229,33 @@ impl LauncherView {
                     if let Err(e) = copy_to_clipboard(calc.text_for_clipboard()) {
                         tracing::warn!(%e, "Failed to copy to clipboard");
                     }
+                    remember(&calc.result);
+                }
+                ListItem::Clipboard(clip) => {
+                    if let Err(e) = clipboard::restore(clip.entry_id) {
+                        tracing::warn!(%e, "Failed to restore clipboard entry");
+                    }
                 }
                 _ => {}
             }
             on_hide_for_confirm();
         });
+        let on_confirm_for_main = on_confirm.clone();
+        delegate.set_on_confirm(move |item| on_confirm_for_main(item));
         delegate.set_on_cancel(move || on_hide_for_cancel());
+        // Clicks go through `confirm`, which opens submenus and command output
+        delegate.set_click_action(Box::new(Confirm));
 
         let list_state = cx.new(|cx| ListState::new(delegate, window, cx));
 
-        let input_state =
-            cx.new(|cx| InputState::new(window, cx).placeholder("Search applications..."));
+        let placeholder = config().behavior.search_placeholder.clone();
+        let input_state = cx.new(|cx| InputState::new(window, cx).placeholder(placeholder.clone()));
 
-        let list_state_for_subscribe = list_state.clone();
         cx.subscribe(&input_state, move |this, input, event, cx| {
             if let gpui_component::input::InputEvent::Change = event {
                 let text = input.read(cx).value().to_string();
-                this.async_search(text, list_state_for_subscribe.clone(), cx);
+                let list_state = this.active_list().clone();
+                this.async_search(text, list_state, cx);
             }
         })
         .detach();
@@ -141,10 +273,17 @@ impl LauncherView {
             view_mode: ViewMode::Main,
             list_state,
             emoji_list_state: None,
+            actions_list_state: None,
+            on_confirm,
+            output_view: None,
+            placeholder,
+            dmenu: None,
+            compositor,
             input_state,
             focus_handle,
             on_hide,
             _search_task: Task::ready(()),
+            _output_task: Task::ready(()),
         }
     }
 
@@ -163,6 +302,403 @@ impl LauncherView {
         });
     }
 
+    /// Open the view requested with `zlaunch show --mode/--query`.
+    pub fn apply_options(
+        &mut self,
+        options: &ShowOptions,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match &options.mode {
+            None => {}
+            Some(LaunchMode::Apps) => self.restrict_main_list(
+                |item| item.is_application(),
+                "Search applications...",
+                window,
+                cx,
+            ),
+            Some(LaunchMode::Windows) => {
+                self.restrict_main_list(|item| item.is_window(), "Search windows...", window, cx)
+            }
+            // The calculator result is not a list item, so no items are kept
+            Some(LaunchMode::Calc) => {
+                self.restrict_main_list(|_| false, "Calculate...", window, cx)
+            }
+            Some(LaunchMode::Emojis) => self.enter_emoji_mode(window, cx),
+            Some(LaunchMode::Submenu(id)) => {
+                if !self.enter_submenu(id, window, cx) {
+                    tracing::warn!(id = %id, "Unknown submenu, opening the launcher");
+                }
+            }
+        }
+
+        if let Some(query) = &options.query {
+            self.set_search_text(query.clone(), window, cx);
+        }
+    }
+
+    /// Replace the main list with the items matching `keep`.
+    fn restrict_main_list(
+        &mut self,
+        keep: impl Fn(&ListItem) -> bool,
+        placeholder: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let delegate = ItemListDelegate::from_items(self.main_items_matching(keep, cx));
+        self.list_state = self.new_item_list(delegate, window, cx);
+        self.set_main_placeholder(placeholder.to_string(), window, cx);
+    }
+
+    /// Clone the items of the main list matching `keep`.
+    fn main_items_matching(&self, keep: impl Fn(&ListItem) -> bool, cx: &App) -> Vec<ListItem> {
+        self.list_state
+            .read(cx)
+            .delegate()
+            .items()
+            .iter()
+            .filter(|item| keep(item))
+            .cloned()
+            .collect()
+    }
+
+    /// Change the placeholder of the main view.
+    fn set_main_placeholder(
+        &mut self,
+        placeholder: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.placeholder = placeholder.clone();
+        self.input_state.update(cx, |input, cx| {
+            input.set_placeholder(placeholder, window, cx);
+        });
+        cx.notify();
+    }
+
+    /// Show only the dmenu entries and report the chosen ones to `on_select`.
+    pub fn start_dmenu(
+        &mut self,
+        request: &DmenuRequest,
+        on_select: impl Fn(Vec<DmenuSelection>) + Send + Sync + 'static,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let on_select: Arc<dyn Fn(Vec<DmenuSelection>) + Send + Sync> = Arc::new(on_select);
+
+        let items = self.main_items_matching(|item| item.is_dmenu(), cx);
+        let mut delegate = ItemListDelegate::from_items(items).without_calculator();
+        // Clicking an entry picks just that entry
+        let on_select_for_click = on_select.clone();
+        delegate.set_on_confirm(move |item| {
+            if let ListItem::Dmenu(entry) = item {
+                on_select_for_click(vec![entry.selection()]);
+            }
+        });
+        let on_hide = self.on_hide.clone();
+        delegate.set_on_cancel(move || on_hide());
+        self.list_state = cx.new(|cx| ListState::new(delegate, window, cx));
+
+        let placeholder = request
+            .prompt
+            .clone()
+            .unwrap_or_else(|| "Select...".to_string());
+        self.set_main_placeholder(placeholder, window, cx);
+        self.dmenu = Some(DmenuSession {
+            multi_select: request.multi_select,
+            allow_custom: request.allow_custom,
+            on_select,
+        });
+    }
+
+    /// Report the marked entries, or else the selected entry or typed text.
+    fn confirm_dmenu(&self, cx: &mut Context<Self>) {
+        let Some(session) = self.dmenu.as_ref() else {
+            return;
+        };
+        let delegate = self.list_state.read(cx).delegate();
+
+        let mut selection: Vec<DmenuSelection> = delegate
+            .items()
+            .iter()
+            .filter_map(|item| match item {
+                ListItem::Dmenu(entry) if entry.marked => Some(entry.selection()),
+                _ => None,
+            })
+            .collect();
+
+        if selection.is_empty() {
+            match delegate.selected_item() {
+                Some(ListItem::Dmenu(entry)) => selection.push(entry.selection()),
+                _ if session.allow_custom && !delegate.query().is_empty() => {
+                    selection.push(DmenuSelection::custom(delegate.query()));
+                }
+                _ => return,
+            }
+        }
+
+        (session.on_select)(selection);
+    }
+
+    /// Fill the search input and filter the current view.
+    fn set_search_text(&mut self, query: String, window: &mut Window, cx: &mut Context<Self>) {
+        self.input_state.update(cx, |input, cx| {
+            input.set_value(query.clone(), window, cx);
+        });
+
+        match (self.view_mode, &self.emoji_list_state) {
+            (ViewMode::EmojiPicker, Some(emoji_state)) => {
+                emoji_state.update(cx, |list_state, cx| {
+                    list_state.delegate_mut().set_query(query);
+                    list_state.delegate_mut().filter();
+                    cx.notify();
+                });
+            }
+            _ => {
+                let list_state = self.active_list().clone();
+                self.async_search(query, list_state, cx);
+            }
+        }
+    }
+
+    /// Create an item list that confirms and cancels like the main list.
+    fn new_item_list(
+        &self,
+        mut delegate: ItemListDelegate,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<ListState<ItemListDelegate>> {
+        let on_confirm = self.on_confirm.clone();
+        delegate.set_on_confirm(move |item| on_confirm(item));
+        let on_hide = self.on_hide.clone();
+        delegate.set_on_cancel(move || on_hide());
+        delegate.set_click_action(Box::new(Confirm));
+
+        cx.new(|cx| ListState::new(delegate, window, cx))
+    }
+
+    /// Open a submenu by id. Returns false if there is no such submenu.
+    fn enter_submenu(&mut self, id: &str, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        match id {
+            "submenu-emojis" => {
+                self.enter_emoji_mode(window, cx);
+                true
+            }
+            "submenu-workspaces" => {
+                let workspaces = match self.compositor.list_workspaces() {
+                    Ok(workspaces) => workspaces,
+                    Err(e) => {
+                        tracing::warn!(%e, "Failed to list workspaces");
+                        return false;
+                    }
+                };
+                let items = workspace_items(&workspaces)
+                    .into_iter()
+                    .map(ListItem::Workspace)
+                    .collect();
+                self.enter_actions_mode("Search workspaces...".to_string(), items, window, cx);
+                true
+            }
+            "submenu-calculator-history" => {
+                let items = recent_calculations()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, result)| {
+                        ListItem::Calculator(
+                            CalculatorItem::from_calc_result(result)
+                                .with_id(format!("calculator-history-{}", i)),
+                        )
+                    })
+                    .collect();
+                self.enter_actions_mode("Search calculations...".to_string(), items, window, cx);
+                true
+            }
+            "submenu-clipboard" => {
+                let delegate = ItemListDelegate::from_items(clipboard_items()).without_calculator();
+                self.enter_list_mode("Search clipboard...".to_string(), delegate, window, cx);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The item list that navigation and search currently act on.
+    fn active_list(&self) -> &Entity<ListState<ItemListDelegate>> {
+        match (self.view_mode, &self.actions_list_state) {
+            (ViewMode::Actions, Some(actions)) => actions,
+            _ => &self.list_state,
+        }
+    }
+
+    fn copy_decimal(&mut self, _: &CopyDecimal, _: &mut Window, cx: &mut Context<Self>) {
+        self.copy_integer(NumberBase::Decimal, cx);
+    }
+
+    fn copy_hex(&mut self, _: &CopyHex, _: &mut Window, cx: &mut Context<Self>) {
+        self.copy_integer(NumberBase::Hex, cx);
+    }
+
+    fn copy_binary(&mut self, _: &CopyBinary, _: &mut Window, cx: &mut Context<Self>) {
+        self.copy_integer(NumberBase::Binary, cx);
+    }
+
+    /// Copy the selected integer calculator result written in `base`.
+    fn copy_integer(&mut self, base: NumberBase, cx: &mut Context<Self>) {
+        if self.view_mode != ViewMode::Main {
+            return;
+        }
+
+        let Some(ListItem::Calculator(calc)) = self.list_state.read(cx).delegate().selected_item()
+        else {
+            return;
+        };
+        let Some(forms) = calc.result.integer() else {
+            return;
+        };
+
+        if let Err(e) = copy_to_clipboard(forms.get(base)) {
+            tracing::warn!(%e, "Failed to copy to clipboard");
+        }
+        remember(&calc.result);
+        (self.on_hide)();
+    }
+
+    fn toggle_pin(&mut self, _: &TogglePin, window: &mut Window, cx: &mut Context<Self>) {
+        self.update_clipboard_entry(clipboard::toggle_pin, window, cx);
+    }
+
+    fn delete_entry(&mut self, _: &DeleteEntry, window: &mut Window, cx: &mut Context<Self>) {
+        self.update_clipboard_entry(clipboard::delete, window, cx);
+    }
+
+    /// Change the selected clipboard entry with `update`, then list the
+    /// entries again, keeping the search.
+    fn update_clipboard_entry<T>(
+        &mut self,
+        update: impl FnOnce(u64) -> anyhow::Result<T>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.view_mode != ViewMode::Actions {
+            return;
+        }
+        let Some(ListItem::Clipboard(clip)) =
+            self.active_list().read(cx).delegate().selected_item()
+        else {
+            return;
+        };
+
+        if let Err(e) = update(clip.entry_id) {
+            tracing::warn!(%e, "Failed to update clipboard entry");
+            return;
+        }
+
+        let delegate = ItemListDelegate::from_items(clipboard_items()).without_calculator();
+        let list_state = self.new_item_list(delegate, window, cx);
+        self.actions_list_state = Some(list_state.clone());
+        let query = self.input_state.read(cx).value().to_string();
+        self.async_search(query, list_state, cx);
+        cx.notify();
+    }
+
+    /// Open the desktop actions of the selected application, or the operations
+    /// the compositor supports on the selected window.
+    /// In a multi-select dmenu, mark or unmark the selected entry instead.
+    fn show_actions(&mut self, _: &ShowActions, window: &mut Window, cx: &mut Context<Self>) {
+        if self.view_mode != ViewMode::Main {
+            return;
+        }
+
+        if let Some(ref session) = self.dmenu {
+            if session.multi_select {
+                self.list_state.update(cx, |list_state, cx| {
+                    list_state.delegate_mut().toggle_selected_mark();
+                    cx.notify();
+                });
+            }
+            return;
+        }
+
+        let delegate = self.list_state.read(cx).delegate();
+        let selected_item = delegate.selected_item();
+
+        match selected_item {
+            Some(ListItem::Application(app)) if app.has_actions() => {
+                let items = app
+                    .action_items()
+                    .into_iter()
+                    .map(ListItem::Application)
+                    .collect();
+                self.enter_actions_mode(format!("{} actions...", app.name), items, window, cx);
+            }
+            Some(ListItem::Window(win)) if self.compositor.capabilities().any() => {
+                let workspaces = self.move_targets(cx);
+                let items = win
+                    .action_items(self.compositor.capabilities(), &workspaces)
+                    .into_iter()
+                    .map(ListItem::Window)
+                    .collect();
+                self.enter_actions_mode(format!("{} actions...", win.app_name), items, window, cx);
+            }
+            _ => {}
+        }
+    }
+
+    /// Get the workspaces windows can be moved to.
+    ///
+    /// Compositors that list their workspaces offer those, including named
+    /// ones. Others only offer the workspaces up to the highest one with a
+    /// window on it, which are known to exist.
+    fn move_targets(&self, cx: &Context<Self>) -> Vec<WorkspaceItem> {
+        if !self.compositor.capabilities().move_to_workspace {
+            return Vec::new();
+        }
+        if self.compositor.has_workspaces() {
+            return match self.compositor.list_workspaces() {
+                Ok(workspaces) => workspace_items(&workspaces),
+                Err(e) => {
+                    tracing::warn!(%e, "Failed to list workspaces");
+                    Vec::new()
+                }
+            };
+        }
+
+        let delegate = self.list_state.read(cx).delegate();
+        window_workspaces(delegate.items().iter().filter_map(|item| match item {
+            ListItem::Window(win) => Some(win.workspace),
+            _ => None,
+        }))
+    }
+
+    /// Enter the secondary list view with the given items.
+    fn enter_actions_mode(
+        &mut self,
+        placeholder: String,
+        items: Vec<ListItem>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.enter_list_mode(placeholder, ItemListDelegate::from_items(items), window, cx);
+    }
+
+    /// Enter the secondary list view with the given delegate.
+    fn enter_list_mode(
+        &mut self,
+        placeholder: String,
+        delegate: ItemListDelegate,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.input_state.update(cx, |input, cx| {
+            input.set_value("", window, cx);
+            input.set_placeholder(placeholder, window, cx);
+        });
+
+        self.actions_list_state = Some(self.new_item_list(delegate, window, cx));
+        self.view_mode = ViewMode::Actions;
+        cx.notify();
+    }
+
     /// Enter emoji picker mode.
     fn enter_emoji_mode(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         // Clear search input and update placeholder
@@ -203,15 +739,72 @@ impl LauncherView {
         cx.notify();
     }
 
-    /// Exit emoji picker mode and return to main view.
-    fn exit_emoji_mode(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+    /// Run a custom command and show its output.
+    fn enter_output_mode(
+        &mut self,
+        title: String,
+        command: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.input_state.update(cx, |input, cx| {
+            input.set_value("", window, cx);
+            input.set_placeholder(title.clone(), window, cx);
+        });
+
+        self.output_view = Some(OutputView {
+            title,
+            output: CommandOutput::Running,
+        });
+        self.view_mode = ViewMode::Output;
+
+        let background = cx.background_executor().clone();
+        self._output_task = cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let result = background
+                .spawn(async move { capture_output(&command) })
+                .await;
+
+            let _ = this.update(cx, |this, cx| {
+                if let Some(view) = this.output_view.as_mut() {
+                    view.output = match result {
+                        Ok(stdout) => CommandOutput::Finished(stdout),
+                        Err(e) => CommandOutput::Failed(e.to_string()),
+                    };
+                    cx.notify();
+                }
+            });
+        });
+        cx.notify();
+    }
+
+    /// Copy the finished command output to the clipboard and hide.
+    fn copy_output(&mut self) {
+        let Some(OutputView {
+            output: CommandOutput::Finished(ref stdout),
+            ..
+        }) = self.output_view
+        else {
+            return;
+        };
+
+        if let Err(e) = copy_to_clipboard(stdout.trim_end()) {
+            tracing::warn!(%e, "Failed to copy command output to clipboard");
+        }
+        (self.on_hide)();
+    }
+
+    /// Leave the emoji picker, actions or output view and return to main view.
+    fn exit_to_main(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         self.view_mode = ViewMode::Main;
         self.emoji_list_state = None;
+        self.actions_list_state = None;
+        self.output_view = None;
+        self._output_task = Task::ready(());
 
         // Clear search, reset placeholder, and reset main list
         self.input_state.update(cx, |input, cx| {
             input.set_value("", window, cx);
-            input.set_placeholder("Search applications...", window, cx);
+            input.set_placeholder(self.placeholder.clone(), window, cx);
         });
         self.list_state.update(cx, |list_state, _cx| {
             list_state.delegate_mut().clear_query();
@@ -221,11 +814,11 @@ impl LauncherView {
 
     /// Handle back action (backspace or back button).
     fn go_back(&mut self, _: &GoBack, window: &mut Window, cx: &mut Context<Self>) {
-        if self.view_mode == ViewMode::EmojiPicker {
+        if self.view_mode != ViewMode::Main {
             // Check if input is empty before going back
             let is_empty = self.input_state.read(cx).value().is_empty();
             if is_empty {
-                self.exit_emoji_mode(window, cx);
+                self.exit_to_main(window, cx);
             }
         }
     }
@@ -236,8 +829,10 @@ impl LauncherView {
         list_state: Entity<ListState<ItemListDelegate>>,
         cx: &mut Context<Self>,
     ) {
-        // Get items Arc for background processing
-        let items = list_state.read(cx).delegate().items();
+        // Get items and frecency Arcs for background processing
+        let delegate = list_state.read(cx).delegate();
+        let items = delegate.items();
+        let frecency = delegate.frecency();
         let query_clone = query.clone();
 
         // Update query immediately (without filtering)
@@ -250,7 +845,9 @@ impl LauncherView {
         self._search_task = cx.spawn(async move |_this: WeakEntity<Self>, cx: &mut AsyncApp| {
             // Run filtering on background thread
             let filtered_indices = background
-                .spawn(async move { ItemListDelegate::filter_items_sync(&items, &query_clone) })
+                .spawn(async move {
+                    ItemListDelegate::filter_items_sync(&items, &frecency, &query_clone)
+                })
                 .await;
 
             // Apply results on main thread
@@ -267,8 +864,8 @@ impl LauncherView {
 
     fn select_next(&mut self, _: &SelectNext, window: &mut Window, cx: &mut Context<Self>) {
         match self.view_mode {
-            ViewMode::Main => {
-                self.list_state.update(cx, |list_state, cx| {
+            ViewMode::Main | ViewMode::Actions => {
+                self.active_list().clone().update(cx, |list_state, cx| {
                     let delegate = list_state.delegate_mut();
                     let count = delegate.filtered_count();
                     if count == 0 {
@@ -304,13 +901,14 @@ impl LauncherView {
                     });
                 }
             }
+            ViewMode::Output => {}
         }
     }
 
     fn select_prev(&mut self, _: &SelectPrev, window: &mut Window, cx: &mut Context<Self>) {
         match self.view_mode {
-            ViewMode::Main => {
-                self.list_state.update(cx, |list_state, cx| {
+            ViewMode::Main | ViewMode::Actions => {
+                self.active_list().clone().update(cx, |list_state, cx| {
                     let delegate = list_state.delegate_mut();
                     let count = delegate.filtered_count();
                     if count == 0 {
@@ -346,14 +944,15 @@ impl LauncherView {
                     });
                 }
             }
+            ViewMode::Output => {}
         }
     }
 
     /// Tab moves to next item linearly (for both main view and emoji grid).
     fn select_tab(&mut self, _: &SelectTab, window: &mut Window, cx: &mut Context<Self>) {
         match self.view_mode {
-            ViewMode::Main => {
-                self.list_state.update(cx, |list_state, cx| {
+            ViewMode::Main | ViewMode::Actions => {
+                self.active_list().clone().update(cx, |list_state, cx| {
                     let delegate = list_state.delegate_mut();
                     let count = delegate.filtered_count();
                     if count == 0 {
@@ -389,14 +988,15 @@ impl LauncherView {
                     });
                 }
             }
+            ViewMode::Output => {}
         }
     }
 
     /// Shift+Tab moves to previous item linearly.
     fn select_tab_prev(&mut self, _: &SelectTabPrev, window: &mut Window, cx: &mut Context<Self>) {
         match self.view_mode {
-            ViewMode::Main => {
-                self.list_state.update(cx, |list_state, cx| {
+            ViewMode::Main | ViewMode::Actions => {
+                self.active_list().clone().update(cx, |list_state, cx| {
                     let delegate = list_state.delegate_mut();
                     let count = delegate.filtered_count();
                     if count == 0 {
@@ -432,24 +1032,45 @@ impl LauncherView {
                     });
                 }
             }
+            ViewMode::Output => {}
         }
     }
 
     fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
         match self.view_mode {
+            ViewMode::Main if self.dmenu.is_some() => self.confirm_dmenu(cx),
             ViewMode::Main => {
-                // Check if selected item is the Emojis submenu
+                // Check if selected item is a submenu
                 let selected_item = self.list_state.read(cx).delegate().selected_item();
 
-                if let Some(ListItem::Submenu(ref submenu)) = selected_item {
-                    if submenu.id == "submenu-emojis" {
-                        self.enter_emoji_mode(window, cx);
-                        return;
-                    }
+                if let Some(ListItem::Submenu(ref submenu)) = selected_item
+                    && self.enter_submenu(&submenu.id, window, cx)
+                {
+                    record_use(&ListItem::Submenu(submenu.clone()));
+                    return;
+                }
+
+                // Commands that show their output open the output view
+                if let Some(ListItem::Action(ref action)) = selected_item
+                    && let ActionKind::Command {
+                        ref command,
+                        show_output: true,
+                        ..
+                    } = action.kind
+                {
+                    record_use(&ListItem::Action(action.clone()));
+                    self.enter_output_mode(action.name.clone(), command.clone(), window, cx);
+                    return;
                 }
 
                 // Default confirm for other items
-                self.list_state.update(cx, |list_state, _cx| {
+                self.list_state.update(cx, |list_state, cx| {
+                    list_state.delegate_mut().do_confirm();
+                    cx.notify();
+                });
+            }
+            ViewMode::Actions => {
+                self.active_list().clone().update(cx, |list_state, _cx| {
                     list_state.delegate_mut().do_confirm();
                 });
             }
@@ -460,6 +1081,7 @@ impl LauncherView {
                     });
                 }
             }
+            ViewMode::Output => self.copy_output(),
         }
     }
 
@@ -470,13 +1092,69 @@ impl LauncherView {
                     list_state.delegate_mut().do_cancel();
                 });
             }
-            ViewMode::EmojiPicker => {
-                self.exit_emoji_mode(window, cx);
+            ViewMode::EmojiPicker | ViewMode::Actions | ViewMode::Output => {
+                self.exit_to_main(window, cx);
             }
         }
     }
 }
 
+impl LauncherView {
+    /// Render the output view of a custom command.
+    fn render_output(&self) -> impl IntoElement {
+        let t = theme();
+        let Some(view) = self.output_view.as_ref() else {
+            return div().flex_1();
+        };
+
+        let (text, color) = match &view.output {
+            CommandOutput::Running => ("Running...".to_string(), t.empty_state_color),
+            CommandOutput::Finished(stdout) if stdout.trim().is_empty() => {
+                ("No output".to_string(), t.empty_state_color)
+            }
+            CommandOutput::Finished(stdout) => (stdout.trim_end().to_string(), t.item_title_color),
+            CommandOutput::Failed(e) => (format!("Error: {}", e), t.item_description_color),
+        };
+        let hint = match view.output {
+            CommandOutput::Finished(_) => "Enter to copy, Escape to go back",
+            _ => "Escape to go back",
+        };
+
+        div()
+            .flex_1()
+            .flex()
+            .flex_col()
+            .overflow_hidden()
+            .px(t.item_margin_x + t.item_padding_x)
+            .child(
+                div()
+                    .flex()
+                    .justify_between()
+                    .pt(t.section_header_margin_top)
+                    .pb(t.section_header_margin_bottom)
+                    .text_xs()
+                    .text_color(t.section_header_color)
+                    .child(
+                        div()
+                            .font_weight(gpui::FontWeight::EXTRA_BOLD)
+                            .child(SharedString::from(view.title.clone())),
+                    )
+                    .child(SharedString::from(hint)),
+            )
+            .child(
+                div()
+                    .id("command-output")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .pb_2()
+                    .font_family("monospace")
+                    .text_sm()
+                    .text_color(color)
+                    .child(SharedString::from(text)),
+            )
+    }
+}
+
 impl Focusable for LauncherView {
     fn focus_handle(&self, _cx: &App) -> FocusHandle {
         self.focus_handle.clone()
@@ -484,21 +1162,31 @@ impl Focusable for LauncherView {
 }
 
 impl gpui::Render for LauncherView {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let t = theme();
 
+        // Sizes are logical pixels, so the panel keeps its size on scaled
+        // outputs. Shrink it to fit small outputs and place it on the output.
+        let viewport = window.viewport_size();
+        let output = (f32::from(viewport.width), f32::from(viewport.height));
+        let panel = (
+            f32::from(t.window_width).min(output.0),
+            f32::from(t.window_height).min(output.1),
+        );
+        let (panel_left, panel_top) = config().window.position.origin(output, panel);
+
         // Build input prefix based on view mode
         let input_prefix: AnyElement = match self.view_mode {
             ViewMode::Main => Icon::new(IconName::Search)
                 .text_color(cx.theme().muted_foreground)
                 .mr_2()
                 .into_any_element(),
-            ViewMode::EmojiPicker => div()
+            ViewMode::EmojiPicker | ViewMode::Actions | ViewMode::Output => div()
                 .id("back-button")
                 .cursor_pointer()
                 .mr_2()
                 .on_click(cx.listener(|this, _event, window, cx| {
-                    this.exit_emoji_mode(window, cx);
+                    this.exit_to_main(window, cx);
                 }))
                 .child(Icon::new(IconName::ArrowLeft).text_color(cx.theme().muted_foreground))
                 .into_any_element(),
@@ -510,8 +1198,29 @@ impl gpui::Render for LauncherView {
                 .flex_1()
                 .overflow_hidden()
                 .py_2()
-                .child(List::new(&self.list_state))
+                .child(List::new(self.active_list()))
                 .into_any_element(),
+            ViewMode::Actions => {
+                let selected = self.active_list().read(cx).delegate().selected_item();
+                let list = image_cache(retain_all("app-icons"))
+                    .flex_1()
+                    .overflow_hidden()
+                    .py_2()
+                    .child(List::new(self.active_list()));
+
+                // Clipboard entries are previewed below the list
+                match selected {
+                    Some(ListItem::Clipboard(clip)) => div()
+                        .flex_1()
+                        .flex()
+                        .flex_col()
+                        .overflow_hidden()
+                        .child(list)
+                        .child(render_clipboard_preview(&clip))
+                        .into_any_element(),
+                    _ => list.into_any_element(),
+                }
+            }
             ViewMode::EmojiPicker => {
                 if let Some(ref emoji_state) = self.emoji_list_state {
                     div()
@@ -524,6 +1233,7 @@ impl gpui::Render for LauncherView {
                     div().flex_1().into_any_element()
                 }
             }
+            ViewMode::Output => self.render_output().into_any_element(),
         };
 
         // Fullscreen backdrop - clicking it closes the launcher
@@ -539,20 +1249,26 @@ impl gpui::Render for LauncherView {
             .on_action(cx.listener(Self::confirm))
             .on_action(cx.listener(Self::cancel))
             .on_action(cx.listener(Self::go_back))
+            .on_action(cx.listener(Self::show_actions))
+            .on_action(cx.listener(Self::copy_decimal))
+            .on_action(cx.listener(Self::copy_hex))
+            .on_action(cx.listener(Self::copy_binary))
+            .on_action(cx.listener(Self::toggle_pin))
+            .on_action(cx.listener(Self::delete_entry))
             .size_full()
-            .flex()
-            .items_center()
-            .justify_center()
             // Click on backdrop to close
             .on_mouse_down(gpui::MouseButton::Left, move |_event, _window, _cx| {
                 on_hide();
             })
-            // Centered launcher panel
+            // Launcher panel at the configured position
             .child(
                 div()
                     .id("launcher-panel")
-                    .w(t.window_width)
-                    .h(t.window_height)
+                    .absolute()
+                    .left(px(panel_left))
+                    .top(px(panel_top))
+                    .w(px(panel.0))
+                    .h(px(panel.1))
                     .flex()
                     .flex_col()
                     .bg(t.window_background)
@@ -6,4 +6,4 @@ pub mod theme;
 
 pub use emoji::EmojiGridDelegate;
 pub use launcher::{LauncherView, init as init_launcher};
-pub use theme::{LauncherTheme, theme};
+pub use theme::{LauncherTheme, reload_theme, theme};
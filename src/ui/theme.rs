@@ -1,4 +1,6 @@
+use crate::config::{Color, ThemeConfig, config};
 use gpui::{Hsla, Pixels, hsla, px};
+use std::sync::{Arc, RwLock};
 
 /// Centralized theme configuration for the launcher UI.
 /// All colors, sizes, and spacing are defined here for consistency.
@@ -109,6 +111,43 @@ impl Default for LauncherTheme {
 }
 
 impl LauncherTheme {
+    /// Build a theme from the defaults with the config overrides applied.
+    pub fn from_config(config: &ThemeConfig) -> Self {
+        let mut theme = Self::default();
+
+        macro_rules! apply {
+            (sizes: $($size:ident),* ; colors: $($color:ident),* $(,)?) => {
+                $(if let Some(value) = config.$size {
+                    theme.$size = px(value.0);
+                })*
+                $(if let Some(value) = config.$color {
+                    theme.$color = to_hsla(value);
+                })*
+            };
+        }
+
+        apply!(
+            sizes: window_width, window_height, window_border_radius,
+                item_margin_x, item_margin_y, item_padding_x, item_padding_y,
+                item_border_radius, item_title_line_height, item_content_height,
+                icon_size, action_indicator_width, empty_state_height,
+                section_header_margin_top, section_header_margin_bottom,
+                emoji_cell_size, emoji_font_size;
+            colors: window_background, window_border,
+                item_background, item_background_selected,
+                item_title_color, item_description_color,
+                icon_placeholder_background, icon_placeholder_color,
+                action_label_color, action_key_background, action_key_border, action_key_color,
+                empty_state_color, section_header_color, emoji_cell_selected_bg,
+        );
+
+        if let Some(columns) = config.emoji_columns {
+            theme.emoji_columns = columns;
+        }
+
+        theme
+    }
+
     /// Calculate the maximum text width for item content.
     /// Accounts for window width, margins, padding, icon, and optionally action indicator.
     pub fn max_text_width(&self, with_action_indicator: bool) -> Pixels {
@@ -127,11 +166,25 @@ impl LauncherTheme {
     }
 }
 
-/// Global theme instance.
-/// In the future, this could support multiple themes or runtime switching.
-static THEME: std::sync::OnceLock<LauncherTheme> = std::sync::OnceLock::new();
+/// Convert a config color into a GPUI color.
+fn to_hsla(color: Color) -> Hsla {
+    hsla(color.h, color.s, color.l, color.a)
+}
+
+lazy_static::lazy_static! {
+    /// Global theme instance, built from the `[theme]` config table.
+    static ref THEME: RwLock<Arc<LauncherTheme>> =
+        RwLock::new(Arc::new(LauncherTheme::from_config(&config().theme)));
+}
 
 /// Get the global launcher theme.
-pub fn theme() -> &'static LauncherTheme {
-    THEME.get_or_init(LauncherTheme::default)
+pub fn theme() -> Arc<LauncherTheme> {
+    Arc::clone(&THEME.read().unwrap())
+}
+
+/// Rebuild the global theme from the current config.
+///
+/// Elements rendered afterwards pick up the new values.
+pub fn reload_theme() {
+    *THEME.write().unwrap() = Arc::new(LauncherTheme::from_config(&config().theme));
 }